@@ -0,0 +1,63 @@
+import matplotlib.pyplot as plt
+
+def get_xs_and_ys(filename):
+    file = open(filename)
+
+    xs = []
+    ys = []
+
+    for line in file.read().splitlines():
+        [x, y] = line.split()
+        xs.append(int(x))
+        ys.append(float(y))
+
+    return [xs, ys]
+
+def plot_time():
+    [quick_xs, quick_ys] = get_xs_and_ys("quick_time")
+    [lsd_xs, lsd_ys] = get_xs_and_ys("lsd_radix_time")
+    [msd_xs, msd_ys] = get_xs_and_ys("msd_radix_time")
+    [counting_xs, counting_ys] = get_xs_and_ys("counting_time")
+    [bucket_xs, bucket_ys] = get_xs_and_ys("bucket_time")
+
+    _, ax = plt.subplots()
+
+    ax.plot(quick_xs, quick_ys, label = "quick")
+    ax.plot(lsd_xs, lsd_ys, label = "lsd_radix")
+    ax.plot(msd_xs, msd_ys, label = "msd_radix")
+    ax.plot(counting_xs, counting_ys, label = "counting")
+    ax.plot(bucket_xs, bucket_ys, label = "bucket")
+    ax.legend()
+
+    plt.xlabel("n")
+    plt.ylabel("time [us]")
+
+    plt.savefig("time", dpi=500)
+
+def plot_moves():
+    [quick_xs, quick_ys] = get_xs_and_ys("quick_moves")
+    [lsd_xs, lsd_ys] = get_xs_and_ys("lsd_radix_moves")
+    [msd_xs, msd_ys] = get_xs_and_ys("msd_radix_moves")
+    [counting_xs, counting_ys] = get_xs_and_ys("counting_moves")
+    [bucket_xs, bucket_ys] = get_xs_and_ys("bucket_moves")
+
+    _, ax = plt.subplots()
+
+    ax.plot(quick_xs, quick_ys, label = "quick")
+    ax.plot(lsd_xs, lsd_ys, label = "lsd_radix")
+    ax.plot(msd_xs, msd_ys, label = "msd_radix")
+    ax.plot(counting_xs, counting_ys, label = "counting")
+    ax.plot(bucket_xs, bucket_ys, label = "bucket")
+    ax.legend()
+
+    plt.xlabel("n")
+    plt.ylabel("moves")
+
+    plt.savefig("moves", dpi=500)
+
+def main():
+    plot_time()
+    plot_moves()
+
+if __name__ == "__main__":
+    main()
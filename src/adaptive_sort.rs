@@ -0,0 +1,694 @@
+//! Adaptive sorting algorithms choosing sorter from characteristics of their
+//! input.
+
+use crate::benchmarking::{Decision, NestedBenchmarker};
+use crate::prelude::*;
+use crate::tuning::Parameter;
+
+/// Number of items sampled to estimate run structure and duplicates.
+const SAMPLE_LEN: usize = 32;
+
+/// Input is presorted if its runs are at least this long on average.
+const RUN_LEN: usize = 16;
+
+/// Sample with at most `SAMPLE_LEN / DISTINCT_RATIO` distinct values has
+/// many duplicates.
+const DISTINCT_RATIO: usize = 4;
+
+/// Counting sort is used for key ranges smaller than this multiple of input
+/// size.
+const COUNTING_RANGE_RATIO: u64 = 2;
+
+/// Digit width of radix sort.
+const RADIX_BITS: u32 = 8;
+
+/// Radix sort is used if this multiple of its passes doesn't exceed
+/// `log2 n`, as every pass moves every item twice.
+const RADIX_PASS_COST: u32 = 2;
+
+/// Algorithm which adaptive sorters dispatch their input to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Algorithm {
+    /// `InsertionSort` for small inputs.
+    Insertion,
+    /// Nothing for inputs which are already sorted.
+    Sorted,
+    /// Reversal for inputs sorted in descending order.
+    Reversal,
+    /// `TimSort` for inputs made of few long ascending runs.
+    TimSort,
+    /// Reversal followed by `TimSort` for inputs made of few long
+    /// descending runs.
+    ReversedTimSort,
+    /// `CountingSort` for integers whose key range is comparable to size of
+    /// input.
+    Counting,
+    /// `LsdRadixSort` for integers which need only few digit passes.
+    Radix,
+    /// `ThreeWayQuicksort` for inputs with many duplicates.
+    ThreeWayQuicksort,
+    /// `PdqSort` for everything else.
+    PdqSort,
+}
+
+impl Algorithm {
+    /// Returns short name of algorithm recorded in `Decision`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Algorithm::Insertion => "insertion",
+            Algorithm::Sorted => "sorted",
+            Algorithm::Reversal => "reversal",
+            Algorithm::TimSort => "tim",
+            Algorithm::ReversedTimSort => "reversed_tim",
+            Algorithm::Counting => "counting",
+            Algorithm::Radix => "lsd_radix",
+            Algorithm::ThreeWayQuicksort => "three_way_quick",
+            Algorithm::PdqSort => "pdq",
+        }
+    }
+}
+
+/// Struct implementing `Sorter` + `BenchmarkingSorter` which samples its
+/// input and dispatches it to the best suited sorter. Small inputs go to
+/// insertion sort, inputs made of few long runs to Timsort, inputs with many
+/// duplicates to three-way quicksort and the rest to pdqsort. Sampling takes
+/// `O(1)` comparisons, only input which looks presorted is scanned whole to
+/// confirm it. Input found to be a single run by that scan is only reversed
+/// if it's descending and left as is otherwise, in linear time. Benchmarking records chosen algorithm with its reason as
+/// `Decision` in `Stats`.
+pub struct AdaptiveSort;
+
+impl AdaptiveSort {
+    /// Chooses algorithm for `slice` by its size, run structure and ratio of
+    /// duplicates. Returns it together with reason of the choice.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::adaptive_sort::{AdaptiveSort, Algorithm};
+    ///
+    /// let ascending = (0..1000).collect::<Vec<_>>();
+    /// let (algorithm, reason) = AdaptiveSort::choose(&ascending);
+    ///
+    /// assert_eq!(Algorithm::Sorted, algorithm);
+    /// assert_eq!("1 ascending runs in 1000 items", reason);
+    /// ```
+    pub fn choose<T: Ord + Copy>(slice: &[T]) -> (Algorithm, String) {
+        AdaptiveSort::choose_by_runs(slice)
+            .unwrap_or_else(|| AdaptiveSort::choose_by_duplicates(slice))
+    }
+
+    /// Chooses insertion sort for small input and Timsort for input made of
+    /// few long runs. Adjacent pairs are sampled first and only input whose
+    /// sampled pairs are almost all in order is scanned whole.
+    fn choose_by_runs<T: Ord + Copy>(slice: &[T]) -> Option<(Algorithm, String)> {
+        let len = slice.len();
+
+        let insertion_len = Parameter::AdaptiveInsertionLen.get();
+
+        if len <= insertion_len {
+            return Some((
+                Algorithm::Insertion,
+                format!("{} items are at most {}", len, insertion_len),
+            ));
+        }
+
+        let limit = len / RUN_LEN;
+        let descents = (0..SAMPLE_LEN)
+            .map(|k| k * (len - 1) / SAMPLE_LEN)
+            .filter(|&i| slice[i + 1] < slice[i])
+            .count();
+
+        if descents <= 1 {
+            let descents = AdaptiveSort::count_pairs(slice, limit, |a, b| b < a)?;
+
+            Some(AdaptiveSort::choice_by_runs(descents, len, true))
+        } else if descents >= SAMPLE_LEN - 1 {
+            let ascents = AdaptiveSort::count_pairs(slice, limit, |a, b| a < b)?;
+
+            Some(AdaptiveSort::choice_by_runs(ascents, len, false))
+        } else {
+            None
+        }
+    }
+
+    /// Chooses algorithm for `len` items split into ascending or descending
+    /// runs by `breaks` adjacent pairs out of order.
+    fn choice_by_runs(breaks: usize, len: usize, ascending: bool) -> (Algorithm, String) {
+        let (single, multiple, direction) = if ascending {
+            (Algorithm::Sorted, Algorithm::TimSort, "ascending")
+        } else {
+            (Algorithm::Reversal, Algorithm::ReversedTimSort, "descending")
+        };
+        let algorithm = if breaks == 0 { single } else { multiple };
+
+        (
+            algorithm,
+            format!("{} {} runs in {} items", breaks + 1, direction, len),
+        )
+    }
+
+    /// Returns number of adjacent pairs satisfying `counted`, or `None` as
+    /// soon as it exceeds `limit`.
+    fn count_pairs<T: Ord + Copy>(
+        slice: &[T],
+        limit: usize,
+        counted: impl Fn(T, T) -> bool,
+    ) -> Option<usize> {
+        let mut count = 0;
+
+        for i in 1..slice.len() {
+            if counted(slice[i - 1], slice[i]) {
+                count += 1;
+
+                if count > limit {
+                    return None;
+                }
+            }
+        }
+
+        Some(count)
+    }
+
+    /// Chooses three-way quicksort if sample of non-empty slice has many
+    /// duplicates and pdqsort otherwise.
+    fn choose_by_duplicates<T: Ord + Copy>(slice: &[T]) -> (Algorithm, String) {
+        let mut sample = (0..SAMPLE_LEN)
+            .map(|k| slice[k * slice.len() / SAMPLE_LEN])
+            .collect::<Vec<_>>();
+
+        sample.sort_unstable();
+
+        let distinct = 1 + sample.windows(2).filter(|pair| pair[0] != pair[1]).count();
+
+        AdaptiveSort::choice_by_distinct(distinct)
+    }
+
+    fn choice_by_distinct(distinct: usize) -> (Algorithm, String) {
+        let algorithm = if distinct <= SAMPLE_LEN / DISTINCT_RATIO {
+            Algorithm::ThreeWayQuicksort
+        } else {
+            Algorithm::PdqSort
+        };
+
+        (
+            algorithm,
+            format!("{} distinct values in sample of {}", distinct, SAMPLE_LEN),
+        )
+    }
+
+    fn dispatch<T: Ord + Copy>(slice: &mut [T], algorithm: Algorithm) {
+        match algorithm {
+            Algorithm::Insertion => InsertionSort::sort(slice),
+            Algorithm::Sorted => {}
+            Algorithm::Reversal => slice.reverse(),
+            Algorithm::TimSort => TimSort::sort(slice),
+            Algorithm::ReversedTimSort => {
+                slice.reverse();
+                TimSort::sort(slice);
+            }
+            Algorithm::ThreeWayQuicksort => ThreeWayQuicksort::sort(slice),
+            Algorithm::PdqSort => PdqSort::sort(slice),
+            Algorithm::Counting | Algorithm::Radix => {
+                unreachable!("integer sorters are chosen only for integer keys")
+            }
+        }
+    }
+
+    fn choose_with_benchmark<T: Ord + Copy>(
+        slice: &[T],
+        benchmark: &mut impl Benchmark,
+    ) -> (Algorithm, String) {
+        match AdaptiveSort::choose_by_runs_with_benchmark(slice, benchmark) {
+            Some(choice) => choice,
+            None => AdaptiveSort::choose_by_duplicates_with_benchmark(slice, benchmark),
+        }
+    }
+
+    fn choose_by_runs_with_benchmark<T: Ord + Copy>(
+        slice: &[T],
+        benchmark: &mut impl Benchmark,
+    ) -> Option<(Algorithm, String)> {
+        let len = slice.len();
+
+        let insertion_len = Parameter::AdaptiveInsertionLen.get();
+
+        if len <= insertion_len {
+            return Some((
+                Algorithm::Insertion,
+                format!("{} items are at most {}", len, insertion_len),
+            ));
+        }
+
+        let limit = len / RUN_LEN;
+        let descents = (0..SAMPLE_LEN)
+            .map(|k| k * (len - 1) / SAMPLE_LEN)
+            .filter(|&i| {
+                benchmark.add_cmp();
+                slice[i + 1] < slice[i]
+            })
+            .count();
+
+        if descents <= 1 {
+            let descents =
+                AdaptiveSort::count_pairs_with_benchmark(slice, limit, |a, b| b < a, benchmark)?;
+
+            Some(AdaptiveSort::choice_by_runs(descents, len, true))
+        } else if descents >= SAMPLE_LEN - 1 {
+            let ascents =
+                AdaptiveSort::count_pairs_with_benchmark(slice, limit, |a, b| a < b, benchmark)?;
+
+            Some(AdaptiveSort::choice_by_runs(ascents, len, false))
+        } else {
+            None
+        }
+    }
+
+    fn count_pairs_with_benchmark<T: Ord + Copy>(
+        slice: &[T],
+        limit: usize,
+        counted: impl Fn(T, T) -> bool,
+        benchmark: &mut impl Benchmark,
+    ) -> Option<usize> {
+        let mut count = 0;
+
+        benchmark.add_pass();
+
+        for i in 1..slice.len() {
+            benchmark.add_cmp();
+
+            if counted(slice[i - 1], slice[i]) {
+                count += 1;
+
+                if count > limit {
+                    return None;
+                }
+            }
+        }
+
+        Some(count)
+    }
+
+    fn choose_by_duplicates_with_benchmark<T: Ord + Copy>(
+        slice: &[T],
+        benchmark: &mut impl Benchmark,
+    ) -> (Algorithm, String) {
+        let mut sample = (0..SAMPLE_LEN)
+            .map(|k| slice[k * slice.len() / SAMPLE_LEN])
+            .collect::<Vec<_>>();
+
+        benchmark.add_allocation(std::mem::size_of_val(sample.as_slice()));
+
+        sample.sort_unstable_by(|a, b| {
+            benchmark.add_cmp();
+            a.cmp(b)
+        });
+
+        let distinct = 1 + sample
+            .windows(2)
+            .filter(|pair| {
+                benchmark.add_cmp();
+                pair[0] != pair[1]
+            })
+            .count();
+
+        AdaptiveSort::choice_by_distinct(distinct)
+    }
+
+    fn dispatch_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        algorithm: Algorithm,
+        benchmark: &mut impl Benchmark,
+    ) {
+        let mut nested = NestedBenchmarker::new(benchmark);
+
+        match algorithm {
+            Algorithm::Insertion => InsertionSort::sort_with_benchmark(slice, &mut nested),
+            Algorithm::Sorted => {}
+            Algorithm::Reversal => AdaptiveSort::reverse_with_benchmark(slice, &mut nested),
+            Algorithm::TimSort => TimSort::sort_with_benchmark(slice, &mut nested),
+            Algorithm::ReversedTimSort => {
+                AdaptiveSort::reverse_with_benchmark(slice, &mut nested);
+                TimSort::sort_with_benchmark(slice, &mut nested);
+            }
+            Algorithm::ThreeWayQuicksort => {
+                ThreeWayQuicksort::sort_with_benchmark(slice, &mut nested)
+            }
+            Algorithm::PdqSort => PdqSort::sort_with_benchmark(slice, &mut nested),
+            Algorithm::Counting | Algorithm::Radix => {
+                unreachable!("integer sorters are chosen only for integer keys")
+            }
+        }
+    }
+
+    fn reverse_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        benchmark.add_pass();
+
+        for _ in 0..slice.len() / 2 {
+            benchmark.add_swap();
+            benchmark.add_writes(2);
+        }
+
+        slice.reverse();
+    }
+}
+
+impl Sorter for AdaptiveSort {
+    /// Adaptive sort implementation working on types implementing
+    /// `Ord` + `Copy` so it's mostly usefull for primitive types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    ///
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// AdaptiveSort::sort(&mut slice);
+    ///
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// ```
+    fn sort<T: Ord + Copy>(slice: &mut [T]) {
+        let (algorithm, _) = AdaptiveSort::choose(slice);
+
+        AdaptiveSort::dispatch(slice, algorithm);
+    }
+}
+
+impl BenchmarkingSorter for AdaptiveSort {
+    /// Adaptive sort implementation with additional benchmarking
+    /// capabilities. Comparisons made by sampling are counted too and chosen
+    /// algorithm is recorded as `Decision`.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::StandardBenchmarker;
+    ///
+    /// let mut benchmarker = StandardBenchmarker::default();
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// AdaptiveSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// assert_eq!("insertion", stats.decisions[0].algorithm);
+    /// assert_eq!("6 items are at most 32", stats.decisions[0].reason);
+    /// ```
+    fn sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        benchmark.start_timer();
+
+        let (algorithm, reason) = AdaptiveSort::choose_with_benchmark(slice, benchmark);
+
+        benchmark.add_decision(Decision {
+            algorithm: algorithm.name(),
+            reason,
+        });
+
+        AdaptiveSort::dispatch_with_benchmark(slice, algorithm, benchmark);
+
+        benchmark.stop_timer();
+    }
+}
+
+/// Struct implementing `IntegerSorter` + `BenchmarkingIntegerSorter` which
+/// chooses sorter like `AdaptiveSort`, but knowing that items are integers
+/// it also scans their key range. Keys in range comparable to size of input
+/// go to counting sort and keys differing only in few low digits, with
+/// respect to `log2 n`, to LSD radix sort.
+pub struct AdaptiveIntegerSort;
+
+impl AdaptiveIntegerSort {
+    /// Chooses algorithm for `slice` by its size, run structure, key range
+    /// and ratio of duplicates. Returns it together with reason of the
+    /// choice.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::adaptive_sort::{AdaptiveIntegerSort, Algorithm};
+    ///
+    /// let slice = (0..1000u32).map(|i| i * 7919 % 1000).collect::<Vec<_>>();
+    /// let (algorithm, _) = AdaptiveIntegerSort::choose(&slice);
+    ///
+    /// assert_eq!(Algorithm::Counting, algorithm);
+    /// ```
+    pub fn choose<T: RadixKey>(slice: &[T]) -> (Algorithm, String) {
+        if let Some(choice) = AdaptiveSort::choose_by_runs(slice) {
+            return choice;
+        }
+
+        let (min, max) = slice.iter().fold((u64::MAX, 0), |(min, max), value| {
+            (min.min(value.key()), max.max(value.key()))
+        });
+
+        AdaptiveIntegerSort::choose_by_key_range::<T>(slice.len(), min, max)
+            .unwrap_or_else(|| AdaptiveSort::choose_by_duplicates(slice))
+    }
+
+    /// Chooses counting sort or radix sort for keys in range `min..=max`.
+    /// Digits above the highest bit in which `min` and `max` differ are
+    /// equal in all keys, so radix sort skips them.
+    fn choose_by_key_range<T: RadixKey>(
+        len: usize,
+        min: u64,
+        max: u64,
+    ) -> Option<(Algorithm, String)> {
+        let range = max - min;
+
+        if range < COUNTING_RANGE_RATIO * len as u64 && range < CountingSort::MAX_RANGE {
+            return Some((
+                Algorithm::Counting,
+                format!("{} keys in range for {} items", range + 1, len),
+            ));
+        }
+
+        let bits = u64::BITS - (min ^ max).leading_zeros();
+        let passes = bits.div_ceil(RADIX_BITS);
+
+        if passes * RADIX_PASS_COST <= len.ilog2() {
+            Some((
+                Algorithm::Radix,
+                format!(
+                    "{} radix passes over {} of {} key bits for {} items",
+                    passes,
+                    bits,
+                    T::BITS,
+                    len
+                ),
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn dispatch<T: RadixKey>(slice: &mut [T], algorithm: Algorithm) {
+        match algorithm {
+            Algorithm::Counting => CountingSort::sort(slice),
+            Algorithm::Radix => LsdRadixSort::<RADIX_BITS>::sort(slice),
+            _ => AdaptiveSort::dispatch(slice, algorithm),
+        }
+    }
+
+    fn choose_with_benchmark<T: RadixKey>(
+        slice: &[T],
+        benchmark: &mut impl Benchmark,
+    ) -> (Algorithm, String) {
+        if let Some(choice) = AdaptiveSort::choose_by_runs_with_benchmark(slice, benchmark) {
+            return choice;
+        }
+
+        benchmark.add_pass();
+
+        let (min, max) = slice.iter().fold((u64::MAX, 0), |(min, max), value| {
+            benchmark.add_scan();
+            (min.min(value.key()), max.max(value.key()))
+        });
+
+        match AdaptiveIntegerSort::choose_by_key_range::<T>(slice.len(), min, max) {
+            Some(choice) => choice,
+            None => AdaptiveSort::choose_by_duplicates_with_benchmark(slice, benchmark),
+        }
+    }
+
+    fn dispatch_with_benchmark<T: RadixKey>(
+        slice: &mut [T],
+        algorithm: Algorithm,
+        benchmark: &mut impl Benchmark,
+    ) {
+        let mut nested = NestedBenchmarker::new(benchmark);
+
+        match algorithm {
+            Algorithm::Counting => CountingSort::sort_with_benchmark(slice, &mut nested),
+            Algorithm::Radix => LsdRadixSort::<RADIX_BITS>::sort_with_benchmark(slice, &mut nested),
+            _ => AdaptiveSort::dispatch_with_benchmark(slice, algorithm, &mut nested),
+        }
+    }
+}
+
+impl IntegerSorter for AdaptiveIntegerSort {
+    /// Adaptive integer sort implementation working on integer types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    ///
+    /// let mut slice = [1, -8, 2, 3, 9, -5];
+    ///
+    /// AdaptiveIntegerSort::sort(&mut slice);
+    ///
+    /// assert_eq!([-8, -5, 1, 2, 3, 9], slice);
+    /// ```
+    fn sort<T: RadixKey>(slice: &mut [T]) {
+        let (algorithm, _) = AdaptiveIntegerSort::choose(slice);
+
+        AdaptiveIntegerSort::dispatch(slice, algorithm);
+    }
+}
+
+impl BenchmarkingIntegerSorter for AdaptiveIntegerSort {
+    /// Adaptive integer sort implementation with additional benchmarking
+    /// capabilities. Scanning key range is counted as a pass and chosen
+    /// algorithm is recorded as `Decision`.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::StandardBenchmarker;
+    /// use algorithms::generator::{RandomGenerator, SeededGenerator};
+    ///
+    /// let mut benchmarker = StandardBenchmarker::default();
+    /// let mut slice = RandomGenerator::generate_seeded(1000, 1);
+    ///
+    /// AdaptiveIntegerSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert!(slice.windows(2).all(|pair| pair[0] <= pair[1]));
+    /// assert_eq!("counting", stats.decisions[0].algorithm);
+    /// ```
+    fn sort_with_benchmark<T: RadixKey>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        benchmark.start_timer();
+
+        let (algorithm, reason) = AdaptiveIntegerSort::choose_with_benchmark(slice, benchmark);
+
+        benchmark.add_decision(Decision {
+            algorithm: algorithm.name(),
+            reason,
+        });
+
+        AdaptiveIntegerSort::dispatch_with_benchmark(slice, algorithm, benchmark);
+
+        benchmark.stop_timer();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarking::StandardBenchmarker;
+    use crate::generator::{
+        AscendingGenerator, DescendingGenerator, RandomGenerator, SeededGenerator,
+    };
+
+    crate::sorter_conformance_tests!(AdaptiveSort);
+
+    mod integer {
+        use super::*;
+
+        crate::integer_sorter_conformance_tests!(AdaptiveIntegerSort);
+    }
+
+    /// Inputs of 10000 items which should be dispatched to given algorithm.
+    fn inputs() -> Vec<(Vec<u32>, Algorithm)> {
+        let random = RandomGenerator::generate_seeded(10000, 1);
+        let ascending = AscendingGenerator::generate_seeded(10000, 1);
+        let descending = DescendingGenerator::generate_seeded(10000, 1);
+        let mut almost_ascending = ascending.clone();
+        let mut almost_descending = descending.clone();
+
+        // few swaps break runs only locally
+        for i in (500..10000).step_by(1000) {
+            almost_ascending.swap(i, i + 1);
+            almost_descending.swap(i, i + 1);
+        }
+
+        vec![
+            (random[..20].to_vec(), Algorithm::Insertion),
+            (ascending, Algorithm::Sorted),
+            (descending, Algorithm::Reversal),
+            (almost_ascending, Algorithm::TimSort),
+            (almost_descending, Algorithm::ReversedTimSort),
+            (
+                random.iter().map(|item| item % 4).collect(),
+                Algorithm::ThreeWayQuicksort,
+            ),
+            (random.clone(), Algorithm::PdqSort),
+        ]
+    }
+
+    #[test]
+    fn chooses_algorithm_by_input_characteristics() {
+        for (input, expected) in inputs() {
+            let (algorithm, reason) = AdaptiveSort::choose(&input);
+
+            assert_eq!(expected, algorithm, "{}", reason);
+        }
+    }
+
+    #[test]
+    fn chooses_integer_algorithm_by_key_range() {
+        let random = RandomGenerator::generate_seeded(10000, 1);
+        let spread = random
+            .iter()
+            .map(|item| item.wrapping_mul(2654435761))
+            .collect::<Vec<_>>();
+
+        for (input, expected) in [
+            (random, Algorithm::Counting),
+            (spread.clone(), Algorithm::Radix),
+            (spread[..100].to_vec(), Algorithm::PdqSort),
+        ] {
+            let (algorithm, reason) = AdaptiveIntegerSort::choose(&input);
+
+            assert_eq!(expected, algorithm, "{}", reason);
+        }
+
+        let wide = (0..10000u64)
+            .map(|i| i.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+            .collect::<Vec<_>>();
+
+        assert_eq!(Algorithm::PdqSort, AdaptiveIntegerSort::choose(&wide).0);
+    }
+
+    #[test]
+    fn benchmark_records_decision() {
+        for (input, expected) in inputs() {
+            let mut slice = input.clone();
+            let mut benchmarker = StandardBenchmarker::default();
+
+            AdaptiveSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+
+            let stats = benchmarker.get_stats();
+            let (_, reason) = AdaptiveSort::choose(&input);
+
+            assert!(slice.windows(2).all(|pair| pair[0] <= pair[1]));
+            assert_eq!(
+                vec![Decision {
+                    algorithm: expected.name(),
+                    reason,
+                }],
+                stats.decisions
+            );
+        }
+    }
+
+    #[test]
+    fn presorted_input_takes_linear_comparisons() {
+        for (input, expected) in inputs() {
+            if let Algorithm::Sorted | Algorithm::Reversal = expected {
+                let comparisons = crate::conformance::count_comparisons::<AdaptiveSort>(&input);
+
+                assert!(comparisons <= input.len() + SAMPLE_LEN, "{:?}", expected);
+            }
+        }
+    }
+}
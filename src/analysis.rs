@@ -0,0 +1,313 @@
+//! Analysis of benchmarking results against the information-theoretic lower
+//! bound and expected comparison counts of well-known models.
+
+use crate::benchmarking::Stats;
+
+/// Largest `n` whose factorial fits into `u128`, so its lower bound can be
+/// computed exactly.
+const EXACT_FACTORIAL: usize = 34;
+
+/// Returns `log2 n!`.
+///
+/// Examples:
+/// ```
+/// use algorithms::analysis::log2_factorial;
+///
+/// assert_eq!(0.0, log2_factorial(1));
+/// assert!((log2_factorial(4) - 24f64.log2()).abs() < 1e-9);
+/// ```
+pub fn log2_factorial(n: usize) -> f64 {
+    (2..=n).map(|i| (i as f64).log2()).sum()
+}
+
+/// Returns `ceil(log2 n!)`, the least number of comparisons any comparison
+/// sort needs in the worst case to sort `n` distinct items. It's computed
+/// exactly for inputs whose factorial fits into `u128`.
+///
+/// Examples:
+/// ```
+/// use algorithms::analysis::comparison_lower_bound;
+///
+/// assert_eq!(0, comparison_lower_bound(1));
+/// assert_eq!(7, comparison_lower_bound(5));
+/// assert_eq!(8530, comparison_lower_bound(1000));
+/// ```
+pub fn comparison_lower_bound(n: usize) -> usize {
+    if n <= EXACT_FACTORIAL {
+        let factorial = (2..=n as u128).product::<u128>();
+
+        (u128::BITS - (factorial - 1).leading_zeros()) as usize
+    } else {
+        log2_factorial(n).ceil() as usize
+    }
+}
+
+/// Returns `n`-th harmonic number `H(n) = 1 + 1/2 + ... + 1/n`.
+fn harmonic(n: usize) -> f64 {
+    (1..=n).map(|i| 1.0 / i as f64).sum()
+}
+
+/// Model of expected number of comparisons on random permutation of `n`
+/// distinct items, as counted by `Benchmark` implementations in crate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ComparisonModel {
+    /// Information-theoretic bound `log2 n!` which average case of every
+    /// comparison sort has to reach at least.
+    LowerBound,
+    /// Quicksort with fixed pivot comparing every other item with it,
+    /// `2(n + 1)H(n) - 4n ~ 2n ln n`.
+    Quicksort,
+    /// Dual-pivot quicksort with "count" partitioning, `1.8n ln n - 1.97n`.
+    /// Linear term is fitted to counts of `CountPartitioning`.
+    CountDualPivot,
+    /// Dual-pivot quicksort with clairvoyant partitioning,
+    /// `1.8n ln n - 2.27n`. Linear term is fitted to counts of
+    /// `ClairvoyantPartitioning`.
+    ClairvoyantDualPivot,
+    /// Yaroslavskiy's dual-pivot quicksort, `1.9n ln n - 2.46n`.
+    YaroslavskiyDualPivot,
+    /// Sedgewick's dual-pivot quicksort, `32/15 n ln n - 2.57n`.
+    SedgewickDualPivot,
+    /// Top-down mergesort, `n log2 n - 1.26n`.
+    MergeSort,
+    /// Insertion sort counting only comparisons followed by shift, which is
+    /// number of inversions `n(n - 1)/4`.
+    InsertionSort,
+}
+
+impl ComparisonModel {
+    /// Returns expected number of comparisons for `n` items.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::analysis::ComparisonModel;
+    ///
+    /// assert_eq!(0.0, ComparisonModel::Quicksort.expected_comparisons(1));
+    /// assert_eq!(1.0, ComparisonModel::Quicksort.expected_comparisons(2));
+    /// assert_eq!(22.5, ComparisonModel::InsertionSort.expected_comparisons(10));
+    /// ```
+    pub fn expected_comparisons(&self, n: usize) -> f64 {
+        if n < 2 {
+            return 0.0;
+        }
+
+        let n_f64 = n as f64;
+
+        match self {
+            ComparisonModel::LowerBound => log2_factorial(n),
+            ComparisonModel::Quicksort => 2.0 * (n_f64 + 1.0) * harmonic(n) - 4.0 * n_f64,
+            ComparisonModel::CountDualPivot => 1.8 * n_f64 * n_f64.ln() - 1.97 * n_f64,
+            ComparisonModel::ClairvoyantDualPivot => 1.8 * n_f64 * n_f64.ln() - 2.27 * n_f64,
+            ComparisonModel::YaroslavskiyDualPivot => 1.9 * n_f64 * n_f64.ln() - 2.46 * n_f64,
+            ComparisonModel::SedgewickDualPivot => 32.0 / 15.0 * n_f64 * n_f64.ln() - 2.57 * n_f64,
+            ComparisonModel::MergeSort => n_f64 * n_f64.log2() - 1.26 * n_f64,
+            ComparisonModel::InsertionSort => n_f64 * (n_f64 - 1.0) / 4.0,
+        }
+    }
+}
+
+/// Returns ratio of comparisons of single run on `n` items to
+/// `ceil(log2 n!)`. Ratio is 1 for inputs without any possible
+/// comparisons. Average ratio below 1 on random inputs means that sorter
+/// doesn't count all of its comparisons.
+///
+/// Examples:
+/// ```
+/// use algorithms::analysis::lower_bound_ratio;
+/// use algorithms::benchmarking::StandardBenchmarker;
+/// use algorithms::prelude::*;
+///
+/// let mut benchmarker = StandardBenchmarker::default();
+/// let mut slice = [3, 1, 2, 5, 4];
+///
+/// MergeInsertionSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+///
+/// assert!(lower_bound_ratio(&benchmarker.get_stats(), 5) <= 1.0);
+/// ```
+pub fn lower_bound_ratio(stats: &Stats, n: usize) -> f64 {
+    match comparison_lower_bound(n) {
+        0 => 1.0,
+        lower_bound => stats.comparisons as f64 / lower_bound as f64,
+    }
+}
+
+/// Summary of benchmarking runs of one algorithm on inputs of `n` items,
+/// relating their comparisons to the lower bound and to comparison models.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatsSummary {
+    pub n: usize,
+    pub runs: usize,
+    pub mean_comparisons: f64,
+    pub mean_swaps: f64,
+    pub mean_writes: f64,
+    /// `ceil(log2 n!)`.
+    pub lower_bound: usize,
+    /// Mean of comparisons to lower bound ratios of all runs.
+    pub lower_bound_ratio: f64,
+    /// The biggest comparisons to lower bound ratio of all runs.
+    pub worst_lower_bound_ratio: f64,
+}
+
+impl StatsSummary {
+    /// Summarizes `stats` of runs on inputs of `n` items.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::analysis::{ComparisonModel, StatsSummary};
+    /// use algorithms::benchmarking::StandardBenchmarker;
+    /// use algorithms::generator::{RandomGenerator, SeededGenerator};
+    /// use algorithms::prelude::*;
+    ///
+    /// let stats = (0..10)
+    ///     .map(|seed| {
+    ///         let mut benchmarker = StandardBenchmarker::default();
+    ///         let mut slice = RandomGenerator::generate_seeded(1000, seed);
+    ///
+    ///         MergeSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+    ///
+    ///         benchmarker.get_stats()
+    ///     })
+    ///     .collect::<Vec<_>>();
+    ///
+    /// let summary = StatsSummary::new(1000, &stats);
+    ///
+    /// assert_eq!(10, summary.runs);
+    /// assert!(summary.lower_bound_ratio > 1.0 && summary.lower_bound_ratio < 1.1);
+    /// assert!((summary.model_ratio(ComparisonModel::MergeSort) - 1.0).abs() < 0.05);
+    /// ```
+    pub fn new(n: usize, stats: &[Stats]) -> Self {
+        assert!(!stats.is_empty(), "there has to be at least one run");
+
+        let runs = stats.len();
+        let mean = |counter: fn(&Stats) -> usize| {
+            stats.iter().map(|stats| counter(stats) as f64).sum::<f64>() / runs as f64
+        };
+        let ratios = stats
+            .iter()
+            .map(|stats| lower_bound_ratio(stats, n))
+            .collect::<Vec<_>>();
+
+        StatsSummary {
+            n,
+            runs,
+            mean_comparisons: mean(|stats| stats.comparisons),
+            mean_swaps: mean(|stats| stats.swaps),
+            mean_writes: mean(|stats| stats.writes),
+            lower_bound: comparison_lower_bound(n),
+            lower_bound_ratio: ratios.iter().sum::<f64>() / runs as f64,
+            worst_lower_bound_ratio: ratios.into_iter().fold(0.0, f64::max),
+        }
+    }
+
+    /// Returns ratio of mean comparisons to expected comparisons of `model`.
+    /// Ratio is 1 for inputs without any expected comparisons.
+    pub fn model_ratio(&self, model: ComparisonModel) -> f64 {
+        let expected = model.expected_comparisons(self.n);
+
+        if expected == 0.0 {
+            1.0
+        } else {
+            self.mean_comparisons / expected
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarking::StandardBenchmarker;
+    use crate::dual_pivot_quicksort::{
+        ClairvoyantPartitioning, SedgewickPartitioning, YaroslavskiyPartitioning,
+    };
+    use crate::generator::{RandomGenerator, SeededGenerator};
+    use crate::prelude::*;
+
+    fn summary<S: BenchmarkingSorter>(n: usize) -> StatsSummary {
+        let stats = (0..20)
+            .map(|seed| {
+                let mut benchmarker = StandardBenchmarker::default();
+                let mut slice = RandomGenerator::generate_seeded(n, seed);
+
+                S::sort_with_benchmark(&mut slice, &mut benchmarker);
+
+                benchmarker.get_stats()
+            })
+            .collect::<Vec<_>>();
+
+        StatsSummary::new(n, &stats)
+    }
+
+    #[test]
+    fn lower_bound_matches_known_values() {
+        let known = [
+            0, 0, 1, 3, 5, 7, 10, 13, 16, 19, 22, 26, 29, 33, 37, 41, 45, 49, 53, 57, 62, 66, 70,
+        ];
+
+        for (n, lower_bound) in known.into_iter().enumerate() {
+            assert_eq!(lower_bound, comparison_lower_bound(n), "{} items", n);
+        }
+    }
+
+    #[test]
+    fn exact_and_approximate_lower_bounds_agree() {
+        for n in 2..=EXACT_FACTORIAL {
+            assert_eq!(
+                comparison_lower_bound(n),
+                log2_factorial(n).ceil() as usize,
+                "{} items",
+                n
+            );
+        }
+    }
+
+    #[test]
+    fn models_predict_comparisons() {
+        for (summary, model) in [
+            (summary::<QuickSort>(1000), ComparisonModel::Quicksort),
+            (summary::<MergeSort>(1000), ComparisonModel::MergeSort),
+            (
+                summary::<InsertionSort>(1000),
+                ComparisonModel::InsertionSort,
+            ),
+            (
+                summary::<DualPivotQuicksort>(1000),
+                ComparisonModel::CountDualPivot,
+            ),
+            (
+                summary::<DualPivotQuicksortWith<ClairvoyantPartitioning>>(1000),
+                ComparisonModel::ClairvoyantDualPivot,
+            ),
+            (
+                summary::<DualPivotQuicksortWith<YaroslavskiyPartitioning>>(1000),
+                ComparisonModel::YaroslavskiyDualPivot,
+            ),
+            (
+                summary::<DualPivotQuicksortWith<SedgewickPartitioning>>(1000),
+                ComparisonModel::SedgewickDualPivot,
+            ),
+        ] {
+            assert!(
+                (summary.model_ratio(model) - 1.0).abs() < 0.05,
+                "{:?} off by {}",
+                model,
+                summary.model_ratio(model)
+            );
+        }
+    }
+
+    #[test]
+    fn no_sorter_beats_lower_bound() {
+        for summary in [
+            summary::<QuickSort>(1000),
+            summary::<MergeSort>(1000),
+            summary::<MergeInsertionSort>(1000),
+            summary::<BinaryInsertionSort>(1000),
+        ] {
+            assert!(summary.lower_bound_ratio >= 1.0);
+            assert!(summary.worst_lower_bound_ratio >= summary.lower_bound_ratio);
+            assert!(summary.model_ratio(ComparisonModel::LowerBound) >= 1.0);
+        }
+
+        assert!(summary::<MergeInsertionSort>(1000).lower_bound_ratio < 1.01);
+    }
+}
@@ -0,0 +1,346 @@
+//! Autotuning of parameters of hybrid sorters by searching for values which
+//! minimize cost of sorting chosen inputs.
+
+use std::time::Instant;
+
+use crate::benchmarking::{AtomicBenchmarker, StandardBenchmarker, Stats};
+use crate::generator::SeededGenerator;
+use crate::prelude::*;
+use crate::tuning::{Parameter, Tuning};
+
+/// Cost of sorting minimized by autotuning.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Objective {
+    /// Number of comparisons counted by benchmarking.
+    Comparisons,
+    /// Number of writes into sorted slice and buffers counted by
+    /// benchmarking.
+    Writes,
+    /// Time of sorting without benchmarking in seconds. It's noisy, so it
+    /// needs more inputs than counters.
+    Duration,
+}
+
+impl Objective {
+    /// Returns mean cost of sorting copies of `inputs` by `S` with current
+    /// tuning.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::autotune::Objective;
+    /// use algorithms::prelude::*;
+    ///
+    /// let inputs = vec![vec![1, 8, 2, 3, 9, 5], vec![2, 1]];
+    ///
+    /// assert_eq!(5.5, Objective::Comparisons.cost::<BinaryInsertionSort>(&inputs));
+    /// ```
+    pub fn cost<S: Sorter + BenchmarkingSorter>(self, inputs: &[Vec<u32>]) -> f64 {
+        let total = inputs
+            .iter()
+            .map(|input| {
+                let mut slice = input.clone();
+
+                if self == Objective::Duration {
+                    let start = Instant::now();
+
+                    S::sort(&mut slice);
+
+                    start.elapsed().as_secs_f64()
+                } else {
+                    let mut benchmarker = StandardBenchmarker::default();
+
+                    S::sort_with_benchmark(&mut slice, &mut benchmarker);
+
+                    self.counter(&benchmarker.get_stats())
+                }
+            })
+            .sum::<f64>();
+
+        total / inputs.len().max(1) as f64
+    }
+
+    /// Returns mean cost of sorting copies of `inputs` by integer sorter
+    /// `S` with current tuning.
+    pub fn integer_cost<S: IntegerSorter + BenchmarkingIntegerSorter>(
+        self,
+        inputs: &[Vec<u32>],
+    ) -> f64 {
+        let total = inputs
+            .iter()
+            .map(|input| {
+                let mut slice = input.clone();
+
+                if self == Objective::Duration {
+                    let start = Instant::now();
+
+                    S::sort(&mut slice);
+
+                    start.elapsed().as_secs_f64()
+                } else {
+                    let mut benchmarker = StandardBenchmarker::default();
+
+                    S::sort_with_benchmark(&mut slice, &mut benchmarker);
+
+                    self.counter(&benchmarker.get_stats())
+                }
+            })
+            .sum::<f64>();
+
+        total / inputs.len().max(1) as f64
+    }
+
+    /// Returns mean cost of sorting copies of `inputs` by parallel sorter
+    /// `S` with current tuning. Counters are summed over all its threads.
+    pub fn parallel_cost<S: ParallelSorter + BenchmarkingParallelSorter>(
+        self,
+        inputs: &[Vec<u32>],
+    ) -> f64 {
+        let total = inputs
+            .iter()
+            .map(|input| {
+                let mut slice = input.clone();
+
+                if self == Objective::Duration {
+                    let start = Instant::now();
+
+                    S::sort(&mut slice);
+
+                    start.elapsed().as_secs_f64()
+                } else {
+                    let benchmarker = AtomicBenchmarker::default();
+
+                    S::sort_with_benchmark(&mut slice, &benchmarker);
+
+                    self.counter(&benchmarker.get_stats())
+                }
+            })
+            .sum::<f64>();
+
+        total / inputs.len().max(1) as f64
+    }
+
+    fn counter(self, stats: &Stats) -> f64 {
+        match self {
+            Objective::Comparisons => stats.comparisons as f64,
+            Objective::Writes => stats.writes as f64,
+            Objective::Duration => stats.duration.as_secs_f64(),
+        }
+    }
+}
+
+/// Generates `runs` inputs of every size in `sizes` by generator `G`.
+pub fn inputs<G: SeededGenerator>(sizes: &[usize], runs: u64) -> Vec<Vec<u32>> {
+    sizes
+        .iter()
+        .flat_map(|&n| (0..runs).map(move |seed| G::generate_seeded(n, seed)))
+        .collect()
+}
+
+/// Best tuning found by search together with its cost.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tuned {
+    pub tuning: Tuning,
+    pub cost: f64,
+    /// Number of tunings evaluated by search.
+    pub evaluations: usize,
+}
+
+/// Evaluates `start` and every tuning differing from it only in value of
+/// `parameter` taken from `values`, skipping values out of its range.
+/// `evaluate` has to apply tuning it gets before measuring its cost.
+///
+/// Examples:
+/// ```
+/// use algorithms::autotune::grid_search;
+/// use algorithms::tuning::{Parameter, Tuning};
+///
+/// let tuned = grid_search(&Tuning::DEFAULT, Parameter::TimSortCutoff, 0..=100, |tuning| {
+///     tuning.get(Parameter::TimSortCutoff).abs_diff(17) as f64
+/// });
+///
+/// assert_eq!(17, tuned.tuning.get(Parameter::TimSortCutoff));
+/// assert_eq!(64, tuned.evaluations);
+/// ```
+pub fn grid_search(
+    start: &Tuning,
+    parameter: Parameter,
+    values: impl IntoIterator<Item = usize>,
+    mut evaluate: impl FnMut(&Tuning) -> f64,
+) -> Tuned {
+    let mut best = Tuned {
+        tuning: *start,
+        cost: evaluate(start),
+        evaluations: 1,
+    };
+
+    for value in values {
+        let mut tuning = *start;
+
+        if value == start.get(parameter) || tuning.set(parameter, value).is_err() {
+            continue;
+        }
+
+        let cost = evaluate(&tuning);
+
+        best.evaluations += 1;
+
+        if cost < best.cost {
+            best.tuning = tuning;
+            best.cost = cost;
+        }
+    }
+
+    best
+}
+
+/// Hill climbs from `start` one parameter of `parameters` at a time. Every
+/// parameter tries values one step above and below its current value,
+/// clamped into its range, and moves to the first one which lowers the cost.
+/// If neither does, its step is halved, starting from half of its value.
+/// Search ends once all steps drop to zero. `evaluate` has to apply tuning
+/// it gets before measuring its cost.
+///
+/// Examples:
+/// ```
+/// use algorithms::autotune::hill_climb;
+/// use algorithms::tuning::{Parameter, Tuning};
+///
+/// let parameters = [Parameter::PdqInsertionLen, Parameter::PdqNintherLen];
+/// let tuned = hill_climb(&Tuning::DEFAULT, &parameters, |tuning| {
+///     let insertion = tuning.get(Parameter::PdqInsertionLen) as f64;
+///     let ninther = tuning.get(Parameter::PdqNintherLen) as f64;
+///
+///     (insertion - 28.0).powi(2) + (ninther - 100.0).abs()
+/// });
+///
+/// assert_eq!(28, tuned.tuning.get(Parameter::PdqInsertionLen));
+/// assert_eq!(100, tuned.tuning.get(Parameter::PdqNintherLen));
+/// ```
+pub fn hill_climb(
+    start: &Tuning,
+    parameters: &[Parameter],
+    mut evaluate: impl FnMut(&Tuning) -> f64,
+) -> Tuned {
+    let mut best = Tuned {
+        tuning: *start,
+        cost: evaluate(start),
+        evaluations: 1,
+    };
+    let mut steps = parameters
+        .iter()
+        .map(|&parameter| (start.get(parameter) / 2).max(1))
+        .collect::<Vec<_>>();
+
+    while steps.iter().any(|&step| step > 0) {
+        for (&parameter, step) in parameters.iter().zip(steps.iter_mut()) {
+            if *step == 0 {
+                continue;
+            }
+
+            let value = best.tuning.get(parameter);
+            let range = parameter.range();
+            let mut improved = false;
+
+            for candidate in [value.saturating_add(*step), value.saturating_sub(*step)] {
+                let candidate = candidate.clamp(*range.start(), *range.end());
+                let mut tuning = best.tuning;
+
+                if candidate == value || tuning.set(parameter, candidate).is_err() {
+                    continue;
+                }
+
+                let cost = evaluate(&tuning);
+
+                best.evaluations += 1;
+
+                if cost < best.cost {
+                    best.tuning = tuning;
+                    best.cost = cost;
+                    improved = true;
+                    break;
+                }
+            }
+
+            if !improved {
+                *step /= 2;
+            }
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conformance::count_comparisons;
+    use crate::generator::RandomGenerator;
+    use crate::sample_sort::Ips4oSort;
+
+    #[test]
+    fn cost_is_mean_of_all_inputs() {
+        let inputs = inputs::<RandomGenerator>(&[10, 100], 3);
+
+        let expected = inputs
+            .iter()
+            .map(|input| count_comparisons::<MergeSort>(input))
+            .sum::<usize>() as f64
+            / 6.0;
+
+        assert_eq!(6, inputs.len());
+        assert_eq!(expected, Objective::Comparisons.cost::<MergeSort>(&inputs));
+        assert_eq!(
+            0.0,
+            Objective::Comparisons.integer_cost::<LsdRadixSort<8>>(&inputs)
+        );
+        assert!(Objective::Writes.parallel_cost::<Ips4oSort<2>>(&inputs) > 0.0);
+        assert!(Objective::Duration.cost::<MergeSort>(&inputs) > 0.0);
+    }
+
+    #[test]
+    fn grid_search_keeps_start_without_better_value() {
+        let tuned = grid_search(
+            &Tuning::DEFAULT,
+            Parameter::TimSortCutoff,
+            [1, 1000],
+            |_| 1.0,
+        );
+
+        assert_eq!(Tuning::DEFAULT, tuned.tuning);
+        assert_eq!(2, tuned.evaluations);
+    }
+
+    #[test]
+    fn hill_climb_reaches_range_bounds() {
+        let parameters = [Parameter::TimSortCutoff, Parameter::BlockPartitioningBlock];
+        let tuned = hill_climb(&Tuning::DEFAULT, &parameters, |tuning| {
+            tuning.get(Parameter::TimSortCutoff) as f64
+                - tuning.get(Parameter::BlockPartitioningBlock) as f64
+        });
+
+        assert_eq!(1, tuned.tuning.get(Parameter::TimSortCutoff));
+        assert_eq!(256, tuned.tuning.get(Parameter::BlockPartitioningBlock));
+        assert_eq!(
+            Tuning::DEFAULT.get(Parameter::PdqInsertionLen),
+            tuned.tuning.get(Parameter::PdqInsertionLen)
+        );
+    }
+
+    #[test]
+    fn hill_climb_minimizes_every_parameter() {
+        let targets = [7, 30, 200, 9, 64, 100, 5, 32, 4, 2048, 3, 40, 8, 4];
+        let tuned = hill_climb(&Tuning::DEFAULT, &Parameter::ALL, |tuning| {
+            Parameter::ALL
+                .iter()
+                .zip(targets)
+                .map(|(&parameter, target)| tuning.get(parameter).abs_diff(target) as f64)
+                .sum()
+        });
+
+        assert_eq!(0.0, tuned.cost);
+
+        for (parameter, target) in Parameter::ALL.into_iter().zip(targets) {
+            assert_eq!(target, tuned.tuning.get(parameter), "{:?}", parameter);
+        }
+    }
+}
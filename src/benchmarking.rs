@@ -1,23 +1,56 @@
 //! Collection of structs and traits necessary for benchmarking algorithms.
 
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
 /// Struct representing result of benchmarking. Has to be returned
 /// by benchmarker after finished benchmarking.
-
 pub struct Stats {
     pub comparisons: usize,
     pub swaps: usize,
     pub duration: Duration,
+    pub passes: usize,
+    pub moves: usize,
+    pub allocations: usize,
+    pub allocated_bytes: usize,
+    pub scanned_elements: usize,
+    pub writes: usize,
+    pub decisions: Vec<Decision>,
 }
 
-/// Trait for benchmarkers which are used to benchmark algorithms.
+/// Algorithm chosen by adaptive sorter for its input, together with reason
+/// of the choice.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Decision {
+    pub algorithm: &'static str,
+    pub reason: String,
+}
 
+/// Trait for benchmarkers which are used to benchmark algorithms.
 pub trait Benchmark {
     /// Invoke this method where comparison is made in algorithm.
     fn add_cmp(&mut self);
     /// Invoke this method where swap is made in algorithm.
     fn add_swap(&mut self);
+    /// Invoke this method where algorithm starts another pass over data.
+    fn add_pass(&mut self) {}
+    /// Invoke this method where element is moved without swapping,
+    /// e.g. into or out of a buffer.
+    fn add_move(&mut self) {}
+    /// Invoke this method where algorithm allocates buffer of `bytes` bytes.
+    fn add_allocation(&mut self, _bytes: usize) {}
+    /// Invoke this method where element is read by a sequential scan over
+    /// data. Scanned elements approximate cache misses of an algorithm.
+    fn add_scan(&mut self) {}
+    /// Invoke this method where `writes` elements are stored into sorted
+    /// slice or a buffer, e.g. two for a swap and one for every item copied
+    /// into or out of a buffer. Items stored back where they already were
+    /// are counted too, but filling new buffer before use isn't.
+    fn add_writes(&mut self, _writes: usize) {}
+    /// Invoke this method where algorithm chooses which algorithm sorts
+    /// its input.
+    fn add_decision(&mut self, _decision: Decision) {}
     /// Invoke this method at the start of benchmarking.
     fn start_timer(&mut self);
     /// Invoke this method at the end of benchmarking to save processing time.
@@ -28,11 +61,17 @@ pub trait Benchmark {
 
 /// Most basic implementation of `Benchmark` trait. Used
 /// in all benchmarking examples.
-
 #[derive(Default)]
 pub struct StandardBenchmarker {
     comparisons: usize,
     swaps: usize,
+    passes: usize,
+    moves: usize,
+    allocations: usize,
+    allocated_bytes: usize,
+    scanned_elements: usize,
+    writes: usize,
+    decisions: Vec<Decision>,
     timer: Option<Instant>,
     duration: Option<Duration>,
 }
@@ -46,6 +85,31 @@ impl Benchmark for StandardBenchmarker {
         self.swaps += 1;
     }
 
+    fn add_pass(&mut self) {
+        self.passes += 1;
+    }
+
+    fn add_move(&mut self) {
+        self.moves += 1;
+    }
+
+    fn add_allocation(&mut self, bytes: usize) {
+        self.allocations += 1;
+        self.allocated_bytes += bytes;
+    }
+
+    fn add_scan(&mut self) {
+        self.scanned_elements += 1;
+    }
+
+    fn add_writes(&mut self, writes: usize) {
+        self.writes += writes;
+    }
+
+    fn add_decision(&mut self, decision: Decision) {
+        self.decisions.push(decision);
+    }
+
     fn start_timer(&mut self) {
         // assert!(self.timer.is_none(), "timer already started");
 
@@ -55,10 +119,10 @@ impl Benchmark for StandardBenchmarker {
     fn stop_timer(&mut self) {
         // assert!(self.timer.is_some(), "timer has to be started first");
 
-        if self.timer.is_some() {
+        if let Some(timer) = self.timer {
             let now = Instant::now();
 
-            self.duration = Some(now - self.timer.unwrap());
+            self.duration = Some(now - timer);
         }
     }
 
@@ -69,8 +133,155 @@ impl Benchmark for StandardBenchmarker {
             duration: self.duration.unwrap_or_default(),
             swaps: self.swaps,
             comparisons: self.comparisons,
+            passes: self.passes,
+            moves: self.moves,
+            allocations: self.allocations,
+            allocated_bytes: self.allocated_bytes,
+            scanned_elements: self.scanned_elements,
+            writes: self.writes,
+            decisions: self.decisions.clone(),
+        }
+    }
+}
+
+/// Benchmarker forwarding all counters to wrapped benchmarker while ignoring
+/// timer. Used when algorithm hands its subproblems to other sorters, which
+/// would otherwise restart the timer of whole run.
+pub struct NestedBenchmarker<'a, B: Benchmark> {
+    benchmark: &'a mut B,
+}
+
+impl<'a, B: Benchmark> NestedBenchmarker<'a, B> {
+    pub fn new(benchmark: &'a mut B) -> Self {
+        NestedBenchmarker { benchmark }
+    }
+}
+
+impl<B: Benchmark> Benchmark for NestedBenchmarker<'_, B> {
+    fn add_cmp(&mut self) {
+        self.benchmark.add_cmp();
+    }
+
+    fn add_swap(&mut self) {
+        self.benchmark.add_swap();
+    }
+
+    fn add_pass(&mut self) {
+        self.benchmark.add_pass();
+    }
+
+    fn add_move(&mut self) {
+        self.benchmark.add_move();
+    }
+
+    fn add_allocation(&mut self, bytes: usize) {
+        self.benchmark.add_allocation(bytes);
+    }
+
+    fn add_scan(&mut self) {
+        self.benchmark.add_scan();
+    }
+
+    fn add_writes(&mut self, writes: usize) {
+        self.benchmark.add_writes(writes);
+    }
+
+    fn add_decision(&mut self, decision: Decision) {
+        self.benchmark.add_decision(decision);
+    }
+
+    fn start_timer(&mut self) {}
+
+    fn stop_timer(&mut self) {}
+
+    fn get_stats(&mut self) -> Stats {
+        self.benchmark.get_stats()
+    }
+}
+
+/// Thread-safe benchmarker with atomic counters, aggregating counters of all
+/// workers of parallel sorter. `Benchmark` is implemented for shared
+/// reference to it, so every worker gets its own copy of the reference.
+#[derive(Default)]
+pub struct AtomicBenchmarker {
+    comparisons: AtomicUsize,
+    swaps: AtomicUsize,
+    passes: AtomicUsize,
+    moves: AtomicUsize,
+    allocations: AtomicUsize,
+    allocated_bytes: AtomicUsize,
+    scanned_elements: AtomicUsize,
+    writes: AtomicUsize,
+    decisions: Mutex<Vec<Decision>>,
+    timer: Mutex<Option<Instant>>,
+    duration: Mutex<Option<Duration>>,
+}
+
+impl AtomicBenchmarker {
+    /// Returns `Stats` aggregated from all workers.
+    pub fn get_stats(&self) -> Stats {
+        Stats {
+            duration: self.duration.lock().unwrap().unwrap_or_default(),
+            swaps: self.swaps.load(Ordering::Relaxed),
+            comparisons: self.comparisons.load(Ordering::Relaxed),
+            passes: self.passes.load(Ordering::Relaxed),
+            moves: self.moves.load(Ordering::Relaxed),
+            allocations: self.allocations.load(Ordering::Relaxed),
+            allocated_bytes: self.allocated_bytes.load(Ordering::Relaxed),
+            scanned_elements: self.scanned_elements.load(Ordering::Relaxed),
+            writes: self.writes.load(Ordering::Relaxed),
+            decisions: self.decisions.lock().unwrap().clone(),
+        }
+    }
+}
+
+impl Benchmark for &AtomicBenchmarker {
+    fn add_cmp(&mut self) {
+        self.comparisons.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn add_swap(&mut self) {
+        self.swaps.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn add_pass(&mut self) {
+        self.passes.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn add_move(&mut self) {
+        self.moves.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn add_allocation(&mut self, bytes: usize) {
+        self.allocations.fetch_add(1, Ordering::Relaxed);
+        self.allocated_bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    fn add_scan(&mut self) {
+        self.scanned_elements.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn add_writes(&mut self, writes: usize) {
+        self.writes.fetch_add(writes, Ordering::Relaxed);
+    }
+
+    fn add_decision(&mut self, decision: Decision) {
+        self.decisions.lock().unwrap().push(decision);
+    }
+
+    fn start_timer(&mut self) {
+        *self.timer.lock().unwrap() = Some(Instant::now());
+    }
+
+    fn stop_timer(&mut self) {
+        if let Some(timer) = *self.timer.lock().unwrap() {
+            *self.duration.lock().unwrap() = Some(Instant::now() - timer);
         }
     }
+
+    fn get_stats(&mut self) -> Stats {
+        AtomicBenchmarker::get_stats(self)
+    }
 }
 
 #[cfg(test)]
@@ -91,7 +302,7 @@ mod tests {
         benchmarker.add_cmp();
         benchmarker.add_cmp();
 
-        std::thread::sleep_ms(1);
+        std::thread::sleep(Duration::from_millis(1));
 
         benchmarker.stop_timer();
 
@@ -101,4 +312,104 @@ mod tests {
         assert_eq!(3, stats.comparisons);
         assert!(stats.duration >= Duration::from_millis(1));
     }
+
+    #[test]
+    fn standard_benchmarker_counts_passes_moves_allocations_and_scans() {
+        let mut benchmarker = StandardBenchmarker::default();
+
+        benchmarker.add_writes(2);
+        benchmarker.add_writes(1);
+        benchmarker.add_pass();
+        benchmarker.add_move();
+        benchmarker.add_move();
+        benchmarker.add_allocation(64);
+        benchmarker.add_allocation(16);
+        benchmarker.add_scan();
+
+        let stats = benchmarker.get_stats();
+
+        assert_eq!(1, stats.passes);
+        assert_eq!(2, stats.moves);
+        assert_eq!(2, stats.allocations);
+        assert_eq!(80, stats.allocated_bytes);
+        assert_eq!(1, stats.scanned_elements);
+        assert_eq!(3, stats.writes);
+    }
+
+    #[test]
+    fn nested_benchmarker_forwards_counters_but_not_timer() {
+        let mut benchmarker = StandardBenchmarker::default();
+
+        benchmarker.start_timer();
+
+        {
+            let mut nested = NestedBenchmarker::new(&mut benchmarker);
+
+            nested.start_timer();
+            nested.add_cmp();
+            nested.add_swap();
+            nested.add_allocation(8);
+            nested.add_writes(2);
+            nested.add_decision(Decision {
+                algorithm: "pdq",
+                reason: String::from("random input"),
+            });
+            nested.stop_timer();
+        }
+
+        std::thread::sleep(Duration::from_millis(1));
+
+        benchmarker.stop_timer();
+
+        let stats = benchmarker.get_stats();
+
+        assert_eq!(1, stats.comparisons);
+        assert_eq!(1, stats.swaps);
+        assert_eq!(8, stats.allocated_bytes);
+        assert_eq!(2, stats.writes);
+        assert_eq!(
+            vec![Decision {
+                algorithm: "pdq",
+                reason: String::from("random input"),
+            }],
+            stats.decisions
+        );
+        assert!(stats.duration >= Duration::from_millis(1));
+    }
+
+    #[test]
+    fn atomic_benchmarker_aggregates_counters_of_all_threads() {
+        let benchmarker = AtomicBenchmarker::default();
+
+        (&benchmarker).start_timer();
+
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    let mut benchmark = &benchmarker;
+
+                    for _ in 0..1000 {
+                        benchmark.add_cmp();
+                        benchmark.add_swap();
+                        benchmark.add_writes(2);
+                    }
+
+                    benchmark.add_allocation(8);
+                });
+            }
+        });
+
+        std::thread::sleep(Duration::from_millis(1));
+
+        (&benchmarker).stop_timer();
+
+        let stats = benchmarker.get_stats();
+
+        assert_eq!(8000, stats.comparisons);
+        assert_eq!(8000, stats.swaps);
+        assert_eq!(16000, stats.writes);
+        assert_eq!(8, stats.allocations);
+        assert_eq!(64, stats.allocated_bytes);
+        assert!(stats.duration >= Duration::from_millis(1));
+    }
 }
@@ -0,0 +1,120 @@
+use algorithms::autotune::{hill_climb, inputs, Objective};
+use algorithms::generator::*;
+use algorithms::prelude::*;
+use algorithms::quick_sort::BlockPartitioning;
+use algorithms::sample_sort::Ips4oSort;
+use algorithms::tuning::{Parameter, Tuning};
+
+/// Number of inputs of every size generated by every generator.
+const RUNS: u64 = 5;
+/// Number of threads of tuned parallel sorters.
+const THREADS: usize = 8;
+
+/// Mean cost of sorting inputs by sorter affected by group of parameters.
+type Cost = fn(Objective, &[Vec<u32>]) -> f64;
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+
+    let objective = match args
+        .next()
+        .expect("there has to be an objective given")
+        .as_str()
+    {
+        "comparisons" => Objective::Comparisons,
+        "writes" => Objective::Writes,
+        "duration" => Objective::Duration,
+        _ => panic!("wrong objective"),
+    };
+    let generators = args.next().expect("there have to be generators given");
+    let output = args.next().expect("there has to be an output file given");
+    let sizes = args
+        .map(|size| size.parse().expect("size isn't valid number"))
+        .collect::<Vec<usize>>();
+
+    assert!(!sizes.is_empty(), "there have to be sizes given");
+
+    let inputs = generators
+        .split(',')
+        .flat_map(|generator| match generator {
+            "rand" => inputs::<RandomGenerator>(&sizes, RUNS),
+            "asc" => inputs::<AscendingGenerator>(&sizes, RUNS),
+            "desc" => inputs::<DescendingGenerator>(&sizes, RUNS),
+            _ => panic!("wrong generator mode"),
+        })
+        .collect::<Vec<_>>();
+
+    let groups: [(&[Parameter], Cost); 11] = [
+        (&[Parameter::TimSortCutoff], |objective, inputs| {
+            objective.cost::<TimSort>(inputs)
+        }),
+        (
+            &[Parameter::PdqInsertionLen, Parameter::PdqNintherLen],
+            |objective, inputs| objective.cost::<PdqSort>(inputs),
+        ),
+        (&[Parameter::ThreeWayInsertionLen], |objective, inputs| {
+            objective.cost::<ThreeWayQuicksort>(inputs)
+        }),
+        (&[Parameter::BlockPartitioningBlock], |objective, inputs| {
+            objective.cost::<QuickSortWith<BlockPartitioning>>(inputs)
+        }),
+        (&[Parameter::MsdRadixInsertionLen], |objective, inputs| {
+            objective.integer_cost::<MsdRadixSort>(inputs)
+        }),
+        (&[Parameter::AdaptiveInsertionLen], |objective, inputs| {
+            objective.cost::<AdaptiveSort>(inputs)
+        }),
+        (
+            &[
+                Parameter::SampleSortBaseCaseLen,
+                Parameter::SampleSortMaxLogBuckets,
+            ],
+            |objective, inputs| objective.cost::<SampleSort>(inputs),
+        ),
+        (&[Parameter::Ips4oBlockBytes], |objective, inputs| {
+            objective.parallel_cost::<Ips4oSort<THREADS>>(inputs)
+        }),
+        (
+            &[
+                Parameter::MultiPivotSampleSpacing,
+                Parameter::MultiPivotInsertionLen,
+            ],
+            |objective, inputs| objective.cost::<MultiPivotQuicksort<3>>(inputs),
+        ),
+        (&[Parameter::BlockSortRunLen], |objective, inputs| {
+            objective.cost::<BlockSort>(inputs)
+        }),
+        (&[Parameter::BucketSortBucketLen], |objective, inputs| {
+            objective.integer_cost::<BucketSort<InsertionSort>>(inputs)
+        }),
+    ];
+
+    let mut tuning = Tuning::current();
+
+    println!("parameter start tuned cost evaluations");
+
+    for (parameters, cost) in groups {
+        let tuned = hill_climb(&tuning, parameters, |candidate| {
+            candidate.apply();
+            cost(objective, &inputs)
+        });
+
+        for &parameter in parameters {
+            println!(
+                "{} {} {} {} {}",
+                parameter.name(),
+                tuning.get(parameter),
+                tuned.tuning.get(parameter),
+                tuned.cost,
+                tuned.evaluations
+            );
+        }
+
+        tuning = tuned.tuning;
+        tuning.apply();
+    }
+
+    tuning
+        .save(&output)
+        .expect("couldn't write tuning configuration");
+}
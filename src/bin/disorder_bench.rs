@@ -0,0 +1,66 @@
+use rand::prelude::*;
+
+use algorithms::benchmarking::*;
+use algorithms::generator::*;
+use algorithms::measures::Presortedness;
+use algorithms::prelude::*;
+
+fn main() {
+    let n: usize = std::env::args()
+        .nth(1)
+        .expect("there has to be an n given")
+        .parse()
+        .expect("n isn't valid number");
+
+    println!(
+        "swaps inversions runs rem osc max_displacement total_displacement runs_entropy \
+         insertion binary_insertion natural_merge tim smooth pdq merge quick"
+    );
+
+    // ascending input disordered by growing number of random swaps
+    for swaps in (0..=n.ilog2() + 1).map(|exp| (1usize << exp) - 1) {
+        let input = swapped(AscendingGenerator::generate(n), swaps);
+        let measures = Presortedness::of(&input);
+
+        println!(
+            "{} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {}",
+            swaps,
+            measures.inversions,
+            measures.runs,
+            measures.rem,
+            measures.osc,
+            measures.max_displacement,
+            measures.total_displacement,
+            measures.runs_entropy,
+            comparisons::<InsertionSort>(&input),
+            comparisons::<BinaryInsertionSort>(&input),
+            comparisons::<NaturalMergeSort>(&input),
+            comparisons::<TimSort>(&input),
+            comparisons::<SmoothSort>(&input),
+            comparisons::<PdqSort>(&input),
+            comparisons::<MergeSort>(&input),
+            comparisons::<QuickSort>(&input),
+        );
+    }
+}
+
+fn swapped(mut array: Vec<u32>, swaps: usize) -> Vec<u32> {
+    let mut rng = thread_rng();
+
+    for _ in 0..swaps {
+        let i = rng.gen_range(0..array.len());
+        let j = rng.gen_range(0..array.len());
+
+        array.swap(i, j);
+    }
+
+    array
+}
+
+fn comparisons<S: BenchmarkingSorter>(array: &[u32]) -> usize {
+    let mut benchmark = StandardBenchmarker::default();
+
+    S::sort_with_benchmark(&mut array.to_vec(), &mut benchmark);
+
+    benchmark.get_stats().comparisons
+}
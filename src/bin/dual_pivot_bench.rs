@@ -1,7 +1,11 @@
 use std::fs::File;
 use std::io::Write;
 
+use algorithms::analysis::{ComparisonModel, StatsSummary};
 use algorithms::benchmarking::*;
+use algorithms::dual_pivot_quicksort::{
+    ClairvoyantPartitioning, SedgewickPartitioning, YaroslavskiyPartitioning,
+};
 use algorithms::generator::*;
 use algorithms::prelude::*;
 
@@ -14,66 +18,96 @@ fn main() {
 
     let mut quick_final_stats = Vec::with_capacity(10);
     let mut dual_pivot_final_stats = Vec::with_capacity(10);
+    let mut yaroslavskiy_final_stats = Vec::with_capacity(10);
+    let mut sedgewick_final_stats = Vec::with_capacity(10);
+    let mut clairvoyant_final_stats = Vec::with_capacity(10);
 
     for i in 1..=10 {
         let n = 100 * i;
 
         let mut quick_results = Vec::with_capacity(k);
         let mut dual_pivot_results = Vec::with_capacity(k);
+        let mut yaroslavskiy_results = Vec::with_capacity(k);
+        let mut sedgewick_results = Vec::with_capacity(k);
+        let mut clairvoyant_results = Vec::with_capacity(k);
 
         for _ in 0..k {
             let random_array = RandomGenerator::generate(n);
 
-            let mut quick_benchmark = StandardBenchmarker::default();
-            let mut dual_pivot_benchmark = StandardBenchmarker::default();
-
-            QuickSort::sort_with_benchmark(&mut random_array.clone(), &mut quick_benchmark);
-            DualPivotQuicksort::sort_with_benchmark(&mut random_array.clone(), &mut dual_pivot_benchmark);
-
-            quick_results.push(quick_benchmark.get_stats());
-            dual_pivot_results.push(dual_pivot_benchmark.get_stats());
+            quick_results.push(bench::<QuickSort>(&random_array));
+            dual_pivot_results.push(bench::<DualPivotQuicksort>(&random_array));
+            yaroslavskiy_results.push(bench::<DualPivotQuicksortWith<YaroslavskiyPartitioning>>(
+                &random_array,
+            ));
+            sedgewick_results.push(bench::<DualPivotQuicksortWith<SedgewickPartitioning>>(
+                &random_array,
+            ));
+            clairvoyant_results.push(bench::<DualPivotQuicksortWith<ClairvoyantPartitioning>>(
+                &random_array,
+            ));
         }
 
-        let quick_comps_and_swaps = get_avg_stats(&quick_results, k);
-        let dual_pivot_comps_and_swaps = get_avg_stats(&dual_pivot_results, k);
+        quick_final_stats.push(StatsSummary::new(n, &quick_results));
+        dual_pivot_final_stats.push(StatsSummary::new(n, &dual_pivot_results));
+        yaroslavskiy_final_stats.push(StatsSummary::new(n, &yaroslavskiy_results));
+        sedgewick_final_stats.push(StatsSummary::new(n, &sedgewick_results));
+        clairvoyant_final_stats.push(StatsSummary::new(n, &clairvoyant_results));
+    }
 
-        quick_final_stats.push(quick_comps_and_swaps);
-        dual_pivot_final_stats.push(dual_pivot_comps_and_swaps);
+    for (algorithm, model, final_stats) in [
+        ("quick", ComparisonModel::Quicksort, &quick_final_stats),
+        (
+            "dual_pivot",
+            ComparisonModel::CountDualPivot,
+            &dual_pivot_final_stats,
+        ),
+        (
+            "yaroslavskiy",
+            ComparisonModel::YaroslavskiyDualPivot,
+            &yaroslavskiy_final_stats,
+        ),
+        (
+            "sedgewick",
+            ComparisonModel::SedgewickDualPivot,
+            &sedgewick_final_stats,
+        ),
+        (
+            "clairvoyant",
+            ComparisonModel::ClairvoyantDualPivot,
+            &clairvoyant_final_stats,
+        ),
+    ] {
+        print_final_stats_to_file(algorithm, StatType::Comps, final_stats);
+        print_final_stats_to_file(algorithm, StatType::Swaps, final_stats);
+
+        let largest = final_stats.last().expect("there has to be at least one n");
+
+        println!(
+            "Ratio of {} comps to log2(n!): {}",
+            algorithm, largest.lower_bound_ratio
+        );
+        println!(
+            "Ratio of {} comps to {:?} model: {}",
+            algorithm,
+            model,
+            largest.model_ratio(model)
+        );
     }
+}
 
-    print_final_stats_to_file("quick", StatType::Comps, &quick_final_stats);
-    print_final_stats_to_file("quick", StatType::Swaps, &quick_final_stats);
-    print_final_stats_to_file("dual_pivot", StatType::Comps, &dual_pivot_final_stats);
-    print_final_stats_to_file("dual_pivot", StatType::Swaps, &dual_pivot_final_stats);
+fn bench<S: BenchmarkingSorter>(array: &[u32]) -> Stats {
+    let mut benchmark = StandardBenchmarker::default();
 
-    let (const_swaps_quicksort, const_comps_quicksort) = calculate_const_in_front_of_n_lg_n(&quick_final_stats);
-    let (const_swaps_dual_pivot, const_comps_dual_pivot) = calculate_const_in_front_of_n_lg_n(&dual_pivot_final_stats);
+    S::sort_with_benchmark(&mut array.to_vec(), &mut benchmark);
 
-    println!("Const in front of n*log(n) in quicksort swaps: {}", const_swaps_quicksort);
-    println!("Const in front of n*log(n) in quicksort comps: {}", const_comps_quicksort);
-    println!("Const in front of n*log(n) in dual pivot swaps: {}", const_swaps_dual_pivot);
-    println!("Const in front of n*log(n) in dual pivot comps: {}", const_comps_dual_pivot);
+    benchmark.get_stats()
 }
 
-fn get_avg_stats(stats: &[Stats], k: usize) -> (f64, f64) {
-    let (mut insertion_comps, mut insertion_swaps) = stats
-        .iter()
-        .map(
-            |Stats {
-                 comparisons, swaps, ..
-             }| (*comparisons, *swaps),
-        )
-        .fold((0.0, 0.0), |(total_comps, total_swaps), (comps, swaps)| {
-            (total_comps + comps as f64, total_swaps + swaps as f64)
-        });
-
-    insertion_comps /= k as f64;
-    insertion_swaps /= k as f64;
-
-    (insertion_comps, insertion_swaps)
-}
-
-fn print_final_stats_to_file(algorithm: &str, type_of_stat: StatType, final_stats: &[(f64, f64)]) {
+fn print_final_stats_to_file(
+    algorithm: &str,
+    type_of_stat: StatType,
+    final_stats: &[StatsSummary],
+) {
     let type_of_stat_string = match type_of_stat {
         StatType::Comps => "comps",
         StatType::Swaps => "swaps",
@@ -85,10 +119,9 @@ fn print_final_stats_to_file(algorithm: &str, type_of_stat: StatType, final_stat
 
     let file_content = final_stats
         .iter()
-        .enumerate()
-        .map(|(i, (comps, swaps))| match type_of_stat {
-            StatType::Comps => format!("{} {}", 100 * (i + 1), comps),
-            StatType::Swaps => format!("{} {}", 100 * (i + 1), swaps),
+        .map(|summary| match type_of_stat {
+            StatType::Comps => format!("{} {}", summary.n, summary.mean_comparisons),
+            StatType::Swaps => format!("{} {}", summary.n, summary.mean_swaps),
         })
         .collect::<Vec<_>>()
         .join("\n");
@@ -101,26 +134,3 @@ enum StatType {
     Comps,
     Swaps,
 }
-
-fn calculate_const_in_front_of_n_lg_n(stats: &[(f64, f64)]) -> (f64, f64) {
-    assert!(stats.len() >= 10, "stats too short");
-
-    let mut sum_comps_const = 0.0;
-    let mut sum_swaps_const = 0.0;
-
-    for i in 0..10 {
-        let n = (100 * (i + 1)) as f64;
-
-        let (comps, swaps) = stats[i];
-
-        let n_log_n = n.log2() * n;
-
-        sum_swaps_const += swaps / n_log_n;
-        sum_comps_const += comps / n_log_n;
-    }
-
-    sum_swaps_const /= 10.0;
-    sum_comps_const /= 10.0;
-
-    (sum_swaps_const, sum_comps_const)
-}
@@ -0,0 +1,86 @@
+use algorithms::analysis::{ComparisonModel, StatsSummary};
+use algorithms::benchmarking::*;
+use algorithms::generator::*;
+use algorithms::prelude::*;
+use algorithms::shell_sort::CiuraGaps;
+
+/// Algorithms taking `O(n^2)` time are run only on inputs up to this size.
+const QUADRATIC_LIMIT: usize = 2000;
+
+fn main() {
+    let k = std::env::args()
+        .nth(1)
+        .expect("there has to be a k given")
+        .parse()
+        .expect("k isn't valid number");
+
+    println!("algorithm n comps log2(n!) ratio worst_ratio model model_ratio");
+
+    for n in [100, 1000, 10000] {
+        let inputs = (0..k)
+            .map(|_| RandomGenerator::generate(n))
+            .collect::<Vec<_>>();
+
+        if n <= QUADRATIC_LIMIT {
+            report::<InsertionSort>("insertion", &inputs, Some(ComparisonModel::InsertionSort));
+            report::<BinaryInsertionSort>("binary_insertion", &inputs, None);
+            report::<MergeInsertionSort>("merge_insertion", &inputs, None);
+            report::<SelectionSort>("selection", &inputs, None);
+            report::<CycleSort>("cycle", &inputs, None);
+        }
+
+        report::<MergeSort>("merge", &inputs, Some(ComparisonModel::MergeSort));
+        report::<BottomUpMergeSort>("bottom_up_merge", &inputs, None);
+        report::<NaturalMergeSort>("natural_merge", &inputs, None);
+        report::<PingPongMergeSort>("ping_pong_merge", &inputs, None);
+        report::<InPlaceMergeSort>("in_place_merge", &inputs, None);
+        report::<BlockSort>("block", &inputs, None);
+        report::<TimSort>("tim", &inputs, None);
+        report::<QuickSort>("quick", &inputs, Some(ComparisonModel::Quicksort));
+        report::<DualPivotQuicksort>("dual_pivot", &inputs, Some(ComparisonModel::CountDualPivot));
+        report::<MultiPivotQuicksort<3>>("multi_pivot", &inputs, None);
+        report::<PdqSort>("pdq", &inputs, None);
+        report::<ThreeWayQuicksort>("three_way_quick", &inputs, None);
+        report::<AdaptiveSort>("adaptive", &inputs, None);
+        report::<SampleSort>("sample", &inputs, None);
+        report::<NetworkSort>("network", &inputs, None);
+        report::<HeapSort>("heap", &inputs, None);
+        report::<SmoothSort>("smooth", &inputs, None);
+        report::<WeakHeapSort>("weak_heap", &inputs, None);
+        report::<AdaptiveWeakHeapSort>("adaptive_weak_heap", &inputs, None);
+        report::<ShellSort<CiuraGaps>>("shell", &inputs, None);
+    }
+}
+
+fn report<S: BenchmarkingSorter>(
+    algorithm: &str,
+    inputs: &[Vec<u32>],
+    model: Option<ComparisonModel>,
+) {
+    let n = inputs[0].len();
+    let stats = inputs
+        .iter()
+        .map(|input| {
+            let mut benchmark = StandardBenchmarker::default();
+
+            S::sort_with_benchmark(&mut input.clone(), &mut benchmark);
+
+            benchmark.get_stats()
+        })
+        .collect::<Vec<_>>();
+
+    let summary = StatsSummary::new(n, &stats);
+    let model = model.unwrap_or(ComparisonModel::LowerBound);
+
+    println!(
+        "{} {} {} {} {} {} {:?} {}",
+        algorithm,
+        n,
+        summary.mean_comparisons,
+        summary.lower_bound,
+        summary.lower_bound_ratio,
+        summary.worst_lower_bound_ratio,
+        model,
+        summary.model_ratio(model)
+    );
+}
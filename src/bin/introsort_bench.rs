@@ -14,37 +14,46 @@ fn main() {
 
     let mut merge_final_stats = Vec::with_capacity(10);
     let mut timsort_final_stats = Vec::with_capacity(10);
+    let mut block_final_stats = Vec::with_capacity(10);
 
     for i in 1..=10 {
         let n = 1000 * i;
 
         let mut merge_results = Vec::with_capacity(10);
         let mut timsort_results = Vec::with_capacity(10);
+        let mut block_results = Vec::with_capacity(10);
 
         for _ in 0..10 {
             let random_array = RandomGenerator::generate(n);
 
             let mut merge_benchmark = StandardBenchmarker::default();
             let mut timsort_benchmark = StandardBenchmarker::default();
+            let mut block_benchmark = StandardBenchmarker::default();
 
             MergeSort::sort_with_benchmark(&mut random_array.clone(), &mut merge_benchmark);
             TimSort::sort_with_benchmark(&mut random_array.clone(), &mut timsort_benchmark);
+            BlockSort::sort_with_benchmark(&mut random_array.clone(), &mut block_benchmark);
 
             merge_results.push(merge_benchmark.get_stats());
             timsort_results.push(timsort_benchmark.get_stats());
+            block_results.push(block_benchmark.get_stats());
         }
 
         let merge_comps_and_swaps = get_avg_stats(&merge_results, 10);
         let timsort_comps_and_swaps = get_avg_stats(&timsort_results, 10);
+        let block_comps_and_swaps = get_avg_stats(&block_results, 10);
 
         merge_final_stats.push(merge_comps_and_swaps);
         timsort_final_stats.push(timsort_comps_and_swaps);
+        block_final_stats.push(block_comps_and_swaps);
     }
 
     print_final_stats_to_file("merge", StatType::Comps, &merge_final_stats);
     print_final_stats_to_file("merge", StatType::Swaps, &merge_final_stats);
     print_final_stats_to_file("timsort", StatType::Comps, &timsort_final_stats);
     print_final_stats_to_file("timsort", StatType::Swaps, &timsort_final_stats);
+    print_final_stats_to_file("block", StatType::Comps, &block_final_stats);
+    print_final_stats_to_file("block", StatType::Swaps, &block_final_stats);
 }
 
 fn get_avg_stats(stats: &[Stats], k: usize) -> (f64, f64) {
@@ -0,0 +1,117 @@
+use std::fs::File;
+use std::io::Write;
+
+use algorithms::benchmarking::*;
+use algorithms::generator::*;
+use algorithms::prelude::*;
+
+fn main() {
+    let k = std::env::args()
+        .nth(1)
+        .expect("there has to be a k given")
+        .parse()
+        .expect("k isn't valid number");
+
+    let mut final_stats = (0..5).map(|_| Vec::with_capacity(10)).collect::<Vec<_>>();
+
+    for i in 1..=10 {
+        let n = 1000 * i;
+
+        let mut results = (0..5).map(|_| Vec::with_capacity(k)).collect::<Vec<_>>();
+
+        for _ in 0..k {
+            let array = RandomGenerator::generate(n);
+
+            results[0].push(bench::<MultiPivotQuicksort<1>>(&array));
+            results[1].push(bench::<MultiPivotQuicksort<2>>(&array));
+            results[2].push(bench::<MultiPivotQuicksort<3>>(&array));
+            results[3].push(bench::<MultiPivotQuicksort<5>>(&array));
+            results[4].push(bench::<MultiPivotQuicksort<7>>(&array));
+        }
+
+        for (final_stats, results) in final_stats.iter_mut().zip(&results) {
+            final_stats.push(get_avg_stats(results, k));
+        }
+    }
+
+    for (pivots, final_stats) in [1, 2, 3, 5, 7].iter().zip(&final_stats) {
+        let name = format!("multi_pivot_{}", pivots);
+
+        print_final_stats_to_file(&name, StatType::Comps, final_stats);
+        print_final_stats_to_file(&name, StatType::Swaps, final_stats);
+        print_final_stats_to_file(&name, StatType::Scans, final_stats);
+    }
+}
+
+fn bench<S: BenchmarkingSorter>(array: &[u32]) -> Stats {
+    let mut benchmark = StandardBenchmarker::default();
+
+    S::sort_with_benchmark(&mut array.to_vec(), &mut benchmark);
+
+    benchmark.get_stats()
+}
+
+fn get_avg_stats(stats: &[Stats], k: usize) -> (f64, f64, f64) {
+    let (mut comps, mut swaps, mut scans) = stats
+        .iter()
+        .map(
+            |Stats {
+                 comparisons,
+                 swaps,
+                 scanned_elements,
+                 ..
+             }| (*comparisons, *swaps, *scanned_elements),
+        )
+        .fold(
+            (0.0, 0.0, 0.0),
+            |(total_comps, total_swaps, total_scans), (comps, swaps, scans)| {
+                (
+                    total_comps + comps as f64,
+                    total_swaps + swaps as f64,
+                    total_scans + scans as f64,
+                )
+            },
+        );
+
+    comps /= k as f64;
+    swaps /= k as f64;
+    scans /= k as f64;
+
+    (comps, swaps, scans)
+}
+
+fn print_final_stats_to_file(
+    algorithm: &str,
+    type_of_stat: StatType,
+    final_stats: &[(f64, f64, f64)],
+) {
+    let type_of_stat_string = match type_of_stat {
+        StatType::Comps => "comps",
+        StatType::Swaps => "swaps",
+        StatType::Scans => "scans",
+    };
+
+    let filename = format!("{}_{}", algorithm, type_of_stat_string);
+
+    let mut file = File::create(filename).expect("problem creating file");
+
+    let file_content = final_stats
+        .iter()
+        .enumerate()
+        .map(|(i, (comps, swaps, scans))| match type_of_stat {
+            StatType::Comps => format!("{} {}", 1000 * (i + 1), comps),
+            StatType::Swaps => format!("{} {}", 1000 * (i + 1), swaps),
+            StatType::Scans => format!("{} {}", 1000 * (i + 1), scans),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    file.write_all(file_content.as_bytes())
+        .expect("problem writing to file");
+}
+
+enum StatType {
+    Comps,
+    Swaps,
+    Scans,
+}
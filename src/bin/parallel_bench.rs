@@ -0,0 +1,165 @@
+use std::fs::File;
+use std::io::Write;
+
+use algorithms::benchmarking::*;
+use algorithms::generator::*;
+use algorithms::oblivious_sort::{
+    BitonicSort, OddEvenMergeSort, ParallelBitonicSort, ParallelOddEvenMergeSort,
+};
+use algorithms::parallel_sort::{ParallelMergeSort, ParallelQuickSort};
+use algorithms::prelude::*;
+use algorithms::sample_sort::Ips4oSort;
+
+const THREADS: usize = 8;
+const CUTOFF: usize = 8192;
+
+fn main() {
+    let k = std::env::args()
+        .nth(1)
+        .expect("there has to be a k given")
+        .parse()
+        .expect("k isn't valid number");
+
+    let mut merge_final_stats = Vec::with_capacity(10);
+    let mut parallel_merge_final_stats = Vec::with_capacity(10);
+    let mut pdq_final_stats = Vec::with_capacity(10);
+    let mut parallel_quick_final_stats = Vec::with_capacity(10);
+    let mut sample_final_stats = Vec::with_capacity(10);
+    let mut ips4o_final_stats = Vec::with_capacity(10);
+    let mut bitonic_final_stats = Vec::with_capacity(10);
+    let mut parallel_bitonic_final_stats = Vec::with_capacity(10);
+    let mut odd_even_merge_final_stats = Vec::with_capacity(10);
+    let mut parallel_odd_even_merge_final_stats = Vec::with_capacity(10);
+
+    for i in 1..=10 {
+        let n = 100000 * i;
+
+        let mut merge_results = Vec::with_capacity(k);
+        let mut parallel_merge_results = Vec::with_capacity(k);
+        let mut pdq_results = Vec::with_capacity(k);
+        let mut parallel_quick_results = Vec::with_capacity(k);
+        let mut sample_results = Vec::with_capacity(k);
+        let mut ips4o_results = Vec::with_capacity(k);
+        let mut bitonic_results = Vec::with_capacity(k);
+        let mut parallel_bitonic_results = Vec::with_capacity(k);
+        let mut odd_even_merge_results = Vec::with_capacity(k);
+        let mut parallel_odd_even_merge_results = Vec::with_capacity(k);
+
+        for _ in 0..k {
+            let array = RandomGenerator::generate(n);
+
+            merge_results.push(bench::<MergeSort>(&array));
+            parallel_merge_results
+                .push(bench_parallel::<ParallelMergeSort<THREADS, CUTOFF>>(&array));
+            pdq_results.push(bench::<PdqSort>(&array));
+            parallel_quick_results
+                .push(bench_parallel::<ParallelQuickSort<THREADS, CUTOFF>>(&array));
+            sample_results.push(bench::<SampleSort>(&array));
+            ips4o_results.push(bench_parallel::<Ips4oSort<THREADS>>(&array));
+            bitonic_results.push(bench::<BitonicSort>(&array));
+            parallel_bitonic_results.push(bench_parallel::<ParallelBitonicSort<THREADS>>(&array));
+            odd_even_merge_results.push(bench::<OddEvenMergeSort>(&array));
+            parallel_odd_even_merge_results
+                .push(bench_parallel::<ParallelOddEvenMergeSort<THREADS>>(&array));
+        }
+
+        merge_final_stats.push(get_avg_stats(&merge_results, k));
+        parallel_merge_final_stats.push(get_avg_stats(&parallel_merge_results, k));
+        pdq_final_stats.push(get_avg_stats(&pdq_results, k));
+        parallel_quick_final_stats.push(get_avg_stats(&parallel_quick_results, k));
+        sample_final_stats.push(get_avg_stats(&sample_results, k));
+        ips4o_final_stats.push(get_avg_stats(&ips4o_results, k));
+        bitonic_final_stats.push(get_avg_stats(&bitonic_results, k));
+        parallel_bitonic_final_stats.push(get_avg_stats(&parallel_bitonic_results, k));
+        odd_even_merge_final_stats.push(get_avg_stats(&odd_even_merge_results, k));
+        parallel_odd_even_merge_final_stats
+            .push(get_avg_stats(&parallel_odd_even_merge_results, k));
+    }
+
+    for (algorithm, final_stats) in [
+        ("merge", &merge_final_stats),
+        ("parallel_merge", &parallel_merge_final_stats),
+        ("pdq", &pdq_final_stats),
+        ("parallel_quick", &parallel_quick_final_stats),
+        ("sample", &sample_final_stats),
+        ("ips4o", &ips4o_final_stats),
+        ("bitonic", &bitonic_final_stats),
+        ("parallel_bitonic", &parallel_bitonic_final_stats),
+        ("odd_even_merge", &odd_even_merge_final_stats),
+        (
+            "parallel_odd_even_merge",
+            &parallel_odd_even_merge_final_stats,
+        ),
+    ] {
+        print_final_stats_to_file(algorithm, StatType::Comps, final_stats);
+        print_final_stats_to_file(algorithm, StatType::Millis, final_stats);
+    }
+}
+
+fn bench<S: BenchmarkingSorter>(array: &[u32]) -> Stats {
+    let mut benchmark = StandardBenchmarker::default();
+
+    S::sort_with_benchmark(&mut array.to_vec(), &mut benchmark);
+
+    benchmark.get_stats()
+}
+
+fn bench_parallel<S: BenchmarkingParallelSorter>(array: &[u32]) -> Stats {
+    let benchmark = AtomicBenchmarker::default();
+
+    S::sort_with_benchmark(&mut array.to_vec(), &benchmark);
+
+    benchmark.get_stats()
+}
+
+fn get_avg_stats(stats: &[Stats], k: usize) -> (f64, f64) {
+    let (mut comps, mut millis) = stats
+        .iter()
+        .map(
+            |Stats {
+                 comparisons,
+                 duration,
+                 ..
+             }| (*comparisons, duration.as_secs_f64() * 1000.0),
+        )
+        .fold(
+            (0.0, 0.0),
+            |(total_comps, total_millis), (comps, millis)| {
+                (total_comps + comps as f64, total_millis + millis)
+            },
+        );
+
+    comps /= k as f64;
+    millis /= k as f64;
+
+    (comps, millis)
+}
+
+fn print_final_stats_to_file(algorithm: &str, type_of_stat: StatType, final_stats: &[(f64, f64)]) {
+    let type_of_stat_string = match type_of_stat {
+        StatType::Comps => "comps",
+        StatType::Millis => "millis",
+    };
+
+    let filename = format!("{}_{}", algorithm, type_of_stat_string);
+
+    let mut file = File::create(filename).expect("problem creating file");
+
+    let file_content = final_stats
+        .iter()
+        .enumerate()
+        .map(|(i, (comps, millis))| match type_of_stat {
+            StatType::Comps => format!("{} {}", 100000 * (i + 1), comps),
+            StatType::Millis => format!("{} {}", 100000 * (i + 1), millis),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    file.write_all(file_content.as_bytes())
+        .expect("problem writing to file");
+}
+
+enum StatType {
+    Comps,
+    Millis,
+}
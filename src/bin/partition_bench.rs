@@ -0,0 +1,108 @@
+use std::fs::File;
+use std::io::Write;
+
+use algorithms::benchmarking::*;
+use algorithms::generator::*;
+use algorithms::prelude::*;
+use algorithms::quick_sort::{BlockPartitioning, HoarePartitioning};
+
+fn main() {
+    let k = std::env::args()
+        .nth(1)
+        .expect("there has to be a k given")
+        .parse()
+        .expect("k isn't valid number");
+
+    let mut lomuto_final_stats = Vec::with_capacity(10);
+    let mut hoare_final_stats = Vec::with_capacity(10);
+    let mut block_final_stats = Vec::with_capacity(10);
+
+    for i in 1..=10 {
+        let n = 100000 * i;
+
+        let mut lomuto_results = Vec::with_capacity(k);
+        let mut hoare_results = Vec::with_capacity(k);
+        let mut block_results = Vec::with_capacity(k);
+
+        for _ in 0..k {
+            let array = RandomGenerator::generate(n);
+
+            lomuto_results.push(bench::<QuickSort>(&array));
+            hoare_results.push(bench::<QuickSortWith<HoarePartitioning>>(&array));
+            block_results.push(bench::<QuickSortWith<BlockPartitioning>>(&array));
+        }
+
+        lomuto_final_stats.push(get_avg_stats(&lomuto_results, k));
+        hoare_final_stats.push(get_avg_stats(&hoare_results, k));
+        block_final_stats.push(get_avg_stats(&block_results, k));
+    }
+
+    for (algorithm, final_stats) in [
+        ("lomuto", &lomuto_final_stats),
+        ("hoare", &hoare_final_stats),
+        ("block_quick", &block_final_stats),
+    ] {
+        print_final_stats_to_file(algorithm, StatType::Comps, final_stats);
+        print_final_stats_to_file(algorithm, StatType::Millis, final_stats);
+    }
+}
+
+fn bench<S: BenchmarkingSorter>(array: &[u32]) -> Stats {
+    let mut benchmark = StandardBenchmarker::default();
+
+    S::sort_with_benchmark(&mut array.to_vec(), &mut benchmark);
+
+    benchmark.get_stats()
+}
+
+fn get_avg_stats(stats: &[Stats], k: usize) -> (f64, f64) {
+    let (mut comps, mut millis) = stats
+        .iter()
+        .map(
+            |Stats {
+                 comparisons,
+                 duration,
+                 ..
+             }| (*comparisons, duration.as_secs_f64() * 1000.0),
+        )
+        .fold(
+            (0.0, 0.0),
+            |(total_comps, total_millis), (comps, millis)| {
+                (total_comps + comps as f64, total_millis + millis)
+            },
+        );
+
+    comps /= k as f64;
+    millis /= k as f64;
+
+    (comps, millis)
+}
+
+fn print_final_stats_to_file(algorithm: &str, type_of_stat: StatType, final_stats: &[(f64, f64)]) {
+    let type_of_stat_string = match type_of_stat {
+        StatType::Comps => "comps",
+        StatType::Millis => "millis",
+    };
+
+    let filename = format!("{}_{}", algorithm, type_of_stat_string);
+
+    let mut file = File::create(filename).expect("problem creating file");
+
+    let file_content = final_stats
+        .iter()
+        .enumerate()
+        .map(|(i, (comps, millis))| match type_of_stat {
+            StatType::Comps => format!("{} {}", 100000 * (i + 1), comps),
+            StatType::Millis => format!("{} {}", 100000 * (i + 1), millis),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    file.write_all(file_content.as_bytes())
+        .expect("problem writing to file");
+}
+
+enum StatType {
+    Comps,
+    Millis,
+}
@@ -0,0 +1,117 @@
+use std::fs::File;
+use std::io::Write;
+
+use algorithms::benchmarking::*;
+use algorithms::generator::*;
+use algorithms::prelude::*;
+
+fn main() {
+    let k = std::env::args()
+        .nth(1)
+        .expect("there has to be a k given")
+        .parse()
+        .expect("k isn't valid number");
+
+    bench_generator::<RandomGenerator>("random", k);
+    bench_generator::<AscendingGenerator>("ascending", k);
+    bench_generator::<DescendingGenerator>("descending", k);
+}
+
+fn bench_generator<G: Generator>(generator: &str, k: usize) {
+    let mut quick_final_stats = Vec::with_capacity(10);
+    let mut dual_pivot_final_stats = Vec::with_capacity(10);
+    let mut pdq_final_stats = Vec::with_capacity(10);
+    let mut sample_final_stats = Vec::with_capacity(10);
+
+    for i in 1..=10 {
+        let n = 100 * i;
+
+        let mut quick_results = Vec::with_capacity(k);
+        let mut dual_pivot_results = Vec::with_capacity(k);
+        let mut pdq_results = Vec::with_capacity(k);
+        let mut sample_results = Vec::with_capacity(k);
+
+        for _ in 0..k {
+            let array = G::generate(n);
+
+            let mut quick_benchmark = StandardBenchmarker::default();
+            let mut dual_pivot_benchmark = StandardBenchmarker::default();
+            let mut pdq_benchmark = StandardBenchmarker::default();
+            let mut sample_benchmark = StandardBenchmarker::default();
+
+            QuickSort::sort_with_benchmark(&mut array.clone(), &mut quick_benchmark);
+            DualPivotQuicksort::sort_with_benchmark(&mut array.clone(), &mut dual_pivot_benchmark);
+            PdqSort::sort_with_benchmark(&mut array.clone(), &mut pdq_benchmark);
+            SampleSort::sort_with_benchmark(&mut array.clone(), &mut sample_benchmark);
+
+            quick_results.push(quick_benchmark.get_stats());
+            dual_pivot_results.push(dual_pivot_benchmark.get_stats());
+            pdq_results.push(pdq_benchmark.get_stats());
+            sample_results.push(sample_benchmark.get_stats());
+        }
+
+        quick_final_stats.push(get_avg_stats(&quick_results, k));
+        dual_pivot_final_stats.push(get_avg_stats(&dual_pivot_results, k));
+        pdq_final_stats.push(get_avg_stats(&pdq_results, k));
+        sample_final_stats.push(get_avg_stats(&sample_results, k));
+    }
+
+    for (algorithm, final_stats) in [
+        ("quick", &quick_final_stats),
+        ("dual_pivot", &dual_pivot_final_stats),
+        ("pdq", &pdq_final_stats),
+        ("sample", &sample_final_stats),
+    ] {
+        let name = format!("{}_{}", algorithm, generator);
+
+        print_final_stats_to_file(&name, StatType::Comps, final_stats);
+        print_final_stats_to_file(&name, StatType::Swaps, final_stats);
+    }
+}
+
+fn get_avg_stats(stats: &[Stats], k: usize) -> (f64, f64) {
+    let (mut comps, mut swaps) = stats
+        .iter()
+        .map(
+            |Stats {
+                 comparisons, swaps, ..
+             }| (*comparisons, *swaps),
+        )
+        .fold((0.0, 0.0), |(total_comps, total_swaps), (comps, swaps)| {
+            (total_comps + comps as f64, total_swaps + swaps as f64)
+        });
+
+    comps /= k as f64;
+    swaps /= k as f64;
+
+    (comps, swaps)
+}
+
+fn print_final_stats_to_file(algorithm: &str, type_of_stat: StatType, final_stats: &[(f64, f64)]) {
+    let type_of_stat_string = match type_of_stat {
+        StatType::Comps => "comps",
+        StatType::Swaps => "swaps",
+    };
+
+    let filename = format!("{}_{}", algorithm, type_of_stat_string);
+
+    let mut file = File::create(filename).expect("problem creating file");
+
+    let file_content = final_stats
+        .iter()
+        .enumerate()
+        .map(|(i, (comps, swaps))| match type_of_stat {
+            StatType::Comps => format!("{} {}", 100 * (i + 1), comps),
+            StatType::Swaps => format!("{} {}", 100 * (i + 1), swaps),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    file.write_all(file_content.as_bytes())
+        .expect("problem writing to file");
+}
+
+enum StatType {
+    Comps,
+    Swaps,
+}
@@ -0,0 +1,127 @@
+use std::fs::File;
+use std::io::Write;
+
+use algorithms::benchmarking::*;
+use algorithms::generator::*;
+use algorithms::prelude::*;
+
+fn main() {
+    let k = std::env::args()
+        .nth(1)
+        .expect("there has to be a k given")
+        .parse()
+        .expect("k isn't valid number");
+
+    let mut quick_final_stats = Vec::with_capacity(10);
+    let mut lsd_radix_final_stats = Vec::with_capacity(10);
+    let mut msd_radix_final_stats = Vec::with_capacity(10);
+    let mut counting_final_stats = Vec::with_capacity(10);
+    let mut bucket_final_stats = Vec::with_capacity(10);
+
+    for i in 1..=10 {
+        let n = 10000 * i;
+
+        let mut quick_results = Vec::with_capacity(k);
+        let mut lsd_radix_results = Vec::with_capacity(k);
+        let mut msd_radix_results = Vec::with_capacity(k);
+        let mut counting_results = Vec::with_capacity(k);
+        let mut bucket_results = Vec::with_capacity(k);
+
+        for _ in 0..k {
+            let random_array = RandomGenerator::generate(n);
+
+            let mut quick_benchmark = StandardBenchmarker::default();
+            let mut lsd_radix_benchmark = StandardBenchmarker::default();
+            let mut msd_radix_benchmark = StandardBenchmarker::default();
+            let mut counting_benchmark = StandardBenchmarker::default();
+            let mut bucket_benchmark = StandardBenchmarker::default();
+
+            QuickSort::sort_with_benchmark(&mut random_array.clone(), &mut quick_benchmark);
+            LsdRadixSort::<8>::sort_with_benchmark(
+                &mut random_array.clone(),
+                &mut lsd_radix_benchmark,
+            );
+            MsdRadixSort::sort_with_benchmark(&mut random_array.clone(), &mut msd_radix_benchmark);
+            CountingSort::sort_with_benchmark(&mut random_array.clone(), &mut counting_benchmark);
+            BucketSort::<InsertionSort>::sort_with_benchmark(
+                &mut random_array.clone(),
+                &mut bucket_benchmark,
+            );
+
+            quick_results.push(quick_benchmark.get_stats());
+            lsd_radix_results.push(lsd_radix_benchmark.get_stats());
+            msd_radix_results.push(msd_radix_benchmark.get_stats());
+            counting_results.push(counting_benchmark.get_stats());
+            bucket_results.push(bucket_benchmark.get_stats());
+        }
+
+        quick_final_stats.push(get_avg_stats(&quick_results, k));
+        lsd_radix_final_stats.push(get_avg_stats(&lsd_radix_results, k));
+        msd_radix_final_stats.push(get_avg_stats(&msd_radix_results, k));
+        counting_final_stats.push(get_avg_stats(&counting_results, k));
+        bucket_final_stats.push(get_avg_stats(&bucket_results, k));
+    }
+
+    print_final_stats_to_file("quick", StatType::Time, &quick_final_stats);
+    print_final_stats_to_file("quick", StatType::Moves, &quick_final_stats);
+    print_final_stats_to_file("lsd_radix", StatType::Time, &lsd_radix_final_stats);
+    print_final_stats_to_file("lsd_radix", StatType::Moves, &lsd_radix_final_stats);
+    print_final_stats_to_file("msd_radix", StatType::Time, &msd_radix_final_stats);
+    print_final_stats_to_file("msd_radix", StatType::Moves, &msd_radix_final_stats);
+    print_final_stats_to_file("counting", StatType::Time, &counting_final_stats);
+    print_final_stats_to_file("counting", StatType::Moves, &counting_final_stats);
+    print_final_stats_to_file("bucket", StatType::Time, &bucket_final_stats);
+    print_final_stats_to_file("bucket", StatType::Moves, &bucket_final_stats);
+}
+
+/// Returns average time in microseconds and average number of element
+/// moves, where every swap counts as a single move.
+fn get_avg_stats(stats: &[Stats], k: usize) -> (f64, f64) {
+    let (mut time, mut moves) = stats
+        .iter()
+        .map(
+            |Stats {
+                 duration,
+                 swaps,
+                 moves,
+                 ..
+             }| (duration.as_micros(), swaps + moves),
+        )
+        .fold((0.0, 0.0), |(total_time, total_moves), (time, moves)| {
+            (total_time + time as f64, total_moves + moves as f64)
+        });
+
+    time /= k as f64;
+    moves /= k as f64;
+
+    (time, moves)
+}
+
+fn print_final_stats_to_file(algorithm: &str, type_of_stat: StatType, final_stats: &[(f64, f64)]) {
+    let type_of_stat_string = match type_of_stat {
+        StatType::Time => "time",
+        StatType::Moves => "moves",
+    };
+
+    let filename = format!("{}_{}", algorithm, type_of_stat_string);
+
+    let mut file = File::create(filename).expect("problem creating file");
+
+    let file_content = final_stats
+        .iter()
+        .enumerate()
+        .map(|(i, (time, moves))| match type_of_stat {
+            StatType::Time => format!("{} {}", 10000 * (i + 1), time),
+            StatType::Moves => format!("{} {}", 10000 * (i + 1), moves),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    file.write_all(file_content.as_bytes())
+        .expect("problem writing to file");
+}
+
+enum StatType {
+    Time,
+    Moves,
+}
@@ -15,6 +15,8 @@ fn main() {
     let mut insertion_final_stats = Vec::with_capacity(10);
     let mut merge_final_stats = Vec::with_capacity(10);
     let mut quick_final_stats = Vec::with_capacity(10);
+    let mut selection_final_stats = Vec::with_capacity(10);
+    let mut cycle_final_stats = Vec::with_capacity(10);
 
     for i in 1..=10 {
         let n = 100 * i;
@@ -22,6 +24,8 @@ fn main() {
         let mut insertion_results = Vec::with_capacity(k);
         let mut merge_results = Vec::with_capacity(k);
         let mut quick_results = Vec::with_capacity(k);
+        let mut selection_results = Vec::with_capacity(k);
+        let mut cycle_results = Vec::with_capacity(k);
 
         for _ in 0..k {
             let random_array = RandomGenerator::generate(n);
@@ -42,6 +46,17 @@ fn main() {
                     &mut insertion_benchmark,
                 );
                 insertion_results.push(insertion_benchmark.get_stats());
+
+                let mut selection_benchmark = StandardBenchmarker::default();
+                SelectionSort::sort_with_benchmark(
+                    &mut random_array.clone(),
+                    &mut selection_benchmark,
+                );
+                selection_results.push(selection_benchmark.get_stats());
+
+                let mut cycle_benchmark = StandardBenchmarker::default();
+                CycleSort::sort_with_benchmark(&mut random_array.clone(), &mut cycle_benchmark);
+                cycle_results.push(cycle_benchmark.get_stats());
             }
         }
 
@@ -54,39 +69,62 @@ fn main() {
         if n < 500 {
             let insertion_comps_and_swaps = get_avg_stats(&insertion_results, k);
             insertion_final_stats.push(insertion_comps_and_swaps);
+            selection_final_stats.push(get_avg_stats(&selection_results, k));
+            cycle_final_stats.push(get_avg_stats(&cycle_results, k));
         }
     }
 
-    print_final_stats_to_file("insertion", StatType::Comps, &insertion_final_stats);
-    print_final_stats_to_file("insertion", StatType::Swaps, &insertion_final_stats);
-    print_final_stats_to_file("merge", StatType::Comps, &merge_final_stats);
-    print_final_stats_to_file("merge", StatType::Swaps, &merge_final_stats);
-    print_final_stats_to_file("quick", StatType::Comps, &quick_final_stats);
-    print_final_stats_to_file("quick", StatType::Swaps, &quick_final_stats);
+    for (algorithm, final_stats) in [
+        ("insertion", &insertion_final_stats),
+        ("merge", &merge_final_stats),
+        ("quick", &quick_final_stats),
+        ("selection", &selection_final_stats),
+        ("cycle", &cycle_final_stats),
+    ] {
+        print_final_stats_to_file(algorithm, StatType::Comps, final_stats);
+        print_final_stats_to_file(algorithm, StatType::Swaps, final_stats);
+        print_final_stats_to_file(algorithm, StatType::Writes, final_stats);
+    }
 }
 
-fn get_avg_stats(stats: &[Stats], k: usize) -> (f64, f64) {
-    let (mut insertion_comps, mut insertion_swaps) = stats
+fn get_avg_stats(stats: &[Stats], k: usize) -> (f64, f64, f64) {
+    let (mut insertion_comps, mut insertion_swaps, mut insertion_writes) = stats
         .iter()
         .map(
             |Stats {
-                 comparisons, swaps, ..
-             }| (*comparisons, *swaps),
+                 comparisons,
+                 swaps,
+                 writes,
+                 ..
+             }| (*comparisons, *swaps, *writes),
         )
-        .fold((0.0, 0.0), |(total_comps, total_swaps), (comps, swaps)| {
-            (total_comps + comps as f64, total_swaps + swaps as f64)
-        });
+        .fold(
+            (0.0, 0.0, 0.0),
+            |(total_comps, total_swaps, total_writes), (comps, swaps, writes)| {
+                (
+                    total_comps + comps as f64,
+                    total_swaps + swaps as f64,
+                    total_writes + writes as f64,
+                )
+            },
+        );
 
     insertion_comps /= k as f64;
     insertion_swaps /= k as f64;
+    insertion_writes /= k as f64;
 
-    (insertion_comps, insertion_swaps)
+    (insertion_comps, insertion_swaps, insertion_writes)
 }
 
-fn print_final_stats_to_file(algorithm: &str, type_of_stat: StatType, final_stats: &[(f64, f64)]) {
+fn print_final_stats_to_file(
+    algorithm: &str,
+    type_of_stat: StatType,
+    final_stats: &[(f64, f64, f64)],
+) {
     let type_of_stat_string = match type_of_stat {
         StatType::Comps => "comps",
         StatType::Swaps => "swaps",
+        StatType::Writes => "writes",
     };
 
     let filename = format!("{}_{}", algorithm, type_of_stat_string);
@@ -96,9 +134,10 @@ fn print_final_stats_to_file(algorithm: &str, type_of_stat: StatType, final_stat
     let file_content = final_stats
         .iter()
         .enumerate()
-        .map(|(i, (comps, swaps))| match type_of_stat {
+        .map(|(i, (comps, swaps, writes))| match type_of_stat {
             StatType::Comps => format!("{} {}", 100 * (i + 1), comps),
             StatType::Swaps => format!("{} {}", 100 * (i + 1), swaps),
+            StatType::Writes => format!("{} {}", 100 * (i + 1), writes),
         })
         .collect::<Vec<_>>()
         .join("\n");
@@ -110,4 +149,5 @@ fn print_final_stats_to_file(algorithm: &str, type_of_stat: StatType, final_stat
 enum StatType {
     Comps,
     Swaps,
+    Writes,
 }
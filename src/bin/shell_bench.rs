@@ -0,0 +1,95 @@
+use std::fs::File;
+use std::io::Write;
+
+use algorithms::benchmarking::*;
+use algorithms::generator::*;
+use algorithms::prelude::*;
+use algorithms::shell_sort::*;
+
+fn main() {
+    let k = std::env::args()
+        .nth(1)
+        .expect("there has to be a k given")
+        .parse()
+        .expect("k isn't valid number");
+
+    bench_sorter::<ShellSort<ShellGaps>>("shell_shell", k);
+    bench_sorter::<ShellSort<KnuthGaps>>("shell_knuth", k);
+    bench_sorter::<ShellSort<HibbardGaps>>("shell_hibbard", k);
+    bench_sorter::<ShellSort<SedgewickGaps>>("shell_sedgewick", k);
+    bench_sorter::<ShellSort<TokudaGaps>>("shell_tokuda", k);
+    bench_sorter::<ShellSort<CiuraGaps>>("shell_ciura", k);
+    bench_sorter::<InsertionSort>("insertion", k);
+    bench_sorter::<MergeSort>("merge", k);
+}
+
+fn bench_sorter<S: BenchmarkingSorter>(algorithm: &str, k: usize) {
+    let mut final_stats = Vec::with_capacity(10);
+
+    for i in 1..=10 {
+        let n = 1000 * i;
+
+        let mut results = Vec::with_capacity(k);
+
+        for _ in 0..k {
+            let mut random_array = RandomGenerator::generate(n);
+            let mut benchmark = StandardBenchmarker::default();
+
+            S::sort_with_benchmark(&mut random_array, &mut benchmark);
+
+            results.push(benchmark.get_stats());
+        }
+
+        final_stats.push(get_avg_stats(&results, k));
+    }
+
+    print_final_stats_to_file(algorithm, StatType::Comps, &final_stats);
+    print_final_stats_to_file(algorithm, StatType::Swaps, &final_stats);
+}
+
+fn get_avg_stats(stats: &[Stats], k: usize) -> (f64, f64) {
+    let (mut comps, mut swaps) = stats
+        .iter()
+        .map(
+            |Stats {
+                 comparisons, swaps, ..
+             }| (*comparisons, *swaps),
+        )
+        .fold((0.0, 0.0), |(total_comps, total_swaps), (comps, swaps)| {
+            (total_comps + comps as f64, total_swaps + swaps as f64)
+        });
+
+    comps /= k as f64;
+    swaps /= k as f64;
+
+    (comps, swaps)
+}
+
+fn print_final_stats_to_file(algorithm: &str, type_of_stat: StatType, final_stats: &[(f64, f64)]) {
+    let type_of_stat_string = match type_of_stat {
+        StatType::Comps => "comps",
+        StatType::Swaps => "swaps",
+    };
+
+    let filename = format!("{}_{}", algorithm, type_of_stat_string);
+
+    let mut file = File::create(filename).expect("problem creating file");
+
+    let file_content = final_stats
+        .iter()
+        .enumerate()
+        .map(|(i, (comps, swaps))| match type_of_stat {
+            StatType::Comps => format!("{} {}", 1000 * (i + 1), comps),
+            StatType::Swaps => format!("{} {}", 1000 * (i + 1), swaps),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    file.write_all(file_content.as_bytes())
+        .expect("problem writing to file");
+}
+
+enum StatType {
+    Comps,
+    Swaps,
+}
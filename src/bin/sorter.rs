@@ -2,7 +2,10 @@ use std::io::Read;
 
 use algorithms::benchmarking::StandardBenchmarker;
 use algorithms::benchmarking::Stats;
+use algorithms::oblivious_sort::{BitonicSort, OddEvenMergeSort, OddEvenTranspositionSort};
 use algorithms::prelude::*;
+use algorithms::quick_sort::{BlockPartitioning, HoarePartitioning};
+use algorithms::shell_sort::CiuraGaps;
 
 fn main() {
     let mode = std::env::args()
@@ -32,9 +35,40 @@ fn main() {
 
     match mode.as_str() {
         "insertion" => InsertionSort::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "binary_insertion" => BinaryInsertionSort::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "merge_insertion" => MergeInsertionSort::sort_with_benchmark(&mut numbers, &mut benchmark),
         "merge" => MergeSort::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "bottom_up_merge" => BottomUpMergeSort::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "natural_merge" => NaturalMergeSort::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "ping_pong_merge" => PingPongMergeSort::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "in_place_merge" => InPlaceMergeSort::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "block" => BlockSort::sort_with_benchmark(&mut numbers, &mut benchmark),
         "quick" => QuickSort::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "hoare_quick" => QuickSortWith::<HoarePartitioning>::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "block_quick" => QuickSortWith::<BlockPartitioning>::sort_with_benchmark(&mut numbers, &mut benchmark),
         "dual_pivot" => DualPivotQuicksort::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "pdq" => PdqSort::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "three_way_quick" => ThreeWayQuicksort::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "multi_pivot" => MultiPivotQuicksort::<3>::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "sample" => SampleSort::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "network" => NetworkSort::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "network_tim" => TimSortWith::<NetworkSort>::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "selection" => SelectionSort::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "cycle" => CycleSort::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "heap" => HeapSort::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "smooth" => SmoothSort::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "weak_heap" => WeakHeapSort::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "adaptive_weak_heap" => AdaptiveWeakHeapSort::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "bitonic" => BitonicSort::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "odd_even_merge" => OddEvenMergeSort::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "transposition" => OddEvenTranspositionSort::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "shell" => ShellSort::<CiuraGaps>::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "lsd_radix" => LsdRadixSort::<8>::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "msd_radix" => MsdRadixSort::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "counting" => CountingSort::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "bucket" => BucketSort::<InsertionSort>::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "adaptive" => AdaptiveSort::sort_with_benchmark(&mut numbers, &mut benchmark),
+        "adaptive_integer" => AdaptiveIntegerSort::sort_with_benchmark(&mut numbers, &mut benchmark),
         _ => panic!("wrong program mode"),
     }
 
@@ -43,11 +77,28 @@ fn main() {
     }
 
     let Stats {
-        comparisons, swaps, ..
+        comparisons,
+        swaps,
+        passes,
+        moves,
+        allocated_bytes,
+        scanned_elements,
+        writes,
+        decisions,
+        ..
     } = benchmark.get_stats();
 
     println!("Number of comparisons: {}", comparisons);
     println!("Number of swaps: {}", swaps);
+    println!("Number of passes: {}", passes);
+    println!("Number of moves: {}", moves);
+    println!("Allocated bytes: {}", allocated_bytes);
+    println!("Scanned elements: {}", scanned_elements);
+    println!("Number of writes: {}", writes);
+
+    for decision in decisions {
+        println!("Chosen algorithm: {} ({})", decision.algorithm, decision.reason);
+    }
 
     let mut sorted = true;
 
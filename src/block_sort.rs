@@ -0,0 +1,728 @@
+//! Block merge sort algorithm implementation.
+
+use std::cmp::Ordering;
+use std::mem;
+
+use crate::merging;
+use crate::prelude::*;
+use crate::rotation;
+use crate::tuning::Parameter;
+
+/// Struct implementing `Sorter` + `BenchmarkingSorter` using GrailSort style
+/// block merge sort to sort items stably with O(1) auxiliary memory.
+///
+/// About `2 * sqrt(n)` distinct items are first collected at the front of
+/// the slice. Part of them tags blocks of `sqrt(n)` items and the rest is
+/// used as a buffer which items are swapped with, so its contents are never
+/// lost. Runs longer than the buffer are merged by sorting their blocks by
+/// first item and merging neighbouring blocks coming from different runs.
+/// At the end keys are sorted and merged back in place. When there are too
+/// few distinct items, runs are merged with rotations instead.
+pub struct BlockSort;
+
+impl BlockSort {
+    fn block_sort<T: Ord + Copy>(slice: &mut [T]) {
+        let len = slice.len();
+        // runs and blocks have to be merged with the same length
+        let run_len = Parameter::BlockSortRunLen.get();
+
+        if len <= run_len {
+            InsertionSort::sort(slice);
+            return;
+        }
+
+        let mut block_len = run_len;
+
+        while block_len * block_len < len {
+            block_len *= 2;
+        }
+
+        let tags_len = len / block_len + 2;
+        let keys_len = BlockSort::collect_keys(slice, tags_len + block_len);
+
+        if keys_len < tags_len + block_len {
+            BlockSort::rotation_merge_sort(slice, run_len);
+            return;
+        }
+
+        let (keys, rest) = slice.split_at_mut(keys_len);
+        let (tags, buffer) = keys.split_at_mut(tags_len);
+
+        BlockSort::merge_levels(rest, tags, buffer, run_len);
+
+        InsertionSort::sort(keys);
+        merging::sym_merge(slice, keys_len);
+    }
+
+    fn collect_keys<T: Ord + Copy>(slice: &mut [T], needed: usize) -> usize {
+        let mut keys_start = 0;
+        let mut keys_len = 1;
+
+        for i in 1..slice.len() {
+            if keys_len == needed {
+                break;
+            }
+
+            let value = slice[i];
+            let keys = &slice[keys_start..keys_start + keys_len];
+            let position = keys.partition_point(|&key| key < value);
+
+            if position < keys_len && keys[position] == value {
+                continue;
+            }
+
+            rotation::block_swap_rotate(&mut slice[keys_start..i], keys_len);
+            keys_start = i - keys_len;
+
+            rotation::block_swap_rotate(&mut slice[keys_start + position..=i], keys_len - position);
+            keys_len += 1;
+        }
+
+        rotation::block_swap_rotate(&mut slice[..keys_start + keys_len], keys_start);
+
+        keys_len
+    }
+
+    fn merge_levels<T: Ord + Copy>(
+        slice: &mut [T],
+        tags: &mut [T],
+        buffer: &mut [T],
+        run_len: usize,
+    ) {
+        for run in slice.chunks_mut(run_len) {
+            InsertionSort::sort(run);
+        }
+
+        let len = slice.len();
+        let mut width = run_len;
+
+        while width < len {
+            for start in (0..len - width).step_by(2 * width) {
+                let end = (start + 2 * width).min(len);
+                let pair = &mut slice[start..end];
+
+                if pair[width - 1] <= pair[width] {
+                    continue;
+                }
+
+                if width <= buffer.len() {
+                    BlockSort::smart_merge(pair, 0, width, end - start, true, buffer);
+                } else {
+                    BlockSort::block_merge(pair, width, tags, buffer);
+                }
+            }
+
+            width *= 2;
+        }
+    }
+
+    fn block_merge<T: Ord + Copy>(slice: &mut [T], mid: usize, tags: &mut [T], buffer: &mut [T]) {
+        let block_len = buffer.len();
+        let a_blocks = mid / block_len;
+        let blocks = a_blocks + (slice.len() - mid) / block_len;
+        let tail = blocks * block_len;
+        let midkey = tags[a_blocks];
+
+        for i in 0..blocks {
+            let mut min = i;
+
+            for j in i + 1..blocks {
+                let less = match slice[j * block_len].cmp(&slice[min * block_len]) {
+                    Ordering::Less => true,
+                    Ordering::Greater => false,
+                    Ordering::Equal => tags[j] < tags[min],
+                };
+
+                if less {
+                    min = j;
+                }
+            }
+
+            if min != i {
+                let (left, right) = slice.split_at_mut(min * block_len);
+
+                left[i * block_len..(i + 1) * block_len].swap_with_slice(&mut right[..block_len]);
+
+                tags.swap(i, min);
+            }
+        }
+
+        let mut fragment_start = 0;
+        let mut fragment_is_a = tags[0] < midkey;
+
+        for (i, &tag) in tags[..blocks].iter().enumerate().skip(1) {
+            let block_start = i * block_len;
+            let block_is_a = tag < midkey;
+
+            if block_is_a == fragment_is_a {
+                fragment_start = block_start;
+            } else {
+                (fragment_start, fragment_is_a) = BlockSort::smart_merge(
+                    slice,
+                    fragment_start,
+                    block_start,
+                    block_start + block_len,
+                    fragment_is_a,
+                    buffer,
+                );
+            }
+        }
+
+        if tail < slice.len() {
+            BlockSort::merge_tail(slice, tail, buffer);
+        }
+
+        InsertionSort::sort(&mut tags[..blocks]);
+    }
+
+    fn smart_merge<T: Ord + Copy>(
+        slice: &mut [T],
+        start: usize,
+        mid: usize,
+        end: usize,
+        fragment_is_a: bool,
+        buffer: &mut [T],
+    ) -> (usize, bool) {
+        let fragment_len = mid - start;
+
+        slice[start..mid].swap_with_slice(&mut buffer[..fragment_len]);
+
+        let mut i = 0;
+        let mut j = mid;
+        let mut dest = start;
+
+        while i < fragment_len && j < end {
+            let take_fragment = if fragment_is_a {
+                buffer[i] <= slice[j]
+            } else {
+                buffer[i] < slice[j]
+            };
+
+            if take_fragment {
+                mem::swap(&mut slice[dest], &mut buffer[i]);
+                i += 1;
+            } else {
+                slice.swap(dest, j);
+                j += 1;
+            }
+
+            dest += 1;
+        }
+
+        if i < fragment_len {
+            slice[dest..end].swap_with_slice(&mut buffer[i..fragment_len]);
+
+            (dest, fragment_is_a)
+        } else {
+            (j, !fragment_is_a)
+        }
+    }
+
+    fn merge_tail<T: Ord + Copy>(slice: &mut [T], tail: usize, buffer: &mut [T]) {
+        let tail_len = slice.len() - tail;
+
+        slice[tail..].swap_with_slice(&mut buffer[..tail_len]);
+
+        let mut i = tail_len;
+        let mut j = tail;
+        let mut dest = slice.len();
+
+        while i > 0 && j > 0 {
+            dest -= 1;
+
+            if buffer[i - 1] >= slice[j - 1] {
+                mem::swap(&mut slice[dest], &mut buffer[i - 1]);
+                i -= 1;
+            } else {
+                slice.swap(dest, j - 1);
+                j -= 1;
+            }
+        }
+
+        slice[..i].swap_with_slice(&mut buffer[..i]);
+    }
+
+    fn rotation_merge_sort<T: Ord + Copy>(slice: &mut [T], run_len: usize) {
+        for run in slice.chunks_mut(run_len) {
+            InsertionSort::sort(run);
+        }
+
+        let len = slice.len();
+        let mut width = run_len;
+
+        while width < len {
+            for start in (0..len - width).step_by(2 * width) {
+                let end = (start + 2 * width).min(len);
+
+                merging::sym_merge(&mut slice[start..end], width);
+            }
+
+            width *= 2;
+        }
+    }
+
+    fn block_sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        let len = slice.len();
+        // runs and blocks have to be merged with the same length
+        let run_len = Parameter::BlockSortRunLen.get();
+
+        if len <= run_len {
+            InsertionSort::insertion_sort_with_benchmark(slice, benchmark);
+            return;
+        }
+
+        let mut block_len = run_len;
+
+        while block_len * block_len < len {
+            block_len *= 2;
+        }
+
+        let tags_len = len / block_len + 2;
+        let keys_len =
+            BlockSort::collect_keys_with_benchmark(slice, tags_len + block_len, benchmark);
+
+        if keys_len < tags_len + block_len {
+            BlockSort::rotation_merge_sort_with_benchmark(slice, run_len, benchmark);
+            return;
+        }
+
+        let (keys, rest) = slice.split_at_mut(keys_len);
+        let (tags, buffer) = keys.split_at_mut(tags_len);
+
+        BlockSort::merge_levels_with_benchmark(rest, tags, buffer, run_len, benchmark);
+
+        InsertionSort::insertion_sort_with_benchmark(keys, benchmark);
+        merging::sym_merge_with_benchmark(slice, keys_len, benchmark);
+    }
+
+    fn collect_keys_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        needed: usize,
+        benchmark: &mut impl Benchmark,
+    ) -> usize {
+        let mut keys_start = 0;
+        let mut keys_len = 1;
+
+        for i in 1..slice.len() {
+            if keys_len == needed {
+                break;
+            }
+
+            let value = slice[i];
+            let keys = &slice[keys_start..keys_start + keys_len];
+            let position = keys.partition_point(|&key| {
+                benchmark.add_cmp();
+                key < value
+            });
+
+            if position < keys_len {
+                benchmark.add_cmp();
+
+                if keys[position] == value {
+                    continue;
+                }
+            }
+
+            rotation::block_swap_rotate_with_benchmark(
+                &mut slice[keys_start..i],
+                keys_len,
+                benchmark,
+            );
+            keys_start = i - keys_len;
+
+            rotation::block_swap_rotate_with_benchmark(
+                &mut slice[keys_start + position..=i],
+                keys_len - position,
+                benchmark,
+            );
+            keys_len += 1;
+        }
+
+        rotation::block_swap_rotate_with_benchmark(
+            &mut slice[..keys_start + keys_len],
+            keys_start,
+            benchmark,
+        );
+
+        keys_len
+    }
+
+    fn merge_levels_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        tags: &mut [T],
+        buffer: &mut [T],
+        run_len: usize,
+        benchmark: &mut impl Benchmark,
+    ) {
+        for run in slice.chunks_mut(run_len) {
+            InsertionSort::insertion_sort_with_benchmark(run, benchmark);
+        }
+
+        let len = slice.len();
+        let mut width = run_len;
+
+        while width < len {
+            benchmark.add_pass();
+
+            for start in (0..len - width).step_by(2 * width) {
+                let end = (start + 2 * width).min(len);
+                let pair = &mut slice[start..end];
+
+                benchmark.add_cmp();
+
+                if pair[width - 1] <= pair[width] {
+                    continue;
+                }
+
+                if width <= buffer.len() {
+                    BlockSort::smart_merge_with_benchmark(
+                        pair,
+                        0,
+                        width,
+                        end - start,
+                        true,
+                        buffer,
+                        benchmark,
+                    );
+                } else {
+                    BlockSort::block_merge_with_benchmark(pair, width, tags, buffer, benchmark);
+                }
+            }
+
+            width *= 2;
+        }
+    }
+
+    fn block_merge_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        mid: usize,
+        tags: &mut [T],
+        buffer: &mut [T],
+        benchmark: &mut impl Benchmark,
+    ) {
+        let block_len = buffer.len();
+        let a_blocks = mid / block_len;
+        let blocks = a_blocks + (slice.len() - mid) / block_len;
+        let tail = blocks * block_len;
+        let midkey = tags[a_blocks];
+
+        for i in 0..blocks {
+            let mut min = i;
+
+            for j in i + 1..blocks {
+                benchmark.add_cmp();
+
+                let less = match slice[j * block_len].cmp(&slice[min * block_len]) {
+                    Ordering::Less => true,
+                    Ordering::Greater => false,
+                    Ordering::Equal => {
+                        benchmark.add_cmp();
+                        tags[j] < tags[min]
+                    }
+                };
+
+                if less {
+                    min = j;
+                }
+            }
+
+            if min != i {
+                let (left, right) = slice.split_at_mut(min * block_len);
+
+                BlockSort::swap_blocks_with_benchmark(
+                    &mut left[i * block_len..(i + 1) * block_len],
+                    &mut right[..block_len],
+                    benchmark,
+                );
+
+                benchmark.add_swap();
+                benchmark.add_writes(2);
+                tags.swap(i, min);
+            }
+        }
+
+        let mut fragment_start = 0;
+        let mut fragment_is_a = BlockSort::is_a_with_benchmark(tags[0], midkey, benchmark);
+
+        for (i, &tag) in tags[..blocks].iter().enumerate().skip(1) {
+            let block_start = i * block_len;
+            let block_is_a = BlockSort::is_a_with_benchmark(tag, midkey, benchmark);
+
+            if block_is_a == fragment_is_a {
+                fragment_start = block_start;
+            } else {
+                (fragment_start, fragment_is_a) = BlockSort::smart_merge_with_benchmark(
+                    slice,
+                    fragment_start,
+                    block_start,
+                    block_start + block_len,
+                    fragment_is_a,
+                    buffer,
+                    benchmark,
+                );
+            }
+        }
+
+        if tail < slice.len() {
+            BlockSort::merge_tail_with_benchmark(slice, tail, buffer, benchmark);
+        }
+
+        InsertionSort::insertion_sort_with_benchmark(&mut tags[..blocks], benchmark);
+    }
+
+    fn smart_merge_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        start: usize,
+        mid: usize,
+        end: usize,
+        fragment_is_a: bool,
+        buffer: &mut [T],
+        benchmark: &mut impl Benchmark,
+    ) -> (usize, bool) {
+        let fragment_len = mid - start;
+
+        BlockSort::swap_blocks_with_benchmark(
+            &mut slice[start..mid],
+            &mut buffer[..fragment_len],
+            benchmark,
+        );
+
+        let mut i = 0;
+        let mut j = mid;
+        let mut dest = start;
+
+        while i < fragment_len && j < end {
+            benchmark.add_cmp();
+
+            let take_fragment = if fragment_is_a {
+                buffer[i] <= slice[j]
+            } else {
+                buffer[i] < slice[j]
+            };
+
+            benchmark.add_swap();
+            benchmark.add_writes(2);
+
+            if take_fragment {
+                mem::swap(&mut slice[dest], &mut buffer[i]);
+                i += 1;
+            } else {
+                slice.swap(dest, j);
+                j += 1;
+            }
+
+            dest += 1;
+        }
+
+        if i < fragment_len {
+            BlockSort::swap_blocks_with_benchmark(
+                &mut slice[dest..end],
+                &mut buffer[i..fragment_len],
+                benchmark,
+            );
+
+            (dest, fragment_is_a)
+        } else {
+            (j, !fragment_is_a)
+        }
+    }
+
+    fn merge_tail_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        tail: usize,
+        buffer: &mut [T],
+        benchmark: &mut impl Benchmark,
+    ) {
+        let tail_len = slice.len() - tail;
+
+        BlockSort::swap_blocks_with_benchmark(
+            &mut slice[tail..],
+            &mut buffer[..tail_len],
+            benchmark,
+        );
+
+        let mut i = tail_len;
+        let mut j = tail;
+        let mut dest = slice.len();
+
+        while i > 0 && j > 0 {
+            dest -= 1;
+
+            benchmark.add_cmp();
+            benchmark.add_swap();
+            benchmark.add_writes(2);
+
+            if buffer[i - 1] >= slice[j - 1] {
+                mem::swap(&mut slice[dest], &mut buffer[i - 1]);
+                i -= 1;
+            } else {
+                slice.swap(dest, j - 1);
+                j -= 1;
+            }
+        }
+
+        BlockSort::swap_blocks_with_benchmark(&mut slice[..i], &mut buffer[..i], benchmark);
+    }
+
+    fn rotation_merge_sort_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        run_len: usize,
+        benchmark: &mut impl Benchmark,
+    ) {
+        for run in slice.chunks_mut(run_len) {
+            InsertionSort::insertion_sort_with_benchmark(run, benchmark);
+        }
+
+        let len = slice.len();
+        let mut width = run_len;
+
+        while width < len {
+            benchmark.add_pass();
+
+            for start in (0..len - width).step_by(2 * width) {
+                let end = (start + 2 * width).min(len);
+
+                merging::sym_merge_with_benchmark(&mut slice[start..end], width, benchmark);
+            }
+
+            width *= 2;
+        }
+    }
+
+    fn swap_blocks_with_benchmark<T: Copy>(
+        a: &mut [T],
+        b: &mut [T],
+        benchmark: &mut impl Benchmark,
+    ) {
+        for _ in 0..a.len() {
+            benchmark.add_swap();
+            benchmark.add_writes(2);
+        }
+
+        a.swap_with_slice(b);
+    }
+
+    fn is_a_with_benchmark<T: Ord + Copy>(
+        tag: T,
+        midkey: T,
+        benchmark: &mut impl Benchmark,
+    ) -> bool {
+        benchmark.add_cmp();
+
+        tag < midkey
+    }
+}
+
+impl Sorter for BlockSort {
+    /// Block merge sort implementation working on types implementing
+    /// `Ord` + `Copy` so it's mostly usefull for primitive types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    ///
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// BlockSort::sort(&mut slice);
+    ///
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// ```
+    fn sort<T: Ord + Copy>(slice: &mut [T]) {
+        BlockSort::block_sort(slice);
+    }
+}
+
+impl BenchmarkingSorter for BlockSort {
+    /// Block merge sort implementation with additional benchmarking
+    /// capabilities. Items are only ever swapped, never copied out of slice,
+    /// so all of their movement is counted as swaps.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::StandardBenchmarker;
+    ///
+    /// let mut benchmarker = StandardBenchmarker::default();
+    /// let mut slice = (0..1000).rev().collect::<Vec<_>>();
+    ///
+    /// BlockSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!((0..1000).collect::<Vec<_>>(), slice);
+    /// assert_eq!(0, stats.moves);
+    /// assert_eq!(0, stats.allocations);
+    /// ```
+    fn sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        benchmark.start_timer();
+
+        BlockSort::block_sort_with_benchmark(slice, benchmark);
+
+        benchmark.stop_timer();
+    }
+}
+
+impl StableSorter for BlockSort {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarking::StandardBenchmarker;
+    use crate::conformance::*;
+    use crate::generator::*;
+
+    crate::sorter_conformance_tests!(BlockSort, stable);
+
+    #[test]
+    fn block_sort_is_stable_with_few_distinct_keys() {
+        for keys in [1, 2, 10, 40, 100] {
+            for n in [100, 1000, 5000] {
+                let mut slice = keyed_input(n, keys, keys as u64);
+
+                BlockSort::sort(&mut slice);
+
+                assert_stably_sorted(&slice);
+
+                let mut slice = keyed_input(n, keys, keys as u64);
+
+                BlockSort::sort_with_benchmark(&mut slice, &mut StandardBenchmarker::default());
+
+                assert_stably_sorted(&slice);
+            }
+        }
+    }
+
+    #[test]
+    fn block_sort_is_stable_with_block_merges() {
+        let mut slice = keyed_input(100000, 50000, 0);
+
+        BlockSort::sort(&mut slice);
+
+        assert_stably_sorted(&slice);
+    }
+
+    #[test]
+    fn collect_keys_finds_first_occurrences() {
+        let mut slice = [3, 1, 3, 2, 1, 4, 2];
+
+        let keys_len = BlockSort::collect_keys(&mut slice, 3);
+
+        assert_eq!(3, keys_len);
+        assert_eq!([1, 2, 3, 3, 1, 4, 2], slice);
+    }
+
+    #[test]
+    fn block_sort_is_n_log_n_on_distinct_keys() {
+        let n = 100000;
+        let mut slice = RandomGenerator::generate_seeded(n, 0);
+        let mut benchmarker = StandardBenchmarker::default();
+
+        BlockSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+
+        let stats = benchmarker.get_stats();
+        let n_log_n = n * (usize::BITS - n.leading_zeros()) as usize;
+
+        assert!(stats.comparisons < 2 * n_log_n);
+        assert!(stats.swaps < 4 * n_log_n);
+    }
+}
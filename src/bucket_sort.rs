@@ -0,0 +1,291 @@
+//! Bucket sort algorithm implementation for uniformly distributed keys.
+
+use std::marker::PhantomData;
+use std::mem;
+
+use crate::benchmarking::NestedBenchmarker;
+use crate::prelude::*;
+use crate::radix_sort::counts_to_offsets;
+use crate::tuning::Parameter;
+
+/// Struct implementing `IntegerSorter` + `BenchmarkingIntegerSorter` using
+/// bucket sort to sort items. Key range is split into buckets of equal width,
+/// items are distributed into them through single buffer and every bucket is
+/// sorted with inner sorter `S`. It runs in linear expected time when keys
+/// are uniformly distributed.
+pub struct BucketSort<S> {
+    sorter: PhantomData<S>,
+}
+
+impl<S> BucketSort<S> {
+    fn bucket<T: RadixKey>(value: T, min: T, max: T, bucket_count: usize) -> usize {
+        let offset = (value.key() - min.key()) as u128;
+        let range = (max.key() - min.key()) as u128 + 1;
+
+        (offset * bucket_count as u128 / range) as usize
+    }
+
+    /// Returns number of buckets, so that they hold
+    /// `Parameter::BucketSortBucketLen` items on average.
+    fn bucket_count(len: usize) -> usize {
+        (len / Parameter::BucketSortBucketLen.get()).max(1)
+    }
+
+    /// Returns smallest and biggest value, or `None` if slice is too short
+    /// to need sorting.
+    fn bounds<T: RadixKey>(slice: &[T]) -> Option<(T, T)> {
+        if slice.len() < 2 {
+            return None;
+        }
+
+        let min = *slice.iter().min()?;
+        let max = *slice.iter().max()?;
+
+        Some((min, max))
+    }
+}
+
+impl<S: Sorter> BucketSort<S> {
+    /// Bucket sort using `bucket_count` buckets. Panics if `bucket_count` is 0.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    ///
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// BucketSort::<InsertionSort>::sort_with_buckets(&mut slice, 3);
+    ///
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// ```
+    pub fn sort_with_buckets<T: RadixKey>(slice: &mut [T], bucket_count: usize) {
+        assert!(bucket_count > 0, "there has to be at least one bucket");
+
+        let (min, max) = match BucketSort::<S>::bounds(slice) {
+            Some(bounds) => bounds,
+            None => return,
+        };
+
+        let mut counts = vec![0; bucket_count + 1];
+
+        for &value in slice.iter() {
+            counts[BucketSort::<S>::bucket(value, min, max, bucket_count)] += 1;
+        }
+
+        // extra last count stays zero, so it becomes end of the last bucket
+        counts_to_offsets(&mut counts);
+
+        let mut buffer = slice.to_vec();
+        let mut next = counts.clone();
+
+        for &value in slice.iter() {
+            let bucket = BucketSort::<S>::bucket(value, min, max, bucket_count);
+
+            buffer[next[bucket]] = value;
+            next[bucket] += 1;
+        }
+
+        for bucket in counts.windows(2) {
+            S::sort(&mut buffer[bucket[0]..bucket[1]]);
+        }
+
+        slice.copy_from_slice(&buffer);
+    }
+}
+
+impl<S: BenchmarkingSorter> BucketSort<S> {
+    /// Bucket sort with additional benchmarking capabilities. Distributing
+    /// items into buffer and copying them back are counted as moves, inner
+    /// sorter counts its own comparisons and swaps.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::StandardBenchmarker;
+    ///
+    /// let mut benchmarker = StandardBenchmarker::default();
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// BucketSort::<InsertionSort>::sort_with_buckets_and_benchmark(
+    ///     &mut slice,
+    ///     3,
+    ///     &mut benchmarker,
+    /// );
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// assert_eq!(12, stats.moves);
+    /// assert_eq!(2, stats.allocations);
+    /// ```
+    pub fn sort_with_buckets_and_benchmark<T: RadixKey>(
+        slice: &mut [T],
+        bucket_count: usize,
+        benchmark: &mut impl Benchmark,
+    ) {
+        benchmark.start_timer();
+
+        BucketSort::<S>::bucket_sort_with_benchmark(slice, bucket_count, benchmark);
+
+        benchmark.stop_timer();
+    }
+
+    fn bucket_sort_with_benchmark<T: RadixKey>(
+        slice: &mut [T],
+        bucket_count: usize,
+        benchmark: &mut impl Benchmark,
+    ) {
+        assert!(bucket_count > 0, "there has to be at least one bucket");
+
+        benchmark.add_pass();
+
+        let (min, max) = match BucketSort::<S>::bounds(slice) {
+            Some(bounds) => bounds,
+            None => return,
+        };
+
+        let mut counts = vec![0; bucket_count + 1];
+
+        benchmark.add_allocation(counts.len() * mem::size_of::<usize>());
+        benchmark.add_pass();
+
+        for &value in slice.iter() {
+            counts[BucketSort::<S>::bucket(value, min, max, bucket_count)] += 1;
+        }
+
+        // extra last count stays zero, so it becomes end of the last bucket
+        counts_to_offsets(&mut counts);
+
+        let mut buffer = slice.to_vec();
+        let mut next = counts.clone();
+
+        benchmark.add_allocation(mem::size_of_val(slice));
+        benchmark.add_pass();
+
+        for &value in slice.iter() {
+            let bucket = BucketSort::<S>::bucket(value, min, max, bucket_count);
+
+            buffer[next[bucket]] = value;
+            next[bucket] += 1;
+
+            benchmark.add_move();
+            benchmark.add_writes(1);
+        }
+
+        let mut nested = NestedBenchmarker::new(benchmark);
+
+        for bucket in counts.windows(2) {
+            S::sort_with_benchmark(&mut buffer[bucket[0]..bucket[1]], &mut nested);
+        }
+
+        benchmark.add_pass();
+
+        slice.copy_from_slice(&buffer);
+
+        for _ in 0..slice.len() {
+            benchmark.add_move();
+            benchmark.add_writes(1);
+        }
+    }
+}
+
+impl<S: Sorter> IntegerSorter for BucketSort<S> {
+    /// Bucket sort implementation using one bucket per
+    /// `Parameter::BucketSortBucketLen` items.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    ///
+    /// let mut slice = [1, -8, 2, 3, 9, -5];
+    ///
+    /// BucketSort::<InsertionSort>::sort(&mut slice);
+    ///
+    /// assert_eq!([-8, -5, 1, 2, 3, 9], slice);
+    /// ```
+    fn sort<T: RadixKey>(slice: &mut [T]) {
+        BucketSort::<S>::sort_with_buckets(slice, BucketSort::<S>::bucket_count(slice.len()));
+    }
+}
+
+impl<S: BenchmarkingSorter> BenchmarkingIntegerSorter for BucketSort<S> {
+    /// Bucket sort implementation using one bucket per
+    /// `Parameter::BucketSortBucketLen` items with additional benchmarking
+    /// capabilities.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::StandardBenchmarker;
+    ///
+    /// let mut benchmarker = StandardBenchmarker::default();
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// BucketSort::<InsertionSort>::sort_with_benchmark(&mut slice, &mut benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!(0, stats.comparisons);
+    /// assert_eq!(4, stats.passes);
+    /// assert_eq!(12, stats.moves);
+    /// ```
+    fn sort_with_benchmark<T: RadixKey>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        let bucket_count = BucketSort::<S>::bucket_count(slice.len());
+
+        BucketSort::<S>::sort_with_buckets_and_benchmark(slice, bucket_count, benchmark);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarking::StandardBenchmarker;
+    use crate::generator::*;
+
+    mod insertion_buckets {
+        use super::*;
+
+        crate::integer_sorter_conformance_tests!(BucketSort<InsertionSort>);
+    }
+
+    mod merge_buckets {
+        use super::*;
+
+        crate::integer_sorter_conformance_tests!(BucketSort<MergeSort>);
+    }
+
+    #[test]
+    fn bucket_sort_works_with_any_bucket_count() {
+        let input = RandomGenerator::generate_seeded(1000, 0);
+
+        let mut expected = input.clone();
+        expected.sort();
+
+        for bucket_count in [1, 2, 7, 1000, 5000] {
+            let mut slice = input.clone();
+
+            BucketSort::<InsertionSort>::sort_with_buckets(&mut slice, bucket_count);
+
+            assert_eq!(expected, slice);
+        }
+    }
+
+    #[test]
+    fn bucket_sort_is_linear_on_uniform_keys() {
+        let mut slice = RandomGenerator::generate_seeded(10000, 0);
+        let mut benchmarker = StandardBenchmarker::default();
+
+        BucketSort::<InsertionSort>::sort_with_benchmark(&mut slice, &mut benchmarker);
+
+        let stats = benchmarker.get_stats();
+
+        assert!(stats.comparisons < 2 * 10000);
+        assert_eq!(2 * 10000, stats.moves);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one bucket")]
+    fn bucket_sort_rejects_zero_buckets() {
+        BucketSort::<InsertionSort>::sort_with_buckets(&mut [2, 1], 0);
+    }
+}
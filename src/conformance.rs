@@ -0,0 +1,698 @@
+//! Conformance tests which every `Sorter` has to pass. Use
+//! `sorter_conformance_tests!` macro to generate whole suite as `#[test]`
+//! functions, also in crates implementing their own sorters.
+//! `integer_sorter_conformance_tests!` does the same for `IntegerSorter`s
+//! and `parallel_sorter_conformance_tests!` for `ParallelSorter`s.
+
+use std::cell::Cell;
+use std::cmp::Ordering;
+
+use rand::distributions::{Distribution, Standard};
+use rand::prelude::*;
+use rand_pcg::Pcg64Mcg;
+
+use crate::benchmarking::{AtomicBenchmarker, StandardBenchmarker, Stats};
+use crate::generator::*;
+use crate::prelude::*;
+use crate::property::{self, counter_bound};
+
+/// Generates full conformance suite for given sorter as `#[test]` functions.
+/// Sorter has to implement `Sorter` + `BenchmarkingSorter`. Passing `stable`
+/// as second argument adds stability tests, which require `StableSorter`.
+///
+/// Examples:
+/// ```
+/// use algorithms::prelude::*;
+///
+/// pub struct MySort;
+///
+/// impl Sorter for MySort {
+///     fn sort<T: Ord + Copy>(slice: &mut [T]) {
+///         InsertionSort::sort(slice);
+///     }
+/// }
+///
+/// impl BenchmarkingSorter for MySort {
+///     fn sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+///         InsertionSort::sort_with_benchmark(slice, benchmark);
+///     }
+/// }
+///
+/// impl StableSorter for MySort {}
+///
+/// mod tests {
+///     algorithms::sorter_conformance_tests!(super::MySort, stable);
+/// }
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! sorter_conformance_tests {
+    ($sorter:ty) => {
+        #[test]
+        fn basic_sorting_test() {
+            $crate::conformance::basic_sorting_test::<$sorter>();
+        }
+
+        #[test]
+        fn empty_sorting_test() {
+            $crate::conformance::empty_sorting_test::<$sorter>();
+        }
+
+        #[test]
+        fn single_element_sorting_test() {
+            $crate::conformance::single_element_sorting_test::<$sorter>();
+        }
+
+        #[test]
+        fn two_elements_sorting_test() {
+            $crate::conformance::two_elements_sorting_test::<$sorter>();
+        }
+
+        #[test]
+        fn sorted_sorting_test() {
+            $crate::conformance::sorted_sorting_test::<$sorter>();
+        }
+
+        #[test]
+        fn sorted_backwards_sorting_test() {
+            $crate::conformance::sorted_backwards_sorting_test::<$sorter>();
+        }
+
+        #[test]
+        fn all_equal_sorting_test() {
+            $crate::conformance::all_equal_sorting_test::<$sorter>();
+        }
+
+        #[test]
+        fn duplicates_sorting_test() {
+            $crate::conformance::duplicates_sorting_test::<$sorter>();
+        }
+
+        #[test]
+        fn extreme_values_sorting_test() {
+            $crate::conformance::extreme_values_sorting_test::<$sorter>();
+        }
+
+        #[test]
+        fn generators_sorting_test() {
+            $crate::conformance::generators_sorting_test::<$sorter>();
+        }
+
+        #[test]
+        fn permutation_test() {
+            $crate::conformance::permutation_test::<$sorter>();
+        }
+
+        #[test]
+        fn benchmark_counters_test() {
+            $crate::conformance::benchmark_counters_test::<$sorter>();
+        }
+    };
+    ($sorter:ty, stable) => {
+        $crate::sorter_conformance_tests!($sorter);
+
+        #[test]
+        fn stable_sorting_test() {
+            $crate::conformance::stable_sorting_test::<$sorter>();
+        }
+    };
+}
+
+/// Generates conformance suite for given integer sorter as `#[test]` functions.
+/// Sorter has to implement `IntegerSorter` + `BenchmarkingIntegerSorter`.
+#[macro_export]
+macro_rules! integer_sorter_conformance_tests {
+    ($sorter:ty) => {
+        #[test]
+        fn basic_sorting_test() {
+            $crate::conformance::integer_basic_sorting_test::<$sorter>();
+        }
+
+        #[test]
+        fn edge_cases_sorting_test() {
+            $crate::conformance::integer_edge_cases_sorting_test::<$sorter>();
+        }
+
+        #[test]
+        fn integer_types_sorting_test() {
+            $crate::conformance::integer_types_sorting_test::<$sorter>();
+        }
+
+        #[test]
+        fn generators_sorting_test() {
+            $crate::conformance::integer_generators_sorting_test::<$sorter>();
+        }
+
+        #[test]
+        fn benchmark_sorting_test() {
+            $crate::conformance::integer_benchmark_sorting_test::<$sorter>();
+        }
+    };
+}
+
+/// Generates conformance suite for given parallel sorter as `#[test]`
+/// functions. Sorter has to implement `ParallelSorter` +
+/// `BenchmarkingParallelSorter`.
+#[macro_export]
+macro_rules! parallel_sorter_conformance_tests {
+    ($sorter:ty) => {
+        #[test]
+        fn basic_sorting_test() {
+            $crate::conformance::parallel_basic_sorting_test::<$sorter>();
+        }
+
+        #[test]
+        fn edge_cases_sorting_test() {
+            $crate::conformance::parallel_edge_cases_sorting_test::<$sorter>();
+        }
+
+        #[test]
+        fn generators_sorting_test() {
+            $crate::conformance::parallel_generators_sorting_test::<$sorter>();
+        }
+
+        #[test]
+        fn benchmark_sorting_test() {
+            $crate::conformance::parallel_benchmark_sorting_test::<$sorter>();
+        }
+    };
+}
+
+pub fn basic_sorting_test<T: Sorter>() {
+    let mut slice = [1, 8, 2, 3, 9, 5];
+
+    T::sort(&mut slice);
+
+    assert_eq!([1, 2, 3, 5, 8, 9], slice);
+}
+
+pub fn empty_sorting_test<T: Sorter>() {
+    let mut slice = [];
+
+    T::sort(&mut slice);
+
+    let expected: [(); 0] = [];
+
+    assert_eq!(expected, slice);
+}
+
+pub fn single_element_sorting_test<T: Sorter>() {
+    let mut slice = [7];
+
+    T::sort(&mut slice);
+
+    assert_eq!([7], slice);
+}
+
+pub fn two_elements_sorting_test<T: Sorter>() {
+    let mut slice = [2, 1];
+
+    T::sort(&mut slice);
+
+    assert_eq!([1, 2], slice);
+
+    T::sort(&mut slice);
+
+    assert_eq!([1, 2], slice);
+}
+
+pub fn sorted_sorting_test<T: Sorter>() {
+    let mut slice = [1, 2, 3, 4, 5];
+
+    T::sort(&mut slice);
+
+    assert_eq!([1, 2, 3, 4, 5], slice);
+}
+
+pub fn sorted_backwards_sorting_test<T: Sorter>() {
+    let mut slice = [5, 4, 3, 2, 1];
+
+    T::sort(&mut slice);
+
+    assert_eq!([1, 2, 3, 4, 5], slice);
+}
+
+pub fn all_equal_sorting_test<T: Sorter>() {
+    for n in [2, 3, 17, 100] {
+        let mut slice = vec![4; n];
+
+        T::sort(&mut slice);
+
+        assert_eq!(vec![4; n], slice);
+    }
+}
+
+pub fn duplicates_sorting_test<T: Sorter>() {
+    let mut rng = Pcg64Mcg::seed_from_u64(0);
+
+    for n in [5, 16, 100, 1000] {
+        for distinct in [2, 3, 10] {
+            let input = (0..n)
+                .map(|_| rng.gen_range(0..distinct))
+                .collect::<Vec<u32>>();
+
+            assert_sorts_like_std::<T, _>(&input);
+        }
+    }
+}
+
+pub fn extreme_values_sorting_test<T: Sorter>() {
+    let mut slice = [i64::MAX, 0, i64::MIN, -1, i64::MAX, 1, i64::MIN];
+
+    T::sort(&mut slice);
+
+    assert_eq!([i64::MIN, i64::MIN, -1, 0, 1, i64::MAX, i64::MAX], slice);
+}
+
+pub fn generators_sorting_test<T: Sorter>() {
+    for (seed, n) in [1, 2, 3, 10, 64, 100, 1000].into_iter().enumerate() {
+        let seed = seed as u64;
+
+        assert_sorts_like_std::<T, _>(&RandomGenerator::generate_seeded(n, seed));
+        assert_sorts_like_std::<T, _>(&AscendingGenerator::generate_seeded(n, seed));
+        assert_sorts_like_std::<T, _>(&DescendingGenerator::generate_seeded(n, seed));
+    }
+}
+
+pub fn permutation_test<T: Sorter + BenchmarkingSorter>() {
+    if let Err(counterexample) = property::check::<T>(&property::Config::default()) {
+        panic!("{}", counterexample);
+    }
+}
+
+pub fn benchmark_counters_test<T: BenchmarkingSorter>() {
+    for n in [0, 1] {
+        let mut benchmarker = StandardBenchmarker::default();
+
+        T::sort_with_benchmark(&mut vec![3; n], &mut benchmarker);
+
+        let Stats {
+            comparisons,
+            swaps,
+            writes,
+            ..
+        } = benchmarker.get_stats();
+
+        assert_eq!(0, comparisons, "comparisons counted for {} elements", n);
+        assert_eq!(0, swaps, "swaps counted for {} elements", n);
+        assert_eq!(0, writes, "writes counted for {} elements", n);
+    }
+
+    for n in [2, 10, 100, 1000] {
+        let mut slice = (0..n as u32).rev().collect::<Vec<_>>();
+        let mut benchmarker = StandardBenchmarker::default();
+
+        T::sort_with_benchmark(&mut slice, &mut benchmarker);
+
+        let Stats {
+            comparisons,
+            swaps,
+            writes,
+            ..
+        } = benchmarker.get_stats();
+
+        assert!(comparisons > 0, "no comparisons counted for {} elements", n);
+        assert!(swaps > 0, "no swaps counted for {} elements", n);
+        assert!(writes > 0, "no writes counted for {} elements", n);
+        assert!(comparisons <= counter_bound(n), "too many comparisons");
+        assert!(swaps <= counter_bound(n), "too many swaps");
+        assert!(writes <= counter_bound(n), "too many writes");
+    }
+
+    for (seed, n) in [2, 10, 100, 1000].into_iter().enumerate() {
+        let mut slice = RandomGenerator::generate_seeded(n, seed as u64);
+        let sorted = is_sorted(&slice);
+        let mut benchmarker = StandardBenchmarker::default();
+
+        T::sort_with_benchmark(&mut slice, &mut benchmarker);
+
+        let Stats {
+            comparisons,
+            swaps,
+            writes,
+            ..
+        } = benchmarker.get_stats();
+
+        assert!(sorted || writes > 0, "no writes counted for {} elements", n);
+        assert!(comparisons <= counter_bound(n), "too many comparisons");
+        assert!(swaps <= counter_bound(n), "too many swaps");
+        assert!(writes <= counter_bound(n), "too many writes");
+    }
+}
+
+fn is_sorted<E: Ord>(slice: &[E]) -> bool {
+    slice.windows(2).all(|pair| pair[0] <= pair[1])
+}
+
+fn assert_sorts_like_std<T: Sorter, E: Ord + Copy + std::fmt::Debug>(input: &[E]) {
+    let mut expected = input.to_vec();
+    expected.sort();
+
+    let mut slice = input.to_vec();
+    T::sort(&mut slice);
+
+    assert_eq!(expected, slice);
+}
+
+/// Element ordered only by its `key`, remembering its position in input
+/// so that order among equal keys can be checked.
+#[derive(Clone, Copy, Debug)]
+pub struct Keyed {
+    pub key: u32,
+    pub index: usize,
+}
+
+impl PartialEq for Keyed {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl Eq for Keyed {}
+
+impl PartialOrd for Keyed {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Keyed {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+/// Element counting every comparison made on it into shared `counter`, so
+/// that comparisons of a sorter can be counted without its benchmarking
+/// implementation.
+#[derive(Clone, Copy, Debug)]
+pub struct Counted<'a, T> {
+    pub value: T,
+    pub counter: &'a Cell<usize>,
+}
+
+impl<T: PartialEq> PartialEq for Counted<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.counter.set(self.counter.get() + 1);
+        self.value == other.value
+    }
+}
+
+impl<T: Eq> Eq for Counted<'_, T> {}
+
+impl<T: Ord> PartialOrd for Counted<'_, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Ord> Ord for Counted<'_, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.counter.set(self.counter.get() + 1);
+        self.value.cmp(&other.value)
+    }
+}
+
+/// Sorts `input` wrapped in `Counted` with sorter `T`, checks the result and
+/// returns number of comparisons made.
+pub fn count_comparisons<T: Sorter>(input: &[u32]) -> usize {
+    let counter = Cell::new(0);
+    let mut slice = input
+        .iter()
+        .map(|&value| Counted {
+            value,
+            counter: &counter,
+        })
+        .collect::<Vec<_>>();
+
+    T::sort(&mut slice);
+
+    let mut expected = input.to_vec();
+    expected.sort();
+
+    assert!(
+        slice.iter().map(|item| item.value).eq(expected),
+        "output isn't sorted"
+    );
+
+    counter.get()
+}
+
+/// Generates `n` elements with keys from `0..keys` so that keys repeat heavily.
+pub fn keyed_input(n: usize, keys: u32, seed: u64) -> Vec<Keyed> {
+    let mut rng = Pcg64Mcg::seed_from_u64(seed);
+
+    (0..n)
+        .map(|index| Keyed {
+            key: rng.gen_range(0..keys),
+            index,
+        })
+        .collect()
+}
+
+pub fn assert_stably_sorted(slice: &[Keyed]) {
+    for pair in slice.windows(2) {
+        assert!(pair[0].key <= pair[1].key, "keys aren't sorted");
+
+        if pair[0].key == pair[1].key {
+            assert!(
+                pair[0].index < pair[1].index,
+                "order of equal keys isn't preserved"
+            );
+        }
+    }
+}
+
+pub fn stable_sorting_test<T: StableSorter + BenchmarkingSorter>() {
+    for (seed, n) in [0, 1, 2, 10, 100, 1000].into_iter().enumerate() {
+        for keys in [1, 2, 8] {
+            let input = keyed_input(n, keys, seed as u64);
+
+            let mut slice = input.clone();
+            T::sort(&mut slice);
+            assert_stably_sorted(&slice);
+
+            let mut slice = input;
+            T::sort_with_benchmark(&mut slice, &mut StandardBenchmarker::default());
+            assert_stably_sorted(&slice);
+        }
+    }
+}
+
+/// Stack size of thread used by `shallow_stack_sorting_test`.
+const SHALLOW_STACK_SIZE: usize = 128 * 1024;
+
+/// Sorts `n` ascending items on a thread with small stack, which overflows
+/// unless recursion depth is logarithmic.
+pub fn shallow_stack_sorting_test<T: Sorter + BenchmarkingSorter + 'static>(n: usize) {
+    std::thread::Builder::new()
+        .stack_size(SHALLOW_STACK_SIZE)
+        .spawn(move || {
+            let expected = (0..n as u32).collect::<Vec<_>>();
+
+            let mut slice = expected.clone();
+            T::sort(&mut slice);
+            assert_eq!(expected, slice);
+
+            T::sort_with_benchmark(&mut slice, &mut StandardBenchmarker::default());
+            assert_eq!(expected, slice);
+        })
+        .expect("couldn't spawn sorting thread")
+        .join()
+        .expect("sorting thread panicked");
+}
+
+pub fn integer_basic_sorting_test<T: IntegerSorter>() {
+    let mut slice = [1, 8, 2, 3, 9, 5];
+
+    T::sort(&mut slice);
+
+    assert_eq!([1, 2, 3, 5, 8, 9], slice);
+}
+
+pub fn integer_edge_cases_sorting_test<T: IntegerSorter>() {
+    let mut slice: [u32; 0] = [];
+    T::sort(&mut slice);
+
+    let mut slice = [7];
+    T::sort(&mut slice);
+    assert_eq!([7], slice);
+
+    let mut slice = [2, 1];
+    T::sort(&mut slice);
+    assert_eq!([1, 2], slice);
+
+    let mut slice = vec![4; 100];
+    T::sort(&mut slice);
+    assert_eq!(vec![4; 100], slice);
+
+    let mut slice = [i64::MAX, 0, i64::MIN, -1, i64::MAX, 1, i64::MIN];
+    T::sort(&mut slice);
+    assert_eq!([i64::MIN, i64::MIN, -1, 0, 1, i64::MAX, i64::MAX], slice);
+}
+
+pub fn integer_types_sorting_test<T: IntegerSorter>() {
+    for (seed, n) in [10, 100, 1000].into_iter().enumerate() {
+        let seed = seed as u64;
+
+        assert_integer_sorts_like_std::<T, u8>(&random_values(n, seed));
+        assert_integer_sorts_like_std::<T, u16>(&random_values(n, seed));
+        assert_integer_sorts_like_std::<T, u32>(&random_values(n, seed));
+        assert_integer_sorts_like_std::<T, u64>(&random_values(n, seed));
+        assert_integer_sorts_like_std::<T, usize>(&random_values(n, seed));
+        assert_integer_sorts_like_std::<T, i8>(&random_values(n, seed));
+        assert_integer_sorts_like_std::<T, i16>(&random_values(n, seed));
+        assert_integer_sorts_like_std::<T, i32>(&random_values(n, seed));
+        assert_integer_sorts_like_std::<T, i64>(&random_values(n, seed));
+        assert_integer_sorts_like_std::<T, isize>(&random_values(n, seed));
+    }
+}
+
+pub fn integer_generators_sorting_test<T: IntegerSorter>() {
+    for (seed, n) in [1, 2, 3, 10, 64, 100, 1000, 10000].into_iter().enumerate() {
+        let seed = seed as u64;
+
+        assert_integer_sorts_like_std::<T, _>(&RandomGenerator::generate_seeded(n, seed));
+        assert_integer_sorts_like_std::<T, _>(&AscendingGenerator::generate_seeded(n, seed));
+        assert_integer_sorts_like_std::<T, _>(&DescendingGenerator::generate_seeded(n, seed));
+    }
+}
+
+pub fn integer_benchmark_sorting_test<T: IntegerSorter + BenchmarkingIntegerSorter>() {
+    for (seed, n) in [0, 1, 2, 10, 100, 1000].into_iter().enumerate() {
+        let input = RandomGenerator::generate_seeded(n, seed as u64);
+
+        let mut sorted = input.clone();
+        T::sort(&mut sorted);
+
+        let presorted = is_sorted(&input);
+        let mut benchmarked = input;
+        let mut benchmarker = StandardBenchmarker::default();
+        T::sort_with_benchmark(&mut benchmarked, &mut benchmarker);
+
+        assert_eq!(sorted, benchmarked);
+
+        let Stats {
+            comparisons,
+            swaps,
+            writes,
+            ..
+        } = benchmarker.get_stats();
+
+        assert!(
+            presorted || writes > 0,
+            "no writes counted for {} elements",
+            n
+        );
+        assert!(comparisons <= counter_bound(n), "too many comparisons");
+        assert!(swaps <= counter_bound(n), "too many swaps");
+        assert!(writes <= counter_bound(n), "too many writes");
+    }
+}
+
+fn random_values<E>(n: usize, seed: u64) -> Vec<E>
+where
+    Standard: Distribution<E>,
+{
+    let mut rng = Pcg64Mcg::seed_from_u64(seed);
+
+    (0..n).map(|_| rng.gen()).collect()
+}
+
+fn assert_integer_sorts_like_std<T: IntegerSorter, E: RadixKey + std::fmt::Debug>(input: &[E]) {
+    let mut expected = input.to_vec();
+    expected.sort();
+
+    let mut slice = input.to_vec();
+    T::sort(&mut slice);
+
+    assert_eq!(expected, slice);
+}
+
+pub fn parallel_basic_sorting_test<T: ParallelSorter>() {
+    let mut slice = [1, 8, 2, 3, 9, 5];
+
+    T::sort(&mut slice);
+
+    assert_eq!([1, 2, 3, 5, 8, 9], slice);
+}
+
+pub fn parallel_edge_cases_sorting_test<T: ParallelSorter>() {
+    let mut slice: [u32; 0] = [];
+    T::sort(&mut slice);
+
+    let mut slice = [7];
+    T::sort(&mut slice);
+    assert_eq!([7], slice);
+
+    let mut slice = [2, 1];
+    T::sort(&mut slice);
+    assert_eq!([1, 2], slice);
+
+    let mut slice = vec![4; 1000];
+    T::sort(&mut slice);
+    assert_eq!(vec![4; 1000], slice);
+
+    let mut slice = [i64::MAX, 0, i64::MIN, -1, i64::MAX, 1, i64::MIN];
+    T::sort(&mut slice);
+    assert_eq!([i64::MIN, i64::MIN, -1, 0, 1, i64::MAX, i64::MAX], slice);
+}
+
+pub fn parallel_generators_sorting_test<T: ParallelSorter>() {
+    for (seed, n) in [1, 2, 3, 10, 64, 100, 1000, 10000].into_iter().enumerate() {
+        let seed = seed as u64;
+
+        assert_parallel_sorts_like_std::<T>(&RandomGenerator::generate_seeded(n, seed));
+        assert_parallel_sorts_like_std::<T>(&AscendingGenerator::generate_seeded(n, seed));
+        assert_parallel_sorts_like_std::<T>(&DescendingGenerator::generate_seeded(n, seed));
+    }
+}
+
+pub fn parallel_benchmark_sorting_test<T: ParallelSorter + BenchmarkingParallelSorter>() {
+    for (seed, n) in [0, 1, 2, 10, 100, 1000, 10000].into_iter().enumerate() {
+        let input = RandomGenerator::generate_seeded(n, seed as u64);
+
+        let mut sorted = input.clone();
+        T::sort(&mut sorted);
+
+        let presorted = is_sorted(&input);
+        let mut benchmarked = input;
+        let benchmarker = AtomicBenchmarker::default();
+        T::sort_with_benchmark(&mut benchmarked, &benchmarker);
+
+        assert_eq!(sorted, benchmarked);
+
+        let Stats {
+            comparisons,
+            swaps,
+            writes,
+            ..
+        } = benchmarker.get_stats();
+
+        assert!(
+            presorted || writes > 0,
+            "no writes counted for {} elements",
+            n
+        );
+        assert!(writes <= counter_bound(n), "too many writes");
+
+        assert_eq!(
+            n > 1,
+            comparisons > 0,
+            "comparisons counted for {} elements",
+            n
+        );
+        assert!(comparisons <= counter_bound(n), "too many comparisons");
+        assert!(swaps <= counter_bound(n), "too many swaps");
+    }
+}
+
+fn assert_parallel_sorts_like_std<T: ParallelSorter>(input: &[u32]) {
+    let mut expected = input.to_vec();
+    expected.sort();
+
+    let mut slice = input.to_vec();
+    T::sort(&mut slice);
+
+    assert_eq!(expected, slice);
+}
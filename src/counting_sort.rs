@@ -0,0 +1,385 @@
+//! Counting sort algorithm implementation for bounded keys.
+
+use std::mem;
+
+use crate::benchmarking::NestedBenchmarker;
+use crate::prelude::*;
+use crate::radix_sort::counts_to_offsets;
+
+/// Struct implementing `IntegerSorter` + `BenchmarkingIntegerSorter` using
+/// counting sort to sort items. It runs in linear time but allocates one
+/// counter for every possible key, so it's only usefull when keys lie in
+/// small range, e.g. values from `RandomGenerator`. Slices whose key range
+/// is wider than `RANGE_PER_ITEM` keys per item are sorted by `LsdRadixSort`.
+pub struct CountingSort;
+
+impl CountingSort {
+    /// Biggest key range for which counters are allocated.
+    pub const MAX_RANGE: u64 = 1 << 24;
+    /// Number of keys per item up to which range taken from slice is
+    /// counted, so that counting stays linear in length of slice.
+    pub const RANGE_PER_ITEM: u64 = 4;
+    /// Key range counted regardless of length of slice.
+    pub const MIN_RANGE: u64 = 1 << 8;
+
+    /// Sorts integers known to lie in range `min..=max`. Panics if any
+    /// value lies outside of it or range is bigger than `MAX_RANGE`.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::counting_sort::CountingSort;
+    ///
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// CountingSort::sort_in_range(&mut slice, 0, 9);
+    ///
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// ```
+    pub fn sort_in_range<T: RadixKey>(slice: &mut [T], min: T, max: T) {
+        let mut counts = CountingSort::allocate_counts(min, max);
+
+        for &value in slice.iter() {
+            counts[CountingSort::index(value, min, max)] += 1;
+        }
+
+        let mut i = 0;
+
+        for (offset, count) in counts.into_iter().enumerate() {
+            let value = T::from_key(min.key() + offset as u64);
+
+            for item in &mut slice[i..i + count] {
+                *item = value;
+            }
+
+            i += count;
+        }
+    }
+
+    /// Counting sort with additional benchmarking capabilities. Every write
+    /// of value into slice is counted as a move.
+    pub fn sort_in_range_with_benchmark<T: RadixKey>(
+        slice: &mut [T],
+        min: T,
+        max: T,
+        benchmark: &mut impl Benchmark,
+    ) {
+        benchmark.start_timer();
+
+        CountingSort::counting_sort_with_benchmark(slice, min, max, benchmark);
+
+        benchmark.stop_timer();
+    }
+
+    fn counting_sort_with_benchmark<T: RadixKey>(
+        slice: &mut [T],
+        min: T,
+        max: T,
+        benchmark: &mut impl Benchmark,
+    ) {
+        let mut counts = CountingSort::allocate_counts(min, max);
+
+        benchmark.add_allocation(counts.len() * mem::size_of::<usize>());
+        benchmark.add_pass();
+
+        for &value in slice.iter() {
+            counts[CountingSort::index(value, min, max)] += 1;
+        }
+
+        benchmark.add_pass();
+
+        let mut i = 0;
+
+        for (offset, count) in counts.into_iter().enumerate() {
+            let value = T::from_key(min.key() + offset as u64);
+
+            for item in &mut slice[i..i + count] {
+                *item = value;
+
+                benchmark.add_move();
+                benchmark.add_writes(1);
+            }
+
+            i += count;
+        }
+    }
+
+    /// Stably sorts items by keys extracted with `key`, which have to be
+    /// smaller than `key_range`. Unlike `sort_in_range` it works on any
+    /// type, at the cost of buffer holding copy of the slice.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::counting_sort::CountingSort;
+    ///
+    /// let mut slice = [(2, 'a'), (0, 'b'), (2, 'c'), (1, 'd')];
+    ///
+    /// CountingSort::sort_by_key(&mut slice, 3, |&(key, _)| key);
+    ///
+    /// assert_eq!([(0, 'b'), (1, 'd'), (2, 'a'), (2, 'c')], slice);
+    /// ```
+    pub fn sort_by_key<T, F>(slice: &mut [T], key_range: usize, key: F)
+    where
+        T: Copy,
+        F: Fn(&T) -> usize,
+    {
+        let mut counts = vec![0; key_range];
+
+        for item in slice.iter() {
+            counts[key(item)] += 1;
+        }
+
+        counts_to_offsets(&mut counts);
+
+        let buffer = slice.to_vec();
+
+        for item in buffer {
+            let k = key(&item);
+
+            slice[counts[k]] = item;
+            counts[k] += 1;
+        }
+    }
+
+    /// Stable counting sort by key with additional benchmarking capabilities.
+    /// Every copy of item into or out of buffer is counted as a move.
+    pub fn sort_by_key_with_benchmark<T, F>(
+        slice: &mut [T],
+        key_range: usize,
+        key: F,
+        benchmark: &mut impl Benchmark,
+    ) where
+        T: Copy,
+        F: Fn(&T) -> usize,
+    {
+        benchmark.start_timer();
+
+        let mut counts = vec![0; key_range];
+
+        benchmark.add_allocation(counts.len() * mem::size_of::<usize>());
+        benchmark.add_pass();
+
+        for item in slice.iter() {
+            counts[key(item)] += 1;
+        }
+
+        counts_to_offsets(&mut counts);
+
+        let buffer = slice.to_vec();
+
+        benchmark.add_allocation(mem::size_of_val(slice));
+        benchmark.add_pass();
+
+        for item in buffer {
+            let k = key(&item);
+
+            slice[counts[k]] = item;
+            counts[k] += 1;
+
+            benchmark.add_move();
+            benchmark.add_move();
+            benchmark.add_writes(2);
+        }
+
+        benchmark.stop_timer();
+    }
+
+    fn allocate_counts<T: RadixKey>(min: T, max: T) -> Vec<usize> {
+        assert!(min <= max, "min has to be smaller than max");
+
+        assert!(
+            CountingSort::fits(min, max),
+            "key range too big for counting sort"
+        );
+
+        vec![0; (max.key() - min.key()) as usize + 1]
+    }
+
+    fn index<T: RadixKey>(value: T, min: T, max: T) -> usize {
+        assert!(min <= value && value <= max, "value out of given range");
+
+        (value.key() - min.key()) as usize
+    }
+
+    fn fits<T: RadixKey>(min: T, max: T) -> bool {
+        max.key() - min.key() < CountingSort::MAX_RANGE
+    }
+
+    fn fits_slice<T: RadixKey>(slice: &[T], min: T, max: T) -> bool {
+        let range = (slice.len() as u64)
+            .saturating_mul(CountingSort::RANGE_PER_ITEM)
+            .max(CountingSort::MIN_RANGE);
+
+        CountingSort::fits(min, max) && max.key() - min.key() < range
+    }
+
+    /// Returns smallest and biggest value, or `None` if slice is too short
+    /// to need sorting.
+    fn bounds<T: RadixKey>(slice: &[T]) -> Option<(T, T)> {
+        if slice.len() < 2 {
+            return None;
+        }
+
+        let min = *slice.iter().min()?;
+        let max = *slice.iter().max()?;
+
+        Some((min, max))
+    }
+}
+
+impl IntegerSorter for CountingSort {
+    /// Counting sort implementation taking key range from smallest and
+    /// biggest value in slice. Slices whose key range isn't smaller than
+    /// `RANGE_PER_ITEM` times their length, or `MIN_RANGE` for short slices,
+    /// are sorted by `LsdRadixSort` instead.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    ///
+    /// let mut slice = [1, -8, 2, 3, 9, -5];
+    ///
+    /// CountingSort::sort(&mut slice);
+    ///
+    /// assert_eq!([-8, -5, 1, 2, 3, 9], slice);
+    /// ```
+    fn sort<T: RadixKey>(slice: &mut [T]) {
+        match CountingSort::bounds(slice) {
+            Some((min, max)) if CountingSort::fits_slice(slice, min, max) => {
+                CountingSort::sort_in_range(slice, min, max);
+            }
+            Some(_) => LsdRadixSort::<8>::sort(slice),
+            None => {}
+        }
+    }
+}
+
+impl BenchmarkingIntegerSorter for CountingSort {
+    /// Counting sort implementation with additional benchmarking capabilities.
+    /// Finding key range, counting keys and writing values back are counted
+    /// as separate passes. Fallback to `LsdRadixSort` counts its own passes.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::StandardBenchmarker;
+    ///
+    /// let mut benchmarker = StandardBenchmarker::default();
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// CountingSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!(0, stats.comparisons);
+    /// assert_eq!(3, stats.passes);
+    /// assert_eq!(6, stats.moves);
+    /// assert_eq!(9 * 8, stats.allocated_bytes);
+    /// ```
+    fn sort_with_benchmark<T: RadixKey>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        benchmark.start_timer();
+
+        benchmark.add_pass();
+
+        match CountingSort::bounds(slice) {
+            Some((min, max)) if CountingSort::fits_slice(slice, min, max) => {
+                CountingSort::counting_sort_with_benchmark(slice, min, max, benchmark);
+            }
+            Some(_) => {
+                LsdRadixSort::<8>::sort_with_benchmark(
+                    slice,
+                    &mut NestedBenchmarker::new(benchmark),
+                );
+            }
+            None => {}
+        }
+
+        benchmark.stop_timer();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarking::StandardBenchmarker;
+    use crate::generator::*;
+
+    crate::integer_sorter_conformance_tests!(CountingSort);
+
+    #[test]
+    fn counting_sort_narrow_types_sorting_test() {
+        let mut bytes = (0..=255u8).rev().cycle().take(1000).collect::<Vec<_>>();
+        let mut expected = bytes.clone();
+        expected.sort();
+
+        CountingSort::sort(&mut bytes);
+
+        assert_eq!(expected, bytes);
+
+        let mut shorts = (i16::MIN..=i16::MAX).rev().step_by(7).collect::<Vec<_>>();
+        let mut expected = shorts.clone();
+        expected.sort();
+
+        CountingSort::sort(&mut shorts);
+
+        assert_eq!(expected, shorts);
+    }
+
+    #[test]
+    fn counting_sort_by_key_is_stable() {
+        let mut slice = RandomGenerator::generate_seeded(1000, 0)
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| (value % 10, index))
+            .collect::<Vec<_>>();
+
+        let mut benchmarker = StandardBenchmarker::default();
+
+        CountingSort::sort_by_key_with_benchmark(
+            &mut slice,
+            10,
+            |&(key, _)| key as usize,
+            &mut benchmarker,
+        );
+
+        let mut expected = slice.clone();
+        expected.sort();
+
+        assert_eq!(expected, slice);
+        assert_eq!(2000, benchmarker.get_stats().moves);
+    }
+
+    #[test]
+    fn counting_sort_falls_back_to_radix_sort_on_huge_ranges() {
+        let mut slice = [u64::MAX, 0];
+        CountingSort::sort(&mut slice);
+        assert_eq!([0, u64::MAX], slice);
+
+        let mut slice = [i64::MAX, i64::MIN, 0];
+        let mut benchmarker = StandardBenchmarker::default();
+        CountingSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+        assert_eq!([i64::MIN, 0, i64::MAX], slice);
+    }
+
+    #[test]
+    fn counting_sort_skips_sparse_short_slices() {
+        let mut slice = [16_000_000u32, 0];
+        let mut benchmarker = StandardBenchmarker::default();
+
+        CountingSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+
+        assert_eq!([0, 16_000_000], slice);
+        assert!(benchmarker.get_stats().allocated_bytes < 1 << 16);
+    }
+
+    #[test]
+    #[should_panic(expected = "key range too big")]
+    fn counting_sort_in_range_rejects_huge_ranges() {
+        CountingSort::sort_in_range(&mut [0, u64::MAX], 0, u64::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "value out of given range")]
+    fn counting_sort_rejects_values_out_of_range() {
+        CountingSort::sort_in_range(&mut [1, 5, 20], 0, 10);
+    }
+}
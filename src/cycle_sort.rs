@@ -0,0 +1,259 @@
+//! Cycle sort algorithm implementation.
+
+use crate::prelude::*;
+
+use std::cmp::Ordering;
+
+/// Marks of items already written into their final positions, one bit per
+/// item.
+struct PlacedBits {
+    words: Vec<u64>,
+}
+
+impl PlacedBits {
+    fn new(len: usize) -> Self {
+        PlacedBits {
+            words: vec![0; len.div_ceil(64)],
+        }
+    }
+
+    fn get(&self, pos: usize) -> bool {
+        self.words[pos / 64] >> (pos % 64) & 1 == 1
+    }
+
+    fn set(&mut self, pos: usize) {
+        self.words[pos / 64] |= 1 << (pos % 64);
+    }
+}
+
+/// Struct implementing `Sorter` + `BenchmarkingSorter` using cycle sort to
+/// sort items. Permutation of slice is decomposed into cycles and every
+/// item is written directly into its final position, found by counting
+/// smaller items. Items already in place are never written, so number of
+/// writes is the minimum possible, while comparisons are `O(n^2)`. Placed
+/// items are marked in a bitset, so position of every item is computed
+/// only once.
+pub struct CycleSort;
+
+impl CycleSort {
+    fn cycle_sort<T: Ord + Copy>(slice: &mut [T]) {
+        let mut placed = PlacedBits::new(slice.len());
+
+        for start in 0..slice.len() {
+            if placed.get(start) {
+                continue;
+            }
+
+            let mut item = slice[start];
+            let (mut pos, mut equal) = CycleSort::position(slice, start, item);
+
+            if pos == start {
+                continue;
+            }
+
+            while pos != start {
+                // equal items already in place are skipped
+                while equal > 0 && (placed.get(pos) || slice[pos] == item) {
+                    pos += 1;
+                    equal -= 1;
+                }
+
+                (slice[pos], item) = (item, slice[pos]);
+                placed.set(pos);
+
+                (pos, equal) = CycleSort::position(slice, start, item);
+            }
+
+            slice[start] = item;
+        }
+    }
+
+    /// Returns final position of `item` taken from cycle starting at
+    /// `start`, before skipping items equal to it, and number of items
+    /// equal to it which may have to be skipped.
+    fn position<T: Ord + Copy>(slice: &[T], start: usize, item: T) -> (usize, usize) {
+        let mut pos = start;
+        let mut equal = 0;
+
+        for other in &slice[start + 1..] {
+            match other.cmp(&item) {
+                Ordering::Less => pos += 1,
+                Ordering::Equal => equal += 1,
+                Ordering::Greater => {}
+            }
+        }
+
+        (pos, equal)
+    }
+
+    fn cycle_sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        let mut placed = PlacedBits::new(slice.len());
+
+        benchmark.add_allocation(placed.words.len() * std::mem::size_of::<u64>());
+
+        for start in 0..slice.len() {
+            if placed.get(start) {
+                continue;
+            }
+
+            let mut item = slice[start];
+            let (mut pos, mut equal) =
+                CycleSort::position_with_benchmark(slice, start, item, benchmark);
+
+            if pos == start {
+                continue;
+            }
+
+            while pos != start {
+                // equal items already in place are skipped
+                while equal > 0
+                    && (placed.get(pos) || {
+                        benchmark.add_cmp();
+                        slice[pos] == item
+                    })
+                {
+                    pos += 1;
+                    equal -= 1;
+                }
+
+                benchmark.add_swap();
+                benchmark.add_writes(1);
+                (slice[pos], item) = (item, slice[pos]);
+                placed.set(pos);
+
+                (pos, equal) = CycleSort::position_with_benchmark(slice, start, item, benchmark);
+            }
+
+            benchmark.add_writes(1);
+            slice[start] = item;
+
+            benchmark.add_pass();
+        }
+    }
+
+    fn position_with_benchmark<T: Ord + Copy>(
+        slice: &[T],
+        start: usize,
+        item: T,
+        benchmark: &mut impl Benchmark,
+    ) -> (usize, usize) {
+        let mut pos = start;
+        let mut equal = 0;
+
+        for other in &slice[start + 1..] {
+            benchmark.add_cmp();
+
+            match other.cmp(&item) {
+                Ordering::Less => pos += 1,
+                Ordering::Equal => equal += 1,
+                Ordering::Greater => {}
+            }
+        }
+
+        (pos, equal)
+    }
+}
+
+impl Sorter for CycleSort {
+    /// Cycle sort implementation working on types implementing
+    /// `Ord` + `Copy` so it's mostly usefull for primitive types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    ///
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// CycleSort::sort(&mut slice);
+    ///
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// ```
+    fn sort<T: Ord + Copy>(slice: &mut [T]) {
+        CycleSort::cycle_sort(slice);
+    }
+}
+
+impl BenchmarkingSorter for CycleSort {
+    /// Cycle sort implementation with additional benchmarking capabilities.
+    /// Bitset of placed items is counted as allocation, every cycle as a
+    /// pass, every item written into slice as a
+    /// write and every exchange of item in hand with item in slice as a swap.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::StandardBenchmarker;
+    ///
+    /// let mut benchmarker = StandardBenchmarker::default();
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// CycleSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// assert_eq!(5, stats.writes);
+    /// assert_eq!(4, stats.swaps);
+    /// ```
+    fn sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        benchmark.start_timer();
+
+        CycleSort::cycle_sort_with_benchmark(slice, benchmark);
+
+        benchmark.stop_timer();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarking::StandardBenchmarker;
+    use crate::generator::{AscendingGenerator, RandomGenerator, SeededGenerator};
+
+    crate::sorter_conformance_tests!(CycleSort);
+
+    fn writes<S: BenchmarkingSorter>(input: &[u32]) -> usize {
+        let mut benchmarker = StandardBenchmarker::default();
+
+        S::sort_with_benchmark(&mut input.to_vec(), &mut benchmarker);
+
+        benchmarker.get_stats().writes
+    }
+
+    /// Every item out of its final position has to be written at least once.
+    fn misplaced(input: &[u32]) -> usize {
+        let mut sorted = input.to_vec();
+        sorted.sort();
+
+        input.iter().zip(&sorted).filter(|(a, b)| a != b).count()
+    }
+
+    #[test]
+    fn cycle_sort_writes_only_misplaced_items() {
+        for (seed, n) in [0, 1, 2, 10, 100, 1000].into_iter().enumerate() {
+            let seed = seed as u64;
+
+            for input in [
+                RandomGenerator::generate_seeded(n, seed),
+                AscendingGenerator::generate_seeded(n, seed),
+                RandomGenerator::generate_seeded(n, seed)
+                    .into_iter()
+                    .map(|item| item % 4)
+                    .collect(),
+            ] {
+                assert_eq!(misplaced(&input), writes::<CycleSort>(&input));
+            }
+        }
+    }
+
+    #[test]
+    fn cycle_sort_writes_less_than_other_sorts() {
+        let input = RandomGenerator::generate_seeded(1000, 1);
+        let cycle = writes::<CycleSort>(&input);
+
+        assert!(cycle <= writes::<SelectionSort>(&input));
+        assert!(cycle < writes::<InsertionSort>(&input));
+        assert!(cycle < writes::<MergeSort>(&input));
+        assert!(cycle < writes::<QuickSort>(&input));
+    }
+}
@@ -1,28 +1,34 @@
-//! Dual pivot quick sort algorithm implementation.
+//! Dual pivot quick sort algorithm implementation with selectable
+//! partitioning strategies.
 
-use crate::prelude::*;
-
-/// Struct implementing `Sorter` + `BenchmarkingSorter` using dual pivot quick sort
-/// to sort items.
-pub struct DualPivotQuicksort;
-
-impl DualPivotQuicksort {
-    fn quick_sort<T: Ord + Copy>(slice: &mut [T]) {
-        if slice.len() <= 1 {
-            return;
-        }
+use std::marker::PhantomData;
 
-        let (left_pivot_index, right_pivot_index) = DualPivotQuicksort::partition(slice);
-
-        DualPivotQuicksort::quick_sort(&mut slice[..left_pivot_index]);
-
-        DualPivotQuicksort::quick_sort(&mut slice[left_pivot_index + 1..right_pivot_index]);
+use crate::prelude::*;
+use crate::quick_sort::depth_limit;
+
+/// Strategy of partitioning slice around two pivots used by
+/// `DualPivotQuicksortWith`. Strategies differ in order in which items are
+/// compared with pivots, which changes constant in front of `n ln n`
+/// comparisons.
+pub trait DualPivotPartitioning {
+    /// Partitions slice of at least 2 items around its first and last item
+    /// taken as pivots. Returns final indices of both pivots, items smaller
+    /// than left pivot are before it, items bigger than right pivot after it.
+    fn partition<T: Ord + Copy>(slice: &mut [T]) -> (usize, usize);
+
+    /// Partitioning with additional benchmarking capabilities.
+    fn partition_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        benchmark: &mut impl Benchmark,
+    ) -> (usize, usize);
+}
 
-        if right_pivot_index < slice.len() - 1 {
-            DualPivotQuicksort::quick_sort(&mut slice[right_pivot_index + 1..]);
-        }
-    }
+/// "Count" partitioning. Every item is compared first with the pivot of the
+/// class which had more items so far. It takes about `1.8 n ln n`
+/// comparisons.
+pub struct CountPartitioning;
 
+impl DualPivotPartitioning for CountPartitioning {
     fn partition<T: Ord + Copy>(slice: &mut [T]) -> (usize, usize) {
         if slice[0] > slice[slice.len() - 1] {
             slice.swap(0, slice.len() - 1);
@@ -95,40 +101,13 @@ impl DualPivotQuicksort {
         (next_smaller - 1, next_larger + 1)
     }
 
-    pub fn quick_sort_with_benchmark<T: Ord + Copy>(
-        slice: &mut [T],
-        benchmark: &mut impl Benchmark,
-    ) {
-        if slice.len() <= 1 {
-            return;
-        }
-
-        let (left_pivot_index, right_pivot_index) =
-            DualPivotQuicksort::partition_with_benchmark(slice, benchmark);
-
-        DualPivotQuicksort::quick_sort_with_benchmark(&mut slice[..left_pivot_index], benchmark);
-
-        if left_pivot_index + 1 < right_pivot_index {
-            DualPivotQuicksort::quick_sort_with_benchmark(
-                &mut slice[left_pivot_index + 1..right_pivot_index],
-                benchmark,
-            );
-        }
-
-        if right_pivot_index < slice.len() - 1 {
-            DualPivotQuicksort::quick_sort_with_benchmark(
-                &mut slice[right_pivot_index + 1..],
-                benchmark,
-            );
-        }
-    }
-
     fn partition_with_benchmark<T: Ord + Copy>(
         slice: &mut [T],
         benchmark: &mut impl Benchmark,
     ) -> (usize, usize) {
         if slice[0] > slice[slice.len() - 1] {
             benchmark.add_swap();
+            benchmark.add_writes(2);
             slice.swap(0, slice.len() - 1);
         }
 
@@ -160,9 +139,11 @@ impl DualPivotQuicksort {
 
                     slice.swap(curr, next_larger);
                     benchmark.add_swap();
+                    benchmark.add_writes(2);
 
                     if slice[curr] < left_pivot {
                         benchmark.add_swap();
+                        benchmark.add_writes(2);
                         slice.swap(curr, next_smaller);
                         next_smaller += 1;
                     }
@@ -175,6 +156,7 @@ impl DualPivotQuicksort {
                     benchmark.add_cmp();
                     slice.swap(curr, next_smaller);
                     benchmark.add_swap();
+                    benchmark.add_writes(2);
                     next_smaller += 1;
 
                     smaller_count += 1;
@@ -185,6 +167,7 @@ impl DualPivotQuicksort {
                 if slice[curr] < left_pivot {
                     slice.swap(curr, next_smaller);
                     benchmark.add_swap();
+                    benchmark.add_writes(2);
                     next_smaller += 1;
 
                     smaller_count += 1;
@@ -198,10 +181,12 @@ impl DualPivotQuicksort {
 
                     slice.swap(curr, next_larger);
                     benchmark.add_swap();
+                    benchmark.add_writes(2);
 
                     if slice[curr] < left_pivot {
                         slice.swap(curr, next_smaller);
                         benchmark.add_swap();
+                        benchmark.add_writes(2);
                         next_smaller += 1;
                     }
                     benchmark.add_cmp();
@@ -221,13 +206,587 @@ impl DualPivotQuicksort {
         slice.swap(next_larger + 1, slice.len() - 1);
 
         benchmark.add_swap();
+        benchmark.add_writes(2);
         benchmark.add_swap();
+        benchmark.add_writes(2);
 
         (next_smaller - 1, next_larger + 1)
     }
 }
 
-impl Sorter for DualPivotQuicksort {
+/// Yaroslavskiy's partitioning used in JDK 7. Items are scanned from the
+/// left and compared with the left pivot first, large items found that way
+/// are exchanged with items scanned from the right, which are compared
+/// with the right pivot first. It takes about `1.9 n ln n` comparisons.
+pub struct YaroslavskiyPartitioning;
+
+impl DualPivotPartitioning for YaroslavskiyPartitioning {
+    fn partition<T: Ord + Copy>(slice: &mut [T]) -> (usize, usize) {
+        let last = slice.len() - 1;
+
+        if slice[0] > slice[last] {
+            slice.swap(0, last);
+        }
+
+        let left_pivot = slice[0];
+        let right_pivot = slice[last];
+
+        let mut less = 1;
+        let mut great = last - 1;
+        let mut curr = 1;
+
+        while curr <= great {
+            if slice[curr] < left_pivot {
+                slice.swap(curr, less);
+                less += 1;
+            } else if slice[curr] > right_pivot {
+                while great > curr && slice[great] > right_pivot {
+                    great -= 1;
+                }
+
+                slice.swap(curr, great);
+                great -= 1;
+
+                if slice[curr] < left_pivot {
+                    slice.swap(curr, less);
+                    less += 1;
+                }
+            }
+
+            curr += 1;
+        }
+
+        slice.swap(0, less - 1);
+        slice.swap(last, great + 1);
+
+        (less - 1, great + 1)
+    }
+
+    fn partition_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        benchmark: &mut impl Benchmark,
+    ) -> (usize, usize) {
+        let last = slice.len() - 1;
+
+        benchmark.add_cmp();
+
+        if slice[0] > slice[last] {
+            benchmark.add_swap();
+            benchmark.add_writes(2);
+            slice.swap(0, last);
+        }
+
+        let left_pivot = slice[0];
+        let right_pivot = slice[last];
+
+        let mut less = 1;
+        let mut great = last - 1;
+        let mut curr = 1;
+
+        while curr <= great {
+            benchmark.add_cmp();
+
+            if slice[curr] < left_pivot {
+                benchmark.add_swap();
+                benchmark.add_writes(2);
+                slice.swap(curr, less);
+                less += 1;
+            } else {
+                benchmark.add_cmp();
+
+                if slice[curr] > right_pivot {
+                    while great > curr {
+                        benchmark.add_cmp();
+
+                        if slice[great] <= right_pivot {
+                            break;
+                        }
+
+                        great -= 1;
+                    }
+
+                    benchmark.add_swap();
+                    benchmark.add_writes(2);
+                    slice.swap(curr, great);
+                    great -= 1;
+
+                    benchmark.add_cmp();
+
+                    if slice[curr] < left_pivot {
+                        benchmark.add_swap();
+                        benchmark.add_writes(2);
+                        slice.swap(curr, less);
+                        less += 1;
+                    }
+                }
+            }
+
+            curr += 1;
+        }
+
+        benchmark.add_swap();
+        benchmark.add_writes(2);
+        benchmark.add_swap();
+        benchmark.add_writes(2);
+
+        slice.swap(0, less - 1);
+        slice.swap(last, great + 1);
+
+        (less - 1, great + 1)
+    }
+}
+
+/// Sedgewick's partitioning. Items are scanned from both ends, from the left
+/// they are compared with the right pivot first and from the right with the
+/// left pivot first, both scans stop at items belonging to the other end,
+/// which are then exchanged. It takes about `2.13 n ln n` comparisons.
+pub struct SedgewickPartitioning;
+
+impl DualPivotPartitioning for SedgewickPartitioning {
+    fn partition<T: Ord + Copy>(slice: &mut [T]) -> (usize, usize) {
+        let last = slice.len() - 1;
+
+        if slice[0] > slice[last] {
+            slice.swap(0, last);
+        }
+
+        let left_pivot = slice[0];
+        let right_pivot = slice[last];
+
+        let mut next_smaller = 1;
+        let mut next_larger = last - 1;
+        let mut i = 1;
+        let mut j = last - 1;
+
+        loop {
+            while i <= j {
+                if slice[i] > right_pivot {
+                    break;
+                }
+
+                if slice[i] < left_pivot {
+                    slice.swap(i, next_smaller);
+                    next_smaller += 1;
+                }
+
+                i += 1;
+            }
+
+            while i <= j {
+                if slice[j] < left_pivot {
+                    break;
+                }
+
+                if slice[j] > right_pivot {
+                    slice.swap(j, next_larger);
+                    next_larger -= 1;
+                }
+
+                j -= 1;
+            }
+
+            if i > j {
+                break;
+            }
+
+            slice.swap(i, j);
+
+            slice.swap(i, next_smaller);
+            next_smaller += 1;
+            i += 1;
+
+            slice.swap(j, next_larger);
+            next_larger -= 1;
+            j -= 1;
+        }
+
+        slice.swap(0, next_smaller - 1);
+        slice.swap(last, next_larger + 1);
+
+        (next_smaller - 1, next_larger + 1)
+    }
+
+    fn partition_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        benchmark: &mut impl Benchmark,
+    ) -> (usize, usize) {
+        let last = slice.len() - 1;
+
+        benchmark.add_cmp();
+
+        if slice[0] > slice[last] {
+            benchmark.add_swap();
+            benchmark.add_writes(2);
+            slice.swap(0, last);
+        }
+
+        let left_pivot = slice[0];
+        let right_pivot = slice[last];
+
+        let mut next_smaller = 1;
+        let mut next_larger = last - 1;
+        let mut i = 1;
+        let mut j = last - 1;
+
+        loop {
+            while i <= j {
+                benchmark.add_cmp();
+
+                if slice[i] > right_pivot {
+                    break;
+                }
+
+                benchmark.add_cmp();
+
+                if slice[i] < left_pivot {
+                    benchmark.add_swap();
+                    benchmark.add_writes(2);
+                    slice.swap(i, next_smaller);
+                    next_smaller += 1;
+                }
+
+                i += 1;
+            }
+
+            while i <= j {
+                benchmark.add_cmp();
+
+                if slice[j] < left_pivot {
+                    break;
+                }
+
+                benchmark.add_cmp();
+
+                if slice[j] > right_pivot {
+                    benchmark.add_swap();
+                    benchmark.add_writes(2);
+                    slice.swap(j, next_larger);
+                    next_larger -= 1;
+                }
+
+                j -= 1;
+            }
+
+            if i > j {
+                break;
+            }
+
+            benchmark.add_swap();
+            benchmark.add_writes(2);
+            slice.swap(i, j);
+
+            benchmark.add_swap();
+            benchmark.add_writes(2);
+            slice.swap(i, next_smaller);
+            next_smaller += 1;
+            i += 1;
+
+            benchmark.add_swap();
+            benchmark.add_writes(2);
+            slice.swap(j, next_larger);
+            next_larger -= 1;
+            j -= 1;
+        }
+
+        benchmark.add_swap();
+        benchmark.add_writes(2);
+        benchmark.add_swap();
+        benchmark.add_writes(2);
+
+        slice.swap(0, next_smaller - 1);
+        slice.swap(last, next_larger + 1);
+
+        (next_smaller - 1, next_larger + 1)
+    }
+}
+
+/// Clairvoyant partitioning, which knows in advance how many small and large
+/// items there are. Every item is compared first with the pivot of the class
+/// having more items left to classify. Finding out the counts isn't counted,
+/// so it's a lower bound for strategies like "count" and it takes about
+/// `1.8 n ln n` comparisons.
+pub struct ClairvoyantPartitioning;
+
+impl ClairvoyantPartitioning {
+    fn class_counts<T: Ord + Copy>(slice: &[T], left_pivot: T, right_pivot: T) -> (usize, usize) {
+        let smaller = slice.iter().filter(|&&value| value < left_pivot).count();
+        let larger = slice.iter().filter(|&&value| value > right_pivot).count();
+
+        (smaller, larger)
+    }
+}
+
+impl DualPivotPartitioning for ClairvoyantPartitioning {
+    fn partition<T: Ord + Copy>(slice: &mut [T]) -> (usize, usize) {
+        let last = slice.len() - 1;
+
+        if slice[0] > slice[last] {
+            slice.swap(0, last);
+        }
+
+        let left_pivot = slice[0];
+        let right_pivot = slice[last];
+
+        let (mut smaller_left, mut larger_left) =
+            ClairvoyantPartitioning::class_counts(&slice[1..last], left_pivot, right_pivot);
+
+        let mut less = 1;
+        let mut great = last - 1;
+        let mut curr = 1;
+
+        while curr <= great {
+            let value = slice[curr];
+
+            let (is_smaller, is_larger) = if larger_left > smaller_left {
+                if value > right_pivot {
+                    (false, true)
+                } else {
+                    (value < left_pivot, false)
+                }
+            } else if value < left_pivot {
+                (true, false)
+            } else {
+                (false, value > right_pivot)
+            };
+
+            if is_smaller {
+                slice.swap(curr, less);
+                less += 1;
+                smaller_left -= 1;
+            } else if is_larger {
+                larger_left -= 1;
+
+                while great > curr && slice[great] > right_pivot {
+                    great -= 1;
+                    larger_left -= 1;
+                }
+
+                slice.swap(curr, great);
+                great -= 1;
+
+                if slice[curr] < left_pivot {
+                    slice.swap(curr, less);
+                    less += 1;
+                    smaller_left -= 1;
+                }
+            }
+
+            curr += 1;
+        }
+
+        slice.swap(0, less - 1);
+        slice.swap(last, great + 1);
+
+        (less - 1, great + 1)
+    }
+
+    fn partition_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        benchmark: &mut impl Benchmark,
+    ) -> (usize, usize) {
+        let last = slice.len() - 1;
+
+        benchmark.add_cmp();
+
+        if slice[0] > slice[last] {
+            benchmark.add_swap();
+            benchmark.add_writes(2);
+            slice.swap(0, last);
+        }
+
+        let left_pivot = slice[0];
+        let right_pivot = slice[last];
+
+        let (mut smaller_left, mut larger_left) =
+            ClairvoyantPartitioning::class_counts(&slice[1..last], left_pivot, right_pivot);
+
+        let mut less = 1;
+        let mut great = last - 1;
+        let mut curr = 1;
+
+        while curr <= great {
+            let value = slice[curr];
+
+            let (is_smaller, is_larger) = if larger_left > smaller_left {
+                benchmark.add_cmp();
+
+                if value > right_pivot {
+                    (false, true)
+                } else {
+                    benchmark.add_cmp();
+
+                    (value < left_pivot, false)
+                }
+            } else {
+                benchmark.add_cmp();
+
+                if value < left_pivot {
+                    (true, false)
+                } else {
+                    benchmark.add_cmp();
+
+                    (false, value > right_pivot)
+                }
+            };
+
+            if is_smaller {
+                benchmark.add_swap();
+                benchmark.add_writes(2);
+                slice.swap(curr, less);
+                less += 1;
+                smaller_left -= 1;
+            } else if is_larger {
+                larger_left -= 1;
+
+                while great > curr {
+                    benchmark.add_cmp();
+
+                    if slice[great] <= right_pivot {
+                        break;
+                    }
+
+                    great -= 1;
+                    larger_left -= 1;
+                }
+
+                benchmark.add_swap();
+                benchmark.add_writes(2);
+                slice.swap(curr, great);
+                great -= 1;
+
+                benchmark.add_cmp();
+
+                if slice[curr] < left_pivot {
+                    benchmark.add_swap();
+                    benchmark.add_writes(2);
+                    slice.swap(curr, less);
+                    less += 1;
+                    smaller_left -= 1;
+                }
+            }
+
+            curr += 1;
+        }
+
+        benchmark.add_swap();
+        benchmark.add_writes(2);
+        benchmark.add_swap();
+        benchmark.add_writes(2);
+
+        slice.swap(0, less - 1);
+        slice.swap(last, great + 1);
+
+        (less - 1, great + 1)
+    }
+}
+
+/// Struct implementing `Sorter` + `BenchmarkingSorter` using dual pivot quick sort
+/// to sort items. Slice is partitioned around its first and last item using
+/// partitioning strategy `P`. Recursion goes into two shorter parts only,
+/// so stack depth is `O(log n)` on every input. Parts still unsorted after
+/// `2 log2 n` levels of partitioning are sorted by `HeapSort`, so inputs
+/// where fixed pivots split badly, e.g. sorted input, take `O(n log n)` time
+/// too.
+pub struct DualPivotQuicksortWith<P> {
+    partitioning: PhantomData<P>,
+}
+
+/// Dual pivot quick sort using "count" partitioning strategy.
+pub type DualPivotQuicksort = DualPivotQuicksortWith<CountPartitioning>;
+
+impl<P: DualPivotPartitioning> DualPivotQuicksortWith<P> {
+    fn quick_sort<T: Ord + Copy>(slice: &mut [T]) {
+        let limit = depth_limit(slice.len());
+
+        DualPivotQuicksortWith::<P>::introsort(slice, limit);
+    }
+
+    fn introsort<T: Ord + Copy>(mut slice: &mut [T], mut limit: u32) {
+        while slice.len() > 1 {
+            if limit == 0 {
+                HeapSort::sort(slice);
+                return;
+            }
+
+            limit -= 1;
+
+            let (left_pivot_index, right_pivot_index) = P::partition(slice);
+
+            let mut parts = DualPivotQuicksortWith::<P>::split_parts(
+                std::mem::take(&mut slice),
+                left_pivot_index,
+                right_pivot_index,
+            );
+
+            // recursing only into two shorter parts keeps stack depth logarithmic
+            parts.sort_by_key(|part| part.len());
+
+            let [smallest, middle, largest] = parts;
+
+            DualPivotQuicksortWith::<P>::introsort(smallest, limit);
+            DualPivotQuicksortWith::<P>::introsort(middle, limit);
+
+            slice = largest;
+        }
+    }
+
+    pub fn quick_sort_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        benchmark: &mut impl Benchmark,
+    ) {
+        let limit = depth_limit(slice.len());
+
+        DualPivotQuicksortWith::<P>::introsort_with_benchmark(slice, limit, benchmark);
+    }
+
+    fn introsort_with_benchmark<T: Ord + Copy>(
+        mut slice: &mut [T],
+        mut limit: u32,
+        benchmark: &mut impl Benchmark,
+    ) {
+        while slice.len() > 1 {
+            if limit == 0 {
+                HeapSort::heap_sort_with_benchmark(slice, benchmark);
+                return;
+            }
+
+            limit -= 1;
+
+            let (left_pivot_index, right_pivot_index) =
+                P::partition_with_benchmark(slice, benchmark);
+
+            let mut parts = DualPivotQuicksortWith::<P>::split_parts(
+                std::mem::take(&mut slice),
+                left_pivot_index,
+                right_pivot_index,
+            );
+
+            // recursing only into two shorter parts keeps stack depth logarithmic
+            parts.sort_by_key(|part| part.len());
+
+            let [smallest, middle, largest] = parts;
+
+            DualPivotQuicksortWith::<P>::introsort_with_benchmark(smallest, limit, benchmark);
+            DualPivotQuicksortWith::<P>::introsort_with_benchmark(middle, limit, benchmark);
+
+            slice = largest;
+        }
+    }
+
+    /// Splits partitioned slice into parts before, between and after pivots.
+    fn split_parts<T>(
+        slice: &mut [T],
+        left_pivot_index: usize,
+        right_pivot_index: usize,
+    ) -> [&mut [T]; 3] {
+        let (left, rest) = slice.split_at_mut(left_pivot_index);
+        let (middle, right) = rest[1..].split_at_mut(right_pivot_index - left_pivot_index - 1);
+
+        [left, middle, &mut right[1..]]
+    }
+}
+
+impl<P: DualPivotPartitioning> Sorter for DualPivotQuicksortWith<P> {
     /// Dual pivot quick sort implementation working on types implementing
     /// `Ord` + `Copy` so it's mostly usefull for primitive types.
     ///
@@ -242,11 +801,11 @@ impl Sorter for DualPivotQuicksort {
     /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
     /// ```
     fn sort<T: Ord + Copy>(slice: &mut [T]) {
-        DualPivotQuicksort::quick_sort(slice);
+        DualPivotQuicksortWith::<P>::quick_sort(slice);
     }
 }
 
-impl BenchmarkingSorter for DualPivotQuicksort {
+impl<P: DualPivotPartitioning> BenchmarkingSorter for DualPivotQuicksortWith<P> {
     /// Dual pivot quick sort implementation with additional benchmarking capabilities.
     ///
     /// Examples:
@@ -261,13 +820,13 @@ impl BenchmarkingSorter for DualPivotQuicksort {
     ///
     /// let stats = benchmarker.get_stats();
     ///
-    /// assert_eq!(4, stats.comparisons);
-    /// assert_eq!(7, stats.swaps);
+    /// assert_eq!(10, stats.comparisons);
+    /// assert_eq!(15, stats.swaps);
     /// ```
     fn sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
         benchmark.start_timer();
 
-        DualPivotQuicksort::quick_sort_with_benchmark(slice, benchmark);
+        DualPivotQuicksortWith::<P>::quick_sort_with_benchmark(slice, benchmark);
 
         benchmark.stop_timer();
     }
@@ -276,25 +835,97 @@ impl BenchmarkingSorter for DualPivotQuicksort {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tests::*;
+    use crate::benchmarking::StandardBenchmarker;
+    use crate::generator::*;
 
-    #[test]
-    fn dual_pivot_quick_sort_basic_sorting_test() {
-        basic_sorting_test::<DualPivotQuicksort>();
+    crate::sorter_conformance_tests!(DualPivotQuicksort);
+
+    mod yaroslavskiy {
+        use super::*;
+
+        crate::sorter_conformance_tests!(DualPivotQuicksortWith<YaroslavskiyPartitioning>);
+    }
+
+    mod sedgewick {
+        use super::*;
+
+        crate::sorter_conformance_tests!(DualPivotQuicksortWith<SedgewickPartitioning>);
+    }
+
+    mod clairvoyant {
+        use super::*;
+
+        crate::sorter_conformance_tests!(DualPivotQuicksortWith<ClairvoyantPartitioning>);
+    }
+
+    fn partition_test<P: DualPivotPartitioning>() {
+        for seed in 0..20 {
+            let input = RandomGenerator::generate_seeded(50, seed);
+
+            let mut plain = input.clone();
+            let mut counted = input.clone();
+
+            let pivots = P::partition(&mut plain);
+            let counted_pivots =
+                P::partition_with_benchmark(&mut counted, &mut StandardBenchmarker::default());
+
+            assert_eq!(pivots, counted_pivots);
+            assert_eq!(plain, counted);
+
+            let (left, right) = pivots;
+
+            assert!(plain[..left].iter().all(|&value| value < plain[left]));
+            assert!(plain[left..right]
+                .iter()
+                .all(|&value| value <= plain[right]));
+            assert!(plain[right + 1..].iter().all(|&value| value > plain[right]));
+        }
     }
 
     #[test]
-    fn dual_pivot_quick_sort_empty_sorting_test() {
-        empty_sorting_test::<DualPivotQuicksort>();
+    fn partitioning_strategies_partition_around_pivots() {
+        partition_test::<CountPartitioning>();
+        partition_test::<YaroslavskiyPartitioning>();
+        partition_test::<SedgewickPartitioning>();
+        partition_test::<ClairvoyantPartitioning>();
+    }
+
+    fn comparisons<S: BenchmarkingSorter>() -> usize {
+        (0..5)
+            .map(|seed| {
+                let mut benchmarker = StandardBenchmarker::default();
+
+                S::sort_with_benchmark(
+                    &mut RandomGenerator::generate_seeded(100000, seed),
+                    &mut benchmarker,
+                );
+
+                benchmarker.get_stats().comparisons
+            })
+            .sum()
     }
 
     #[test]
-    fn dual_pivot_quick_sort_sorted_sorting_test() {
-        sorted_sorting_test::<DualPivotQuicksort>();
+    fn ten_million_sorted_items_keep_stack_shallow() {
+        use crate::conformance::shallow_stack_sorting_test;
+
+        let n = 10_000_000;
+
+        shallow_stack_sorting_test::<DualPivotQuicksort>(n);
+        shallow_stack_sorting_test::<DualPivotQuicksortWith<YaroslavskiyPartitioning>>(n);
+        shallow_stack_sorting_test::<DualPivotQuicksortWith<SedgewickPartitioning>>(n);
+        shallow_stack_sorting_test::<DualPivotQuicksortWith<ClairvoyantPartitioning>>(n);
     }
 
     #[test]
-    fn dual_pivot_quick_sort_sorted_backwards_sorting_test() {
-        sorted_backwards_sorting_test::<DualPivotQuicksort>();
+    fn partitioning_strategies_keep_known_order_of_comparisons() {
+        let clairvoyant = comparisons::<DualPivotQuicksortWith<ClairvoyantPartitioning>>();
+        let count = comparisons::<DualPivotQuicksort>();
+        let yaroslavskiy = comparisons::<DualPivotQuicksortWith<YaroslavskiyPartitioning>>();
+        let sedgewick = comparisons::<DualPivotQuicksortWith<SedgewickPartitioning>>();
+
+        assert!(clairvoyant < count);
+        assert!(count < yaroslavskiy);
+        assert!(yaroslavskiy < sedgewick);
     }
 }
@@ -8,13 +8,31 @@ pub trait Generator {
     fn generate(n: usize) -> Vec<u32>;
 }
 
+/// Trait for generators which draw randomness from given random number
+/// generator, so their output can be reproduced from seed.
+pub trait SeededGenerator: Generator {
+    /// Generates `n` values drawing randomness from given `rng`.
+    fn generate_with_rng(n: usize, rng: &mut impl Rng) -> Vec<u32>;
+
+    /// Generates `n` values deterministically from given `seed`.
+    fn generate_seeded(n: usize, seed: u64) -> Vec<u32> {
+        Self::generate_with_rng(n, &mut Pcg64Mcg::seed_from_u64(seed))
+    }
+}
+
 /// Generator generating values at random with range from `0` to `2*n - 1`.
 pub struct RandomGenerator;
 
 impl Generator for RandomGenerator {
     fn generate(n: usize) -> Vec<u32> {
+        RandomGenerator::generate_with_rng(n, &mut Pcg64Mcg::from_entropy())
+    }
+}
+
+impl SeededGenerator for RandomGenerator {
+    fn generate_with_rng(n: usize, rng: &mut impl Rng) -> Vec<u32> {
         (0..n)
-            .map(|_| Pcg64Mcg::from_entropy().gen_range(0..2 * n - 1))
+            .map(|_| rng.gen_range(0..2 * n - 1))
             .map(|number| number.try_into().expect("value range too big"))
             .collect()
     }
@@ -26,12 +44,16 @@ pub struct AscendingGenerator;
 
 impl Generator for AscendingGenerator {
     fn generate(n: usize) -> Vec<u32> {
-        let mut numbers = (0..n)
-            .map(|_| Pcg64Mcg::from_entropy().gen_range(0..10))
-            .collect::<Vec<_>>();
+        AscendingGenerator::generate_with_rng(n, &mut Pcg64Mcg::from_entropy())
+    }
+}
+
+impl SeededGenerator for AscendingGenerator {
+    fn generate_with_rng(n: usize, rng: &mut impl Rng) -> Vec<u32> {
+        let mut numbers = (0..n).map(|_| rng.gen_range(0..10)).collect::<Vec<_>>();
 
-        for i in 0..numbers.len() - 1 {
-            numbers[i + 1] += numbers[i];
+        for i in 1..numbers.len() {
+            numbers[i] += numbers[i - 1];
         }
 
         numbers
@@ -44,16 +66,17 @@ pub struct DescendingGenerator;
 
 impl Generator for DescendingGenerator {
     fn generate(n: usize) -> Vec<u32> {
-        let mut numbers = (0..n)
-            .map(|_| Pcg64Mcg::from_entropy().gen_range(0..10))
-            .map(|number| number.try_into().expect("value range too big"))
-            .collect::<Vec<u32>>();
+        DescendingGenerator::generate_with_rng(n, &mut Pcg64Mcg::from_entropy())
+    }
+}
 
-        for i in 0..numbers.len() - 1 {
-            numbers[i + 1] += numbers[i];
-        }
+impl SeededGenerator for DescendingGenerator {
+    fn generate_with_rng(n: usize, rng: &mut impl Rng) -> Vec<u32> {
+        let mut numbers = AscendingGenerator::generate_with_rng(n, rng);
+
+        numbers.reverse();
 
-        numbers.into_iter().rev().collect()
+        numbers
     }
 }
 
@@ -94,4 +117,40 @@ mod tests {
 
         assert_eq!(values.len(), 100);
     }
+
+    #[test]
+    fn seeded_generators_are_deterministic() {
+        assert_eq!(
+            RandomGenerator::generate_seeded(100, 7),
+            RandomGenerator::generate_seeded(100, 7)
+        );
+        assert_eq!(
+            AscendingGenerator::generate_seeded(100, 7),
+            AscendingGenerator::generate_seeded(100, 7)
+        );
+        assert_eq!(
+            DescendingGenerator::generate_seeded(100, 7),
+            DescendingGenerator::generate_seeded(100, 7)
+        );
+    }
+
+    #[test]
+    fn generators_handle_empty_input() {
+        assert!(RandomGenerator::generate(0).is_empty());
+        assert!(AscendingGenerator::generate(0).is_empty());
+        assert!(DescendingGenerator::generate(0).is_empty());
+    }
+
+    #[test]
+    fn generator_needs_only_generate() {
+        struct ZeroGenerator;
+
+        impl Generator for ZeroGenerator {
+            fn generate(n: usize) -> Vec<u32> {
+                vec![0; n]
+            }
+        }
+
+        assert_eq!(vec![0; 3], ZeroGenerator::generate(3));
+    }
 }
@@ -0,0 +1,161 @@
+//! Heap sort algorithm implementation.
+
+use crate::prelude::*;
+
+/// Struct implementing `Sorter` + `BenchmarkingSorter` using binary max-heap
+/// built in place to sort items. It makes `O(n log n)` comparisons on every
+/// input with no auxiliary memory, so quicksorts fall back to it once their
+/// recursion gets too deep.
+pub struct HeapSort;
+
+impl HeapSort {
+    fn heap_sort<T: Ord + Copy>(slice: &mut [T]) {
+        for i in (0..slice.len() / 2).rev() {
+            HeapSort::sift_down(slice, i);
+        }
+
+        for end in (1..slice.len()).rev() {
+            slice.swap(0, end);
+            HeapSort::sift_down(&mut slice[..end], 0);
+        }
+    }
+
+    fn sift_down<T: Ord + Copy>(slice: &mut [T], mut node: usize) {
+        loop {
+            let mut child = 2 * node + 1;
+
+            if child >= slice.len() {
+                break;
+            }
+
+            if child + 1 < slice.len() && slice[child] < slice[child + 1] {
+                child += 1;
+            }
+
+            if slice[node] >= slice[child] {
+                break;
+            }
+
+            slice.swap(node, child);
+            node = child;
+        }
+    }
+
+    /// Heap sort counting comparisons and swaps without touching timer,
+    /// so other algorithms can use it as fallback.
+    pub(crate) fn heap_sort_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        benchmark: &mut impl Benchmark,
+    ) {
+        for i in (0..slice.len() / 2).rev() {
+            HeapSort::sift_down_with_benchmark(slice, i, benchmark);
+        }
+
+        for end in (1..slice.len()).rev() {
+            benchmark.add_swap();
+            benchmark.add_writes(2);
+            slice.swap(0, end);
+            HeapSort::sift_down_with_benchmark(&mut slice[..end], 0, benchmark);
+        }
+    }
+
+    fn sift_down_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        mut node: usize,
+        benchmark: &mut impl Benchmark,
+    ) {
+        loop {
+            let mut child = 2 * node + 1;
+
+            if child >= slice.len() {
+                break;
+            }
+
+            if child + 1 < slice.len() {
+                benchmark.add_cmp();
+
+                if slice[child] < slice[child + 1] {
+                    child += 1;
+                }
+            }
+
+            benchmark.add_cmp();
+
+            if slice[node] >= slice[child] {
+                break;
+            }
+
+            benchmark.add_swap();
+            benchmark.add_writes(2);
+            slice.swap(node, child);
+            node = child;
+        }
+    }
+}
+
+impl Sorter for HeapSort {
+    /// Heap sort implementation working on types implementing
+    /// `Ord` + `Copy` so it's mostly usefull for primitive types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    ///
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// HeapSort::sort(&mut slice);
+    ///
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// ```
+    fn sort<T: Ord + Copy>(slice: &mut [T]) {
+        HeapSort::heap_sort(slice);
+    }
+}
+
+impl BenchmarkingSorter for HeapSort {
+    /// Heap sort implementation with additional benchmarking capabilities.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::StandardBenchmarker;
+    ///
+    /// let mut benchmarker = StandardBenchmarker::default();
+    /// let mut slice = [1, 2, 3, 4, 5, 6, 7];
+    ///
+    /// HeapSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!([1, 2, 3, 4, 5, 6, 7], slice);
+    /// assert_eq!(2 * stats.swaps, stats.writes);
+    /// ```
+    fn sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        benchmark.start_timer();
+
+        HeapSort::heap_sort_with_benchmark(slice, benchmark);
+
+        benchmark.stop_timer();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarking::StandardBenchmarker;
+    use crate::generator::{RandomGenerator, SeededGenerator};
+
+    crate::sorter_conformance_tests!(HeapSort);
+
+    #[test]
+    fn heap_sort_takes_n_log_n_comparisons() {
+        for n in [1000, 100000] {
+            let mut slice = RandomGenerator::generate_seeded(n, 0);
+            let mut benchmarker = StandardBenchmarker::default();
+
+            HeapSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+
+            assert!(benchmarker.get_stats().comparisons <= 2 * n * n.ilog2() as usize);
+        }
+    }
+}
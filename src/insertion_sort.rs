@@ -6,6 +6,63 @@ use crate::prelude::*;
 /// to sort items.
 pub struct InsertionSort;
 
+impl InsertionSort {
+    /// Insertion sort counting comparisons and swaps without touching timer,
+    /// so other algorithms can use it for small subproblems.
+    pub(crate) fn insertion_sort_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        benchmark: &mut impl Benchmark,
+    ) {
+        InsertionSort::gapped_insertion_sort_with_benchmark(slice, 1, benchmark);
+    }
+
+    /// Insertion sort of every subsequence made of items `gap` apart. It's
+    /// the inner loop of shellsort, with `gap` equal to 1 it's plain
+    /// insertion sort.
+    pub(crate) fn gapped_insertion_sort<T: Ord + Copy>(slice: &mut [T], gap: usize) {
+        for i in gap..slice.len() {
+            let mut j = i;
+            let elem = slice[i];
+
+            while j >= gap && slice[j - gap] > elem {
+                slice[j] = slice[j - gap];
+                j -= gap;
+            }
+
+            if j != i {
+                slice[j] = elem;
+            }
+        }
+    }
+
+    /// Gapped insertion sort counting comparisons and swaps without touching
+    /// timer.
+    pub(crate) fn gapped_insertion_sort_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        gap: usize,
+        benchmark: &mut impl Benchmark,
+    ) {
+        for i in gap..slice.len() {
+            let mut j = i;
+            let elem = slice[i];
+
+            while j >= gap && slice[j - gap] > elem {
+                slice[j] = slice[j - gap];
+                j -= gap;
+
+                benchmark.add_cmp();
+                benchmark.add_swap();
+                benchmark.add_writes(1);
+            }
+
+            if j != i {
+                slice[j] = elem;
+                benchmark.add_writes(1);
+            }
+        }
+    }
+}
+
 impl Sorter for InsertionSort {
     /// Insertion sort implementation working on types implementing
     /// `Ord` + `Copy` so it's mostly usefull for primitive types.
@@ -21,17 +78,7 @@ impl Sorter for InsertionSort {
     /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
     /// ```
     fn sort<T: Ord + Copy>(slice: &mut [T]) {
-        for i in 0..slice.len() {
-            let mut j = i;
-            let elem = slice[i];
-
-            while j > 0 && slice[j - 1] > elem {
-                slice[j] = slice[j - 1];
-                j -= 1;
-            }
-
-            slice[j] = elem;
-        }
+        InsertionSort::gapped_insertion_sort(slice, 1);
     }
 }
 
@@ -52,51 +99,183 @@ impl BenchmarkingSorter for InsertionSort {
     ///
     /// assert_eq!(4, stats.comparisons);
     /// assert_eq!(4, stats.swaps);
+    /// assert_eq!(7, stats.writes);
     /// ```
     fn sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
         benchmark.start_timer();
 
-        for i in 0..slice.len() {
-            let mut j = i;
+        InsertionSort::insertion_sort_with_benchmark(slice, benchmark);
+
+        benchmark.stop_timer();
+    }
+}
+
+impl StableSorter for InsertionSort {}
+
+/// Struct implementing `Sorter` + `BenchmarkingSorter` using binary
+/// insertion sort to sort items. Position of every item in sorted prefix is
+/// found by binary search, so it takes at most `ceil(log2(i + 1))`
+/// comparisons for `i`-th item, but items are shifted like in insertion
+/// sort. Item is inserted after all equal items, so sort is stable.
+pub struct BinaryInsertionSort;
+
+impl BinaryInsertionSort {
+    fn binary_insertion_sort<T: Ord + Copy>(slice: &mut [T]) {
+        for i in 1..slice.len() {
             let elem = slice[i];
+            let mut low = 0;
+            let mut high = i;
 
-            while j > 0 && slice[j - 1] > elem {
-                slice[j] = slice[j - 1];
-                j -= 1;
+            while low < high {
+                let mid = low + (high - low) / 2;
+
+                if elem < slice[mid] {
+                    high = mid;
+                } else {
+                    low = mid + 1;
+                }
+            }
+
+            slice.copy_within(low..i, low + 1);
+            slice[low] = elem;
+        }
+    }
+
+    fn binary_insertion_sort_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        benchmark: &mut impl Benchmark,
+    ) {
+        for i in 1..slice.len() {
+            let elem = slice[i];
+            let mut low = 0;
+            let mut high = i;
+
+            while low < high {
+                let mid = low + (high - low) / 2;
 
                 benchmark.add_cmp();
+
+                if elem < slice[mid] {
+                    high = mid;
+                } else {
+                    low = mid + 1;
+                }
+            }
+
+            if low == i {
+                continue;
+            }
+
+            for _ in low..i {
                 benchmark.add_swap();
             }
 
-            slice[j] = elem;
+            benchmark.add_writes(i - low + 1);
+            slice.copy_within(low..i, low + 1);
+            slice[low] = elem;
         }
+    }
+}
+
+impl Sorter for BinaryInsertionSort {
+    /// Binary insertion sort implementation working on types implementing
+    /// `Ord` + `Copy` so it's mostly usefull for primitive types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    ///
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// BinaryInsertionSort::sort(&mut slice);
+    ///
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// ```
+    fn sort<T: Ord + Copy>(slice: &mut [T]) {
+        BinaryInsertionSort::binary_insertion_sort(slice);
+    }
+}
+
+impl BenchmarkingSorter for BinaryInsertionSort {
+    /// Binary insertion sort implementation with additional benchmarking
+    /// capabilities. Every shifted item is counted as a swap.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::StandardBenchmarker;
+    ///
+    /// let mut benchmarker = StandardBenchmarker::default();
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// BinaryInsertionSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!(10, stats.comparisons);
+    /// assert_eq!(4, stats.swaps);
+    /// assert_eq!(7, stats.writes);
+    /// ```
+    fn sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        benchmark.start_timer();
+
+        BinaryInsertionSort::binary_insertion_sort_with_benchmark(slice, benchmark);
 
         benchmark.stop_timer();
     }
 }
 
+impl StableSorter for BinaryInsertionSort {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tests::*;
 
-    #[test]
-    fn insertion_sort_basic_sorting_test() {
-        basic_sorting_test::<InsertionSort>();
-    }
+    crate::sorter_conformance_tests!(InsertionSort, stable);
 
-    #[test]
-    fn insertion_sort_empty_sorting_test() {
-        empty_sorting_test::<InsertionSort>();
-    }
+    mod binary {
+        use super::*;
+        use crate::benchmarking::StandardBenchmarker;
+        use crate::conformance::count_comparisons;
+        use crate::generator::{DescendingGenerator, RandomGenerator, SeededGenerator};
 
-    #[test]
-    fn insertion_sort_sorted_sorting_test() {
-        sorted_sorting_test::<InsertionSort>();
-    }
+        crate::sorter_conformance_tests!(BinaryInsertionSort, stable);
+
+        #[test]
+        fn comparisons_stay_below_binary_search_bound() {
+            for n in [2usize, 3, 10, 100, 1000] {
+                let bound = (1..n)
+                    .map(|i| (i + 1).next_power_of_two().ilog2() as usize)
+                    .sum();
+
+                for input in [
+                    RandomGenerator::generate_seeded(n, 1),
+                    DescendingGenerator::generate_seeded(n, 1),
+                ] {
+                    let mut benchmarker = StandardBenchmarker::default();
+
+                    BinaryInsertionSort::sort_with_benchmark(&mut input.clone(), &mut benchmarker);
+
+                    let comparisons = count_comparisons::<BinaryInsertionSort>(&input);
+
+                    assert!(comparisons <= bound, "{} items", n);
+                    assert_eq!(benchmarker.get_stats().comparisons, comparisons);
+                }
+            }
+        }
+
+        #[test]
+        fn writes_match_linear_insertion_sort() {
+            for n in [2, 10, 100, 1000] {
+                let input = RandomGenerator::generate_seeded(n, 2);
+                let mut linear = StandardBenchmarker::default();
+                let mut binary = StandardBenchmarker::default();
 
-    #[test]
-    fn insertion_sort_sorted_backwards_sorting_test() {
-        sorted_backwards_sorting_test::<InsertionSort>();
+                InsertionSort::sort_with_benchmark(&mut input.clone(), &mut linear);
+                BinaryInsertionSort::sort_with_benchmark(&mut input.clone(), &mut binary);
+
+                assert_eq!(linear.get_stats().writes, binary.get_stats().writes);
+            }
+        }
     }
 }
@@ -1,12 +1,38 @@
 pub mod insertion_sort;
 pub mod benchmarking;
 pub mod merge_sort;
+pub mod merging;
 pub mod quick_sort;
 pub mod timsort;
+pub mod block_sort;
 pub mod prelude;
 pub mod dual_pivot_quicksort;
+pub mod multi_pivot_quicksort;
+pub mod three_way_quicksort;
+pub mod pdqsort;
+pub mod parallel_sort;
+pub mod sample_sort;
+pub mod sorting_network;
+pub mod oblivious_sort;
+pub mod smooth_sort;
+pub mod weak_heap_sort;
+pub mod heap_sort;
+pub mod selection_sort;
+pub mod cycle_sort;
+pub mod merge_insertion_sort;
+pub mod shell_sort;
+pub mod rotation;
+pub mod radix_sort;
+pub mod counting_sort;
+pub mod bucket_sort;
+pub mod adaptive_sort;
 pub mod generator;
-mod tests;
+pub mod property;
+pub mod analysis;
+pub mod measures;
+pub mod tuning;
+pub mod autotune;
+pub mod conformance;
 
 // TODO: make sure all comparisons and swaps count are correct
 // TODO: change introsort docs
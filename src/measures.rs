@@ -0,0 +1,364 @@
+//! Measures of presortedness, quantifying how far input is from being
+//! sorted. All of them are 0 for sorted input, except for number of runs,
+//! which is 1.
+
+use crate::merge_sort::merge_into;
+
+/// Returns number of inversions, i.e. pairs of items where the bigger one
+/// comes first. It's computed in `O(n log n)` by bottom-up merge sort of a
+/// copy of `slice`, counting inversions between every two merged runs.
+///
+/// Examples:
+/// ```
+/// use algorithms::measures;
+///
+/// assert_eq!(0, measures::inversions(&[1, 2, 2, 3]));
+/// assert_eq!(5, measures::inversions(&[3, 1, 2, 0]));
+/// assert_eq!(6, measures::inversions(&[4, 3, 2, 1]));
+/// ```
+pub fn inversions<T: Ord + Copy>(slice: &[T]) -> usize {
+    let mut source = slice.to_vec();
+    let mut dest = slice.to_vec();
+    let mut inversions = 0;
+    let mut width = 1;
+
+    while width < slice.len() {
+        for start in (0..slice.len()).step_by(2 * width) {
+            let mid = (start + width).min(slice.len());
+            let end = (start + 2 * width).min(slice.len());
+
+            inversions += merge_into(
+                &source[start..mid],
+                &source[mid..end],
+                &mut dest[start..end],
+            );
+        }
+
+        std::mem::swap(&mut source, &mut dest);
+        width *= 2;
+    }
+
+    inversions
+}
+
+/// Returns lengths of maximal ascending (non-descending) runs of `slice`.
+fn run_lengths<T: Ord>(slice: &[T]) -> Vec<usize> {
+    let mut lengths = Vec::new();
+    let mut start = 0;
+
+    for end in 1..=slice.len() {
+        if end == slice.len() || slice[end] < slice[end - 1] {
+            lengths.push(end - start);
+            start = end;
+        }
+    }
+
+    lengths
+}
+
+/// Returns number of maximal ascending (non-descending) runs, i.e. number
+/// of descents plus one. Empty slice has no runs.
+///
+/// Examples:
+/// ```
+/// use algorithms::measures;
+///
+/// assert_eq!(0, measures::runs::<u32>(&[]));
+/// assert_eq!(1, measures::runs(&[1, 2, 2, 3]));
+/// assert_eq!(3, measures::runs(&[1, 5, 2, 4, 3]));
+/// ```
+pub fn runs<T: Ord>(slice: &[T]) -> usize {
+    run_lengths(slice).len()
+}
+
+/// Returns Rem, the least number of items which have to be removed to leave
+/// sorted sequence, i.e. `n` minus length of the longest non-descending
+/// subsequence. It's computed in `O(n log n)` by patience sorting.
+///
+/// Examples:
+/// ```
+/// use algorithms::measures;
+///
+/// assert_eq!(0, measures::rem(&[1, 2, 2, 3]));
+/// assert_eq!(2, measures::rem(&[1, 5, 2, 4, 3]));
+/// assert_eq!(3, measures::rem(&[4, 3, 2, 1]));
+/// ```
+pub fn rem<T: Ord + Copy>(slice: &[T]) -> usize {
+    // smallest last item of non-descending subsequence of every length
+    let mut tails: Vec<T> = Vec::new();
+
+    for &item in slice {
+        let pos = tails.partition_point(|&tail| tail <= item);
+
+        if pos == tails.len() {
+            tails.push(item);
+        } else {
+            tails[pos] = item;
+        }
+    }
+
+    slice.len() - tails.len()
+}
+
+/// Returns Osc of Levcopoulos and Petersson, i.e. total number of times
+/// items are strictly crossed by segments between neighbouring items. It
+/// counts oscillations of zigzag inputs, which other measures miss. Every
+/// item is checked against sorted segment ends in `O(n log n)`.
+///
+/// Examples:
+/// ```
+/// use algorithms::measures;
+///
+/// assert_eq!(0, measures::osc(&[1, 2, 3, 4]));
+/// assert_eq!(0, measures::osc(&[4, 3, 2, 1]));
+/// assert_eq!(6, measures::osc(&[1, 4, 2, 5, 3]));
+/// ```
+pub fn osc<T: Ord + Copy>(slice: &[T]) -> usize {
+    let (mut lows, mut highs): (Vec<T>, Vec<T>) = slice
+        .windows(2)
+        .filter(|pair| pair[0] != pair[1])
+        .map(|pair| (pair[0].min(pair[1]), pair[0].max(pair[1])))
+        .unzip();
+
+    lows.sort_unstable();
+    highs.sort_unstable();
+
+    // segment strictly crosses item if its lower end is smaller and its
+    // higher end isn't smaller or equal, every segment ending below item
+    // starts below it too
+    slice
+        .iter()
+        .map(|item| {
+            lows.partition_point(|low| low < item) - highs.partition_point(|high| high <= item)
+        })
+        .sum()
+}
+
+/// Returns distance of every item from its position in stably sorted
+/// `slice`.
+fn displacements<T: Ord>(slice: &[T]) -> Vec<usize> {
+    let mut order = (0..slice.len()).collect::<Vec<_>>();
+
+    order.sort_by(|&a, &b| slice[a].cmp(&slice[b]));
+
+    order
+        .into_iter()
+        .enumerate()
+        .map(|(pos, index)| pos.abs_diff(index))
+        .collect()
+}
+
+/// Returns the biggest distance of item from its position in sorted slice.
+/// Equal items keep their order, so they aren't displaced needlessly.
+///
+/// Examples:
+/// ```
+/// use algorithms::measures;
+///
+/// assert_eq!(0, measures::max_displacement(&[1, 2, 2, 3]));
+/// assert_eq!(3, measures::max_displacement(&[4, 1, 2, 3]));
+/// ```
+pub fn max_displacement<T: Ord>(slice: &[T]) -> usize {
+    displacements(slice).into_iter().max().unwrap_or(0)
+}
+
+/// Returns sum of distances of all items from their positions in sorted
+/// slice, which is Spearman's footrule distance to it.
+///
+/// Examples:
+/// ```
+/// use algorithms::measures;
+///
+/// assert_eq!(0, measures::total_displacement(&[1, 2, 2, 3]));
+/// assert_eq!(6, measures::total_displacement(&[4, 1, 2, 3]));
+/// ```
+pub fn total_displacement<T: Ord>(slice: &[T]) -> usize {
+    displacements(slice).into_iter().sum()
+}
+
+/// Returns Shannon entropy `H = -sum(r_i / n * log2(r_i / n))` of lengths
+/// `r_i` of ascending runs in bits. Merging runs in Huffman order takes
+/// `O(n (1 + H))` comparisons, so it measures disorder in number of runs
+/// and their balance at once. It's 0 for sorted input and `log2 n` for
+/// strictly descending one.
+///
+/// Examples:
+/// ```
+/// use algorithms::measures;
+///
+/// assert_eq!(0.0, measures::runs_entropy(&[1, 2, 2, 3]));
+/// assert_eq!(1.0, measures::runs_entropy(&[1, 2, 0, 1]));
+/// assert_eq!(2.0, measures::runs_entropy(&[4, 3, 2, 1]));
+/// ```
+pub fn runs_entropy<T: Ord>(slice: &[T]) -> f64 {
+    let n = slice.len() as f64;
+
+    run_lengths(slice)
+        .into_iter()
+        .map(|len| len as f64 / n)
+        .map(|p| -p * p.log2())
+        .sum::<f64>()
+        .max(0.0)
+}
+
+/// All measures of presortedness of one input.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Presortedness {
+    pub inversions: usize,
+    pub runs: usize,
+    pub rem: usize,
+    pub osc: usize,
+    pub max_displacement: usize,
+    pub total_displacement: usize,
+    pub runs_entropy: f64,
+}
+
+impl Presortedness {
+    /// Computes all measures of presortedness of `slice`.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::measures::Presortedness;
+    ///
+    /// let measures = Presortedness::of(&[2, 1, 3, 5, 4]);
+    ///
+    /// assert_eq!(2, measures.inversions);
+    /// assert_eq!(3, measures.runs);
+    /// assert_eq!(2, measures.rem);
+    /// assert_eq!(1, measures.max_displacement);
+    /// ```
+    pub fn of<T: Ord + Copy>(slice: &[T]) -> Self {
+        Presortedness {
+            inversions: inversions(slice),
+            runs: runs(slice),
+            rem: rem(slice),
+            osc: osc(slice),
+            max_displacement: max_displacement(slice),
+            total_displacement: total_displacement(slice),
+            runs_entropy: runs_entropy(slice),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::{
+        AscendingGenerator, DescendingGenerator, RandomGenerator, SeededGenerator,
+    };
+
+    fn naive_inversions(slice: &[u32]) -> usize {
+        (0..slice.len())
+            .flat_map(|i| (i + 1..slice.len()).map(move |j| (i, j)))
+            .filter(|&(i, j)| slice[i] > slice[j])
+            .count()
+    }
+
+    fn naive_osc(slice: &[u32]) -> usize {
+        slice
+            .iter()
+            .map(|item| {
+                slice
+                    .windows(2)
+                    .filter(|pair| pair[0].min(pair[1]) < *item && *item < pair[0].max(pair[1]))
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Rem by quadratic dynamic programming over longest non-descending
+    /// subsequence ending at every item.
+    fn naive_rem(slice: &[u32]) -> usize {
+        let mut longest = vec![1; slice.len()];
+
+        for i in 0..slice.len() {
+            for j in 0..i {
+                if slice[j] <= slice[i] {
+                    longest[i] = longest[i].max(longest[j] + 1);
+                }
+            }
+        }
+
+        slice.len() - longest.into_iter().max().unwrap_or(0)
+    }
+
+    fn inputs() -> Vec<Vec<u32>> {
+        [0, 1, 2, 10, 100, 500]
+            .into_iter()
+            .enumerate()
+            .flat_map(|(seed, n)| {
+                let seed = seed as u64;
+
+                [
+                    RandomGenerator::generate_seeded(n, seed),
+                    RandomGenerator::generate_seeded(n, seed)
+                        .into_iter()
+                        .map(|item| item % 8)
+                        .collect(),
+                    AscendingGenerator::generate_seeded(n, seed),
+                    DescendingGenerator::generate_seeded(n, seed),
+                ]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn measures_match_naive_implementations() {
+        for input in inputs() {
+            assert_eq!(naive_inversions(&input), inversions(&input));
+            assert_eq!(naive_osc(&input), osc(&input));
+            assert_eq!(naive_rem(&input), rem(&input));
+        }
+    }
+
+    #[test]
+    fn sorted_input_has_no_disorder() {
+        for input in inputs() {
+            let mut sorted = input.clone();
+            sorted.sort();
+
+            assert_eq!(
+                Presortedness {
+                    inversions: 0,
+                    runs: !sorted.is_empty() as usize,
+                    rem: 0,
+                    osc: 0,
+                    max_displacement: 0,
+                    total_displacement: 0,
+                    runs_entropy: 0.0,
+                },
+                Presortedness::of(&sorted)
+            );
+        }
+    }
+
+    #[test]
+    fn descending_input_has_maximal_disorder() {
+        let n = 100;
+        let input = (0..n as u32).rev().collect::<Vec<_>>();
+        let measures = Presortedness::of(&input);
+
+        assert_eq!(n * (n - 1) / 2, measures.inversions);
+        assert_eq!(n, measures.runs);
+        assert_eq!(n - 1, measures.rem);
+        assert_eq!(n - 1, measures.max_displacement);
+        assert_eq!(n * n / 2, measures.total_displacement);
+        assert!((measures.runs_entropy - (n as f64).log2()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn measures_are_related() {
+        for input in inputs() {
+            let measures = Presortedness::of(&input);
+
+            // every descent is an inversion and removing one item of every
+            // inversion leaves sorted sequence
+            assert!(measures.runs.saturating_sub(1) <= measures.inversions);
+            assert!(measures.rem <= measures.inversions);
+            // Diaconis–Graham inequality
+            assert!(measures.inversions <= measures.total_displacement);
+            assert!(measures.total_displacement <= 2 * measures.inversions);
+            assert!(measures.runs_entropy <= (input.len().max(1) as f64).log2() + 1e-9);
+        }
+    }
+}
@@ -0,0 +1,348 @@
+//! Ford–Johnson merge-insertion sort algorithm implementation.
+
+use crate::prelude::*;
+
+/// Returns `k`-th Jacobsthal-based bound `(2^(k + 1) + (-1)^k) / 3`, i.e.
+/// 1, 3, 5, 11, 21, 43, ... for `k` from 1. Pending items with indices in
+/// `(t(k - 1), t(k)]` are inserted in one group, each of them into
+/// `2^k - 1` items at most.
+fn group_bound(k: u32) -> usize {
+    ((1 << (k + 1)) + if k.is_multiple_of(2) { 1 } else { -1 }) as usize / 3
+}
+
+/// Struct implementing `Sorter` + `BenchmarkingSorter` using Ford–Johnson
+/// merge-insertion sort to sort items. Items are compared in pairs, larger
+/// items of pairs are sorted recursively and smaller ones are inserted into
+/// them by binary search in order given by Jacobsthal numbers, so that every
+/// binary search is done on `2^k - 1` items. It makes the fewest comparisons
+/// of all known algorithms for small inputs, close to the `ceil(log2 n!)`
+/// lower bound, and is optimal for up to 11 items. Items are only moved at
+/// the end, but bookkeeping takes `O(n^2)` time, so it's useful only where
+/// comparisons are expensive.
+pub struct MergeInsertionSort;
+
+impl MergeInsertionSort {
+    fn merge_insertion_sort<T: Ord + Copy>(slice: &mut [T]) {
+        if slice.len() < 2 {
+            return;
+        }
+
+        let order = MergeInsertionSort::order(slice);
+        let sorted = order.iter().map(|&index| slice[index]).collect::<Vec<_>>();
+
+        slice.copy_from_slice(&sorted);
+    }
+
+    /// Returns indices of `items` in sorted order.
+    fn order<T: Ord + Copy>(items: &[T]) -> Vec<usize> {
+        if items.len() < 2 {
+            return (0..items.len()).collect();
+        }
+
+        // pairs of (larger, smaller) item indices
+        let pairs = (0..items.len() / 2)
+            .map(|i| {
+                if items[2 * i + 1] < items[2 * i] {
+                    (2 * i, 2 * i + 1)
+                } else {
+                    (2 * i + 1, 2 * i)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let larger = pairs.iter().map(|&(a, _)| items[a]).collect::<Vec<_>>();
+        let pairs = MergeInsertionSort::order(&larger)
+            .into_iter()
+            .map(|pair| pairs[pair])
+            .collect::<Vec<_>>();
+
+        let mut chain = Vec::with_capacity(items.len());
+
+        chain.push(pairs[0].1);
+        chain.extend(pairs.iter().map(|&(a, _)| a));
+
+        // item without pair is pending as the last one, bounded by whole chain
+        let pending = pairs.len() + items.len() % 2;
+
+        for k in 2.. {
+            let first = group_bound(k - 1) + 1;
+
+            if first > pending {
+                break;
+            }
+
+            for j in (first..=group_bound(k).min(pending)).rev() {
+                let (item, bound) = match pairs.get(j - 1) {
+                    Some(&(a, b)) => (b, chain.iter().position(|&index| index == a).unwrap()),
+                    None => (items.len() - 1, chain.len()),
+                };
+
+                let pos = MergeInsertionSort::search(items, &chain[..bound], item);
+
+                chain.insert(pos, item);
+            }
+        }
+
+        chain
+    }
+
+    /// Returns position in sorted `chain` after all items not bigger than
+    /// `item`. Binary search on `2^k - 1` items takes exactly `k` comparisons.
+    fn search<T: Ord + Copy>(items: &[T], chain: &[usize], item: usize) -> usize {
+        let mut low = 0;
+        let mut high = chain.len();
+
+        while low < high {
+            let mid = low + (high - low) / 2;
+
+            if items[item] < items[chain[mid]] {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+
+        low
+    }
+
+    fn merge_insertion_sort_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        benchmark: &mut impl Benchmark,
+    ) {
+        if slice.len() < 2 {
+            return;
+        }
+
+        let order = MergeInsertionSort::order_with_benchmark(slice, benchmark);
+        let sorted = order.iter().map(|&index| slice[index]).collect::<Vec<_>>();
+
+        benchmark.add_allocation(std::mem::size_of_val(sorted.as_slice()));
+        benchmark.add_writes(sorted.len());
+
+        for (pos, index) in order.into_iter().enumerate() {
+            if pos != index {
+                benchmark.add_writes(1);
+                slice[pos] = sorted[pos];
+            }
+        }
+    }
+
+    fn order_with_benchmark<T: Ord + Copy>(
+        items: &[T],
+        benchmark: &mut impl Benchmark,
+    ) -> Vec<usize> {
+        if items.len() < 2 {
+            return (0..items.len()).collect();
+        }
+
+        // pairs of (larger, smaller) item indices
+        let pairs = (0..items.len() / 2)
+            .map(|i| {
+                benchmark.add_cmp();
+
+                if items[2 * i + 1] < items[2 * i] {
+                    benchmark.add_swap();
+                    (2 * i, 2 * i + 1)
+                } else {
+                    (2 * i + 1, 2 * i)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let larger = pairs.iter().map(|&(a, _)| items[a]).collect::<Vec<_>>();
+        let pairs = MergeInsertionSort::order_with_benchmark(&larger, benchmark)
+            .into_iter()
+            .map(|pair| pairs[pair])
+            .collect::<Vec<_>>();
+
+        let mut chain = Vec::with_capacity(items.len());
+
+        benchmark.add_allocation(items.len() * std::mem::size_of::<usize>());
+        benchmark.add_pass();
+
+        chain.push(pairs[0].1);
+        chain.extend(pairs.iter().map(|&(a, _)| a));
+
+        // item without pair is pending as the last one, bounded by whole chain
+        let pending = pairs.len() + items.len() % 2;
+
+        for k in 2.. {
+            let first = group_bound(k - 1) + 1;
+
+            if first > pending {
+                break;
+            }
+
+            for j in (first..=group_bound(k).min(pending)).rev() {
+                let (item, bound) = match pairs.get(j - 1) {
+                    Some(&(a, b)) => (b, chain.iter().position(|&index| index == a).unwrap()),
+                    None => (items.len() - 1, chain.len()),
+                };
+
+                let pos = MergeInsertionSort::search_with_benchmark(
+                    items,
+                    &chain[..bound],
+                    item,
+                    benchmark,
+                );
+
+                chain.insert(pos, item);
+            }
+        }
+
+        chain
+    }
+
+    fn search_with_benchmark<T: Ord + Copy>(
+        items: &[T],
+        chain: &[usize],
+        item: usize,
+        benchmark: &mut impl Benchmark,
+    ) -> usize {
+        let mut low = 0;
+        let mut high = chain.len();
+
+        while low < high {
+            let mid = low + (high - low) / 2;
+
+            benchmark.add_cmp();
+
+            if items[item] < items[chain[mid]] {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+
+        low
+    }
+}
+
+impl Sorter for MergeInsertionSort {
+    /// Merge-insertion sort implementation working on types implementing
+    /// `Ord` + `Copy` so it's mostly usefull for primitive types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    ///
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// MergeInsertionSort::sort(&mut slice);
+    ///
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// ```
+    fn sort<T: Ord + Copy>(slice: &mut [T]) {
+        MergeInsertionSort::merge_insertion_sort(slice);
+    }
+}
+
+impl BenchmarkingSorter for MergeInsertionSort {
+    /// Merge-insertion sort implementation with additional benchmarking
+    /// capabilities. Pairs of items compared out of order are counted as
+    /// swaps, every level of recursion as a pass.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::StandardBenchmarker;
+    ///
+    /// let mut benchmarker = StandardBenchmarker::default();
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// MergeInsertionSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// assert!(stats.comparisons <= 10);
+    /// ```
+    fn sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        benchmark.start_timer();
+
+        MergeInsertionSort::merge_insertion_sort_with_benchmark(slice, benchmark);
+
+        benchmark.stop_timer();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::comparison_lower_bound;
+    use crate::benchmarking::StandardBenchmarker;
+    use crate::conformance::count_comparisons;
+    use crate::generator::{RandomGenerator, SeededGenerator};
+
+    crate::sorter_conformance_tests!(MergeInsertionSort);
+
+    /// Worst case comparisons of Ford–Johnson algorithm for 0 to 22 items.
+    const WORST_CASE: [usize; 23] = [
+        0, 0, 1, 3, 5, 7, 10, 13, 16, 19, 22, 26, 30, 34, 38, 42, 46, 50, 54, 58, 62, 66, 71,
+    ];
+
+    fn permutations(items: &mut Vec<u32>, k: usize, check: &mut impl FnMut(&[u32])) {
+        if k == items.len() {
+            check(items);
+            return;
+        }
+
+        for i in k..items.len() {
+            items.swap(k, i);
+            permutations(items, k + 1, check);
+            items.swap(k, i);
+        }
+    }
+
+    #[test]
+    fn group_bounds_are_jacobsthal_numbers() {
+        assert_eq!(
+            [1, 3, 5, 11, 21, 43, 85],
+            [1, 2, 3, 4, 5, 6, 7].map(group_bound)
+        );
+    }
+
+    #[test]
+    fn worst_case_matches_known_counts_for_all_permutations() {
+        for (n, worst_case) in WORST_CASE.into_iter().enumerate().take(9) {
+            let mut worst = 0;
+
+            permutations(&mut (0..n as u32).collect(), 0, &mut |permutation| {
+                worst = worst.max(count_comparisons::<MergeInsertionSort>(permutation));
+            });
+
+            assert_eq!(worst_case, worst, "{} items", n);
+            assert_eq!(comparison_lower_bound(n), worst, "{} items", n);
+        }
+    }
+
+    #[test]
+    fn comparisons_stay_below_known_counts() {
+        for (n, worst_case) in WORST_CASE.into_iter().enumerate() {
+            for seed in 0..100 {
+                let input = RandomGenerator::generate_seeded(n, seed);
+                let mut benchmarker = StandardBenchmarker::default();
+
+                MergeInsertionSort::sort_with_benchmark(&mut input.clone(), &mut benchmarker);
+
+                let comparisons = count_comparisons::<MergeInsertionSort>(&input);
+
+                assert!(comparisons <= worst_case, "{} items", n);
+                assert_eq!(benchmarker.get_stats().comparisons, comparisons);
+            }
+        }
+    }
+
+    #[test]
+    fn comparisons_stay_close_to_lower_bound() {
+        for n in [100usize, 1000] {
+            let input = RandomGenerator::generate_seeded(n, 1);
+            let lower_bound = comparison_lower_bound(n);
+
+            let comparisons = count_comparisons::<MergeInsertionSort>(&input);
+
+            assert!(comparisons >= lower_bound);
+            assert!(comparisons <= lower_bound + lower_bound / 50, "{} items", n);
+        }
+    }
+}
@@ -1,6 +1,9 @@
-//! Merge sort algorithm implementation.
+//! Merge sort algorithm implementations.
+
+use std::mem;
 
 use crate::benchmarking::Benchmark;
+use crate::merging;
 use crate::prelude::*;
 
 /// Struct implementing `Sorter` + `BenchmarkingSorter` using merge sort
@@ -33,22 +36,22 @@ impl MergeSort {
         let mut first_slice_index = 0;
         let mut second_slice_index = 0;
 
-        for i in 0..slice.len() {
+        for item in slice.iter_mut() {
             if first_slice_index < first_slice.len() {
                 if second_slice_index < second_slice.len() {
                     if first_slice[first_slice_index] <= second_slice[second_slice_index] {
-                        slice[i] = first_slice[first_slice_index];
+                        *item = first_slice[first_slice_index];
                         first_slice_index += 1;
                     } else {
-                        slice[i] = second_slice[second_slice_index];
+                        *item = second_slice[second_slice_index];
                         second_slice_index += 1;
                     }
                 } else {
-                    slice[i] = first_slice[first_slice_index];
+                    *item = first_slice[first_slice_index];
                     first_slice_index += 1;
                 }
             } else {
-                slice[i] = second_slice[second_slice_index];
+                *item = second_slice[second_slice_index];
                 second_slice_index += 1;
             }
         }
@@ -76,31 +79,42 @@ impl MergeSort {
         let first_slice = slice[..slice_mid].to_vec();
         let second_slice = slice[slice_mid..].to_vec();
 
+        benchmark.add_allocation(mem::size_of_val(&first_slice[..]));
+        benchmark.add_allocation(mem::size_of_val(&second_slice[..]));
+
+        for _ in 0..slice.len() {
+            benchmark.add_move();
+        }
+
+        benchmark.add_writes(slice.len());
+
         let mut first_slice_index = 0;
         let mut second_slice_index = 0;
 
-        for i in 0..slice.len() {
+        for item in slice.iter_mut() {
             if first_slice_index < first_slice.len() {
                 if second_slice_index < second_slice.len() {
                     benchmark.add_cmp();
 
                     if first_slice[first_slice_index] <= second_slice[second_slice_index] {
-                        slice[i] = first_slice[first_slice_index];
+                        *item = first_slice[first_slice_index];
                         first_slice_index += 1;
                     } else {
-                        slice[i] = second_slice[second_slice_index];
+                        *item = second_slice[second_slice_index];
                         second_slice_index += 1;
                     }
                 } else {
-                    slice[i] = first_slice[first_slice_index];
+                    *item = first_slice[first_slice_index];
                     first_slice_index += 1;
                 }
             } else {
-                slice[i] = second_slice[second_slice_index];
+                *item = second_slice[second_slice_index];
                 second_slice_index += 1;
             }
 
             benchmark.add_swap();
+            benchmark.add_move();
+            benchmark.add_writes(1);
         }
     }
 }
@@ -151,28 +165,789 @@ impl BenchmarkingSorter for MergeSort {
     }
 }
 
+impl StableSorter for MergeSort {}
+
+/// Merges sorted `slice[..mid]` and `slice[mid..]` copying only the left part
+/// into `buffer`, which has to hold at least `mid` items.
+fn merge_with_buffer<T: Ord + Copy>(slice: &mut [T], mid: usize, buffer: &mut [T]) {
+    let buffer = &mut buffer[..mid];
+    buffer.copy_from_slice(&slice[..mid]);
+
+    let mut left = 0;
+    let mut right = mid;
+    let mut dest = 0;
+
+    while left < mid && right < slice.len() {
+        if buffer[left] <= slice[right] {
+            slice[dest] = buffer[left];
+            left += 1;
+        } else {
+            slice[dest] = slice[right];
+            right += 1;
+        }
+
+        dest += 1;
+    }
+
+    // items left in right part are already in place
+    slice[dest..dest + mid - left].copy_from_slice(&buffer[left..]);
+}
+
+fn merge_with_buffer_and_benchmark<T: Ord + Copy>(
+    slice: &mut [T],
+    mid: usize,
+    buffer: &mut [T],
+    benchmark: &mut impl Benchmark,
+) {
+    let buffer = &mut buffer[..mid];
+    buffer.copy_from_slice(&slice[..mid]);
+
+    for _ in 0..mid {
+        benchmark.add_move();
+    }
+
+    benchmark.add_writes(mid);
+
+    let mut left = 0;
+    let mut right = mid;
+    let mut dest = 0;
+
+    while left < mid && right < slice.len() {
+        benchmark.add_cmp();
+
+        if buffer[left] <= slice[right] {
+            slice[dest] = buffer[left];
+            left += 1;
+        } else {
+            slice[dest] = slice[right];
+            right += 1;
+        }
+
+        dest += 1;
+
+        benchmark.add_swap();
+        benchmark.add_move();
+        benchmark.add_writes(1);
+    }
+
+    while left < mid {
+        slice[dest] = buffer[left];
+        left += 1;
+        dest += 1;
+
+        benchmark.add_swap();
+        benchmark.add_move();
+        benchmark.add_writes(1);
+    }
+}
+
+/// Merges sorted `left` and `right` into `dest` of their combined length.
+/// Returns number of inversions between both parts, i.e. pairs of items
+/// from `left` bigger than items from `right`.
+pub(crate) fn merge_into<T: Ord + Copy>(left: &[T], right: &[T], dest: &mut [T]) -> usize {
+    let mut left_index = 0;
+    let mut right_index = 0;
+    let mut inversions = 0;
+
+    for item in dest.iter_mut() {
+        if right_index == right.len()
+            || (left_index < left.len() && left[left_index] <= right[right_index])
+        {
+            *item = left[left_index];
+            left_index += 1;
+        } else {
+            *item = right[right_index];
+            right_index += 1;
+            inversions += left.len() - left_index;
+        }
+    }
+
+    inversions
+}
+
+pub(crate) fn merge_into_with_benchmark<T: Ord + Copy>(
+    left: &[T],
+    right: &[T],
+    dest: &mut [T],
+    benchmark: &mut impl Benchmark,
+) {
+    let mut left_index = 0;
+    let mut right_index = 0;
+
+    for item in dest.iter_mut() {
+        if left_index < left.len() && right_index < right.len() {
+            benchmark.add_cmp();
+        }
+
+        if right_index == right.len()
+            || (left_index < left.len() && left[left_index] <= right[right_index])
+        {
+            *item = left[left_index];
+            left_index += 1;
+        } else {
+            *item = right[right_index];
+            right_index += 1;
+        }
+
+        benchmark.add_swap();
+        benchmark.add_move();
+        benchmark.add_writes(1);
+    }
+}
+
+/// Struct implementing `Sorter` + `BenchmarkingSorter` using iterative
+/// bottom-up merge sort to sort items. Runs of doubling width are merged
+/// through one scratch buffer allocated up front.
+pub struct BottomUpMergeSort;
+
+impl BottomUpMergeSort {
+    /// Length of longest left run merged, i.e. biggest power of two smaller
+    /// than `len`.
+    fn buffer_len(len: usize) -> usize {
+        1 << (usize::BITS - 1 - (len - 1).leading_zeros())
+    }
+
+    fn merge_sort<T: Ord + Copy>(slice: &mut [T]) {
+        let len = slice.len();
+
+        if len <= 1 {
+            return;
+        }
+
+        let mut buffer = vec![slice[0]; BottomUpMergeSort::buffer_len(len)];
+        let mut width = 1;
+
+        while width < len {
+            for start in (0..len - width).step_by(2 * width) {
+                let end = (start + 2 * width).min(len);
+
+                merge_with_buffer(&mut slice[start..end], width, &mut buffer);
+            }
+
+            width *= 2;
+        }
+    }
+
+    fn merge_sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        let len = slice.len();
+
+        if len <= 1 {
+            return;
+        }
+
+        let mut buffer = vec![slice[0]; BottomUpMergeSort::buffer_len(len)];
+
+        benchmark.add_allocation(mem::size_of_val(&buffer[..]));
+
+        let mut width = 1;
+
+        while width < len {
+            benchmark.add_pass();
+
+            for start in (0..len - width).step_by(2 * width) {
+                let end = (start + 2 * width).min(len);
+
+                merge_with_buffer_and_benchmark(
+                    &mut slice[start..end],
+                    width,
+                    &mut buffer,
+                    benchmark,
+                );
+            }
+
+            width *= 2;
+        }
+    }
+}
+
+impl Sorter for BottomUpMergeSort {
+    /// Bottom-up merge sort implementation working on types implementing
+    /// `Ord` + `Copy` so it's mostly usefull for primitive types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    ///
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// BottomUpMergeSort::sort(&mut slice);
+    ///
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// ```
+    fn sort<T: Ord + Copy>(slice: &mut [T]) {
+        BottomUpMergeSort::merge_sort(slice);
+    }
+}
+
+impl BenchmarkingSorter for BottomUpMergeSort {
+    /// Bottom-up merge sort implementation with additional benchmarking
+    /// capabilities. Every level of merges is counted as a pass.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::StandardBenchmarker;
+    ///
+    /// let mut benchmarker = StandardBenchmarker::default();
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// BottomUpMergeSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!(3, stats.passes);
+    /// assert_eq!(1, stats.allocations);
+    /// ```
+    fn sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        benchmark.start_timer();
+
+        BottomUpMergeSort::merge_sort_with_benchmark(slice, benchmark);
+
+        benchmark.stop_timer();
+    }
+}
+
+impl StableSorter for BottomUpMergeSort {}
+
+/// Struct implementing `Sorter` + `BenchmarkingSorter` using natural merge
+/// sort to sort items. Existing ascending runs are found first (strictly
+/// descending ones get reversed) and adjacent runs are merged until one is
+/// left, so sorted input takes linear time and no scratch buffer.
+pub struct NaturalMergeSort;
+
+impl NaturalMergeSort {
+    /// Returns run boundaries, starting with 0 and ending with `slice.len()`.
+    fn find_runs<T: Ord + Copy>(slice: &mut [T]) -> Vec<usize> {
+        let len = slice.len();
+        let mut bounds = vec![0];
+        let mut start = 0;
+
+        while start < len {
+            let mut end = start + 1;
+
+            if end < len && slice[end] < slice[end - 1] {
+                end += 1;
+
+                while end < len && slice[end] < slice[end - 1] {
+                    end += 1;
+                }
+
+                slice[start..end].reverse();
+            } else {
+                if end < len {
+                    end += 1;
+                }
+
+                while end < len && slice[end] >= slice[end - 1] {
+                    end += 1;
+                }
+            }
+
+            bounds.push(end);
+            start = end;
+        }
+
+        bounds
+    }
+
+    fn find_runs_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        benchmark: &mut impl Benchmark,
+    ) -> Vec<usize> {
+        let len = slice.len();
+        let mut bounds = vec![0];
+        let mut start = 0;
+
+        benchmark.add_pass();
+
+        while start < len {
+            let mut end = start + 1;
+
+            if end < len
+                && NaturalMergeSort::less_with_benchmark(slice[end], slice[end - 1], benchmark)
+            {
+                end += 1;
+
+                while end < len
+                    && NaturalMergeSort::less_with_benchmark(slice[end], slice[end - 1], benchmark)
+                {
+                    end += 1;
+                }
+
+                for _ in 0..(end - start) / 2 {
+                    benchmark.add_swap();
+                    benchmark.add_writes(2);
+                }
+
+                slice[start..end].reverse();
+            } else {
+                if end < len {
+                    end += 1;
+                }
+
+                while end < len
+                    && !NaturalMergeSort::less_with_benchmark(slice[end], slice[end - 1], benchmark)
+                {
+                    end += 1;
+                }
+            }
+
+            bounds.push(end);
+            start = end;
+        }
+
+        benchmark.add_allocation(mem::size_of_val(&bounds[..]));
+
+        bounds
+    }
+
+    fn merge_sort<T: Ord + Copy>(slice: &mut [T]) {
+        let mut bounds = NaturalMergeSort::find_runs(slice);
+
+        if bounds.len() <= 2 {
+            return;
+        }
+
+        let mut buffer = vec![slice[0]; slice.len()];
+
+        while bounds.len() > 2 {
+            let mut merged = bounds.iter().step_by(2).copied().collect::<Vec<_>>();
+
+            for runs in bounds.windows(3).step_by(2) {
+                merge_with_buffer(&mut slice[runs[0]..runs[2]], runs[1] - runs[0], &mut buffer);
+            }
+
+            if merged.last() != Some(&slice.len()) {
+                merged.push(slice.len());
+            }
+
+            bounds = merged;
+        }
+    }
+
+    fn merge_sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        let mut bounds = NaturalMergeSort::find_runs_with_benchmark(slice, benchmark);
+
+        if bounds.len() <= 2 {
+            return;
+        }
+
+        let mut buffer = vec![slice[0]; slice.len()];
+
+        benchmark.add_allocation(mem::size_of_val(&buffer[..]));
+
+        while bounds.len() > 2 {
+            benchmark.add_pass();
+
+            let mut merged = bounds.iter().step_by(2).copied().collect::<Vec<_>>();
+
+            benchmark.add_allocation(mem::size_of_val(&merged[..]));
+
+            for runs in bounds.windows(3).step_by(2) {
+                merge_with_buffer_and_benchmark(
+                    &mut slice[runs[0]..runs[2]],
+                    runs[1] - runs[0],
+                    &mut buffer,
+                    benchmark,
+                );
+            }
+
+            if merged.last() != Some(&slice.len()) {
+                merged.push(slice.len());
+            }
+
+            bounds = merged;
+        }
+    }
+
+    fn less_with_benchmark<T: Ord + Copy>(a: T, b: T, benchmark: &mut impl Benchmark) -> bool {
+        benchmark.add_cmp();
+
+        a < b
+    }
+}
+
+impl Sorter for NaturalMergeSort {
+    /// Natural merge sort implementation working on types implementing
+    /// `Ord` + `Copy` so it's mostly usefull for primitive types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    ///
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// NaturalMergeSort::sort(&mut slice);
+    ///
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// ```
+    fn sort<T: Ord + Copy>(slice: &mut [T]) {
+        NaturalMergeSort::merge_sort(slice);
+    }
+}
+
+impl BenchmarkingSorter for NaturalMergeSort {
+    /// Natural merge sort implementation with additional benchmarking
+    /// capabilities. Finding runs and every level of merges are counted
+    /// as passes.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::StandardBenchmarker;
+    ///
+    /// let mut benchmarker = StandardBenchmarker::default();
+    /// let mut slice = [1, 2, 3, 5, 8, 9];
+    ///
+    /// NaturalMergeSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!(5, stats.comparisons);
+    /// assert_eq!(0, stats.moves);
+    /// assert_eq!(1, stats.passes);
+    /// ```
+    fn sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        benchmark.start_timer();
+
+        NaturalMergeSort::merge_sort_with_benchmark(slice, benchmark);
+
+        benchmark.stop_timer();
+    }
+}
+
+impl StableSorter for NaturalMergeSort {}
+
+/// Struct implementing `Sorter` + `BenchmarkingSorter` using ping-pong merge
+/// sort to sort items. One scratch buffer of slice length is allocated up
+/// front and every level of bottom-up merges writes from slice to buffer or
+/// back, so items are never copied just to be merged.
+pub struct PingPongMergeSort;
+
+impl PingPongMergeSort {
+    fn merge_pass<T: Ord + Copy>(source: &[T], dest: &mut [T], width: usize) {
+        let len = source.len();
+
+        for start in (0..len).step_by(2 * width) {
+            let mid = (start + width).min(len);
+            let end = (start + 2 * width).min(len);
+
+            merge_into(
+                &source[start..mid],
+                &source[mid..end],
+                &mut dest[start..end],
+            );
+        }
+    }
+
+    fn merge_pass_with_benchmark<T: Ord + Copy>(
+        source: &[T],
+        dest: &mut [T],
+        width: usize,
+        benchmark: &mut impl Benchmark,
+    ) {
+        let len = source.len();
+
+        for start in (0..len).step_by(2 * width) {
+            let mid = (start + width).min(len);
+            let end = (start + 2 * width).min(len);
+
+            merge_into_with_benchmark(
+                &source[start..mid],
+                &source[mid..end],
+                &mut dest[start..end],
+                benchmark,
+            );
+        }
+    }
+
+    fn merge_sort<T: Ord + Copy>(slice: &mut [T]) {
+        let len = slice.len();
+
+        if len <= 1 {
+            return;
+        }
+
+        let mut buffer = vec![slice[0]; len];
+        let mut in_buffer = false;
+        let mut width = 1;
+
+        while width < len {
+            if in_buffer {
+                PingPongMergeSort::merge_pass(&buffer, slice, width);
+            } else {
+                PingPongMergeSort::merge_pass(slice, &mut buffer, width);
+            }
+
+            in_buffer = !in_buffer;
+            width *= 2;
+        }
+
+        if in_buffer {
+            slice.copy_from_slice(&buffer);
+        }
+    }
+
+    fn merge_sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        let len = slice.len();
+
+        if len <= 1 {
+            return;
+        }
+
+        let mut buffer = vec![slice[0]; len];
+
+        benchmark.add_allocation(mem::size_of_val(&buffer[..]));
+
+        let mut in_buffer = false;
+        let mut width = 1;
+
+        while width < len {
+            benchmark.add_pass();
+
+            if in_buffer {
+                PingPongMergeSort::merge_pass_with_benchmark(&buffer, slice, width, benchmark);
+            } else {
+                PingPongMergeSort::merge_pass_with_benchmark(slice, &mut buffer, width, benchmark);
+            }
+
+            in_buffer = !in_buffer;
+            width *= 2;
+        }
+
+        if in_buffer {
+            slice.copy_from_slice(&buffer);
+
+            for _ in 0..len {
+                benchmark.add_move();
+            }
+
+            benchmark.add_writes(len);
+        }
+    }
+}
+
+impl Sorter for PingPongMergeSort {
+    /// Ping-pong merge sort implementation working on types implementing
+    /// `Ord` + `Copy` so it's mostly usefull for primitive types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    ///
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// PingPongMergeSort::sort(&mut slice);
+    ///
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// ```
+    fn sort<T: Ord + Copy>(slice: &mut [T]) {
+        PingPongMergeSort::merge_sort(slice);
+    }
+}
+
+impl BenchmarkingSorter for PingPongMergeSort {
+    /// Ping-pong merge sort implementation with additional benchmarking
+    /// capabilities. Every level of merges is counted as a pass.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::StandardBenchmarker;
+    ///
+    /// let mut benchmarker = StandardBenchmarker::default();
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// PingPongMergeSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!(3, stats.passes);
+    /// assert_eq!(3 * 6 + 6, stats.moves);
+    /// assert_eq!(1, stats.allocations);
+    /// ```
+    fn sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        benchmark.start_timer();
+
+        PingPongMergeSort::merge_sort_with_benchmark(slice, benchmark);
+
+        benchmark.stop_timer();
+    }
+}
+
+impl StableSorter for PingPongMergeSort {}
+
+/// Struct implementing `Sorter` + `BenchmarkingSorter` using top-down merge
+/// sort with in-place SymMerge merging, so it needs no scratch buffer at
+/// the cost of `O(n log^2 n)` swaps.
+pub struct InPlaceMergeSort;
+
+impl InPlaceMergeSort {
+    fn merge_sort<T: Ord + Copy>(slice: &mut [T]) {
+        if slice.len() <= 1 {
+            return;
+        }
+
+        let slice_mid = slice.len() / 2;
+
+        InPlaceMergeSort::merge_sort(&mut slice[..slice_mid]);
+        InPlaceMergeSort::merge_sort(&mut slice[slice_mid..]);
+        merging::sym_merge(slice, slice_mid);
+    }
+
+    fn merge_sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        if slice.len() <= 1 {
+            return;
+        }
+
+        let slice_mid = slice.len() / 2;
+
+        InPlaceMergeSort::merge_sort_with_benchmark(&mut slice[..slice_mid], benchmark);
+        InPlaceMergeSort::merge_sort_with_benchmark(&mut slice[slice_mid..], benchmark);
+        merging::sym_merge_with_benchmark(slice, slice_mid, benchmark);
+    }
+}
+
+impl Sorter for InPlaceMergeSort {
+    /// In-place merge sort implementation working on types implementing
+    /// `Ord` + `Copy` so it's mostly usefull for primitive types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    ///
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// InPlaceMergeSort::sort(&mut slice);
+    ///
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// ```
+    fn sort<T: Ord + Copy>(slice: &mut [T]) {
+        InPlaceMergeSort::merge_sort(slice);
+    }
+}
+
+impl BenchmarkingSorter for InPlaceMergeSort {
+    /// In-place merge sort implementation with additional benchmarking
+    /// capabilities.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::StandardBenchmarker;
+    ///
+    /// let mut benchmarker = StandardBenchmarker::default();
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// InPlaceMergeSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!(0, stats.allocations);
+    /// assert_eq!(0, stats.moves);
+    /// ```
+    fn sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        benchmark.start_timer();
+
+        InPlaceMergeSort::merge_sort_with_benchmark(slice, benchmark);
+
+        benchmark.stop_timer();
+    }
+}
+
+impl StableSorter for InPlaceMergeSort {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tests::*;
 
-    #[test]
-    fn merge_sort_basic_sorting_test() {
-        basic_sorting_test::<MergeSort>();
+    use crate::benchmarking::{StandardBenchmarker, Stats};
+    use crate::generator::*;
+
+    crate::sorter_conformance_tests!(MergeSort, stable);
+
+    mod bottom_up {
+        use super::*;
+
+        crate::sorter_conformance_tests!(BottomUpMergeSort, stable);
+    }
+
+    mod natural {
+        use super::*;
+
+        crate::sorter_conformance_tests!(NaturalMergeSort, stable);
+    }
+
+    mod ping_pong {
+        use super::*;
+
+        crate::sorter_conformance_tests!(PingPongMergeSort, stable);
+    }
+
+    mod in_place {
+        use super::*;
+
+        crate::sorter_conformance_tests!(InPlaceMergeSort, stable);
+    }
+
+    fn stats<S: BenchmarkingSorter>(slice: &mut [u32]) -> Stats {
+        let mut benchmarker = StandardBenchmarker::default();
+
+        S::sort_with_benchmark(slice, &mut benchmarker);
+
+        benchmarker.get_stats()
     }
 
     #[test]
-    fn merge_sort_empty_sorting_test() {
-        empty_sorting_test::<MergeSort>();
+    fn merge_sort_variants_allocate_once() {
+        let input = RandomGenerator::generate_seeded(1000, 0);
+
+        assert_eq!(2 * 999, stats::<MergeSort>(&mut input.clone()).allocations);
+        assert_eq!(
+            1,
+            stats::<BottomUpMergeSort>(&mut input.clone()).allocations
+        );
+        assert_eq!(
+            1,
+            stats::<PingPongMergeSort>(&mut input.clone()).allocations
+        );
+
+        let bottom_up = stats::<BottomUpMergeSort>(&mut input.clone());
+
+        assert_eq!(512 * 4, bottom_up.allocated_bytes);
+        assert_eq!(10, bottom_up.passes);
     }
 
     #[test]
-    fn merge_sort_sorted_sorting_test() {
-        sorted_sorting_test::<MergeSort>();
+    fn ping_pong_merge_sort_moves_every_item_once_per_pass() {
+        for n in [2, 3, 100, 1024, 1025] {
+            let stats = stats::<PingPongMergeSort>(&mut RandomGenerator::generate_seeded(n, 0));
+            let copy_back = if stats.passes % 2 == 1 { n } else { 0 };
+
+            assert_eq!(n * stats.passes + copy_back, stats.moves);
+        }
     }
 
     #[test]
-    fn merge_sort_sorted_backwards_sorting_test() {
-        sorted_backwards_sorting_test::<MergeSort>();
+    fn natural_merge_sort_uses_existing_runs() {
+        let n = 10000;
+
+        let sorted = stats::<NaturalMergeSort>(&mut (0..n as u32).collect::<Vec<_>>());
+
+        assert_eq!(n - 1, sorted.comparisons);
+        assert_eq!(0, sorted.moves);
+        assert_eq!(1, sorted.allocations);
+
+        let reversed = stats::<NaturalMergeSort>(&mut (0..n as u32).rev().collect::<Vec<_>>());
+
+        assert_eq!(n - 1, reversed.comparisons);
+        assert_eq!(n / 2, reversed.swaps);
+        assert_eq!(0, reversed.moves);
+
+        let mut two_runs = (0..n as u32 / 2).chain(0..n as u32 / 2).collect::<Vec<_>>();
+        let two_runs = stats::<NaturalMergeSort>(&mut two_runs);
+
+        assert_eq!(2, two_runs.passes);
+        assert!(two_runs.comparisons < 2 * n);
     }
 }
@@ -0,0 +1,629 @@
+//! Stable merging algorithms working in place or with limited scratch space.
+//!
+//! Every function merges sorted `slice[..mid]` and `slice[mid..]`, keeping
+//! equal items in their original order. Panics if `mid` is bigger than slice
+//! length.
+
+use crate::benchmarking::Benchmark;
+use crate::rotation;
+
+/// Symmetric merge (SymMerge) of Kim and Kutzner. Splits both parts around
+/// the middle of the whole slice with one binary search, swaps the middle
+/// blocks with a rotation and merges both halves recursively. Uses no extra
+/// memory besides recursion stack.
+///
+/// Examples:
+/// ```
+/// use algorithms::merging;
+///
+/// let mut slice = [1, 4, 7, 9, 2, 3, 8];
+///
+/// merging::sym_merge(&mut slice, 4);
+///
+/// assert_eq!([1, 2, 3, 4, 7, 8, 9], slice);
+/// ```
+pub fn sym_merge<T: Ord + Copy>(slice: &mut [T], mid: usize) {
+    let len = slice.len();
+
+    assert!(mid <= len, "mid out of bounds");
+
+    if mid == 0 || mid == len {
+        return;
+    }
+
+    if mid == 1 {
+        let value = slice[0];
+        let position = 1 + slice[1..].partition_point(|&x| x < value);
+
+        rotation::block_swap_rotate(&mut slice[..position], 1);
+        return;
+    }
+
+    if mid == len - 1 {
+        let value = slice[mid];
+        let position = slice[..mid].partition_point(|&x| x <= value);
+
+        rotation::block_swap_rotate(&mut slice[position..], len - 1 - position);
+        return;
+    }
+
+    let half = len / 2;
+    let n = half + mid;
+
+    let (mut start, mut r) = if mid > half {
+        (n - len, half)
+    } else {
+        (0, mid)
+    };
+    let p = n - 1;
+
+    while start < r {
+        let c = (start + r) / 2;
+
+        if slice[p - c] >= slice[c] {
+            start = c + 1;
+        } else {
+            r = c;
+        }
+    }
+
+    let end = n - start;
+
+    if start < mid && mid < end {
+        rotation::block_swap_rotate(&mut slice[start..end], mid - start);
+    }
+
+    if 0 < start && start < half {
+        sym_merge(&mut slice[..half], start);
+    }
+
+    if half < end && end < len {
+        sym_merge(&mut slice[half..], end - half);
+    }
+}
+
+/// SymMerge counting comparisons and swaps made by rotations.
+pub fn sym_merge_with_benchmark<T: Ord + Copy>(
+    slice: &mut [T],
+    mid: usize,
+    benchmark: &mut impl Benchmark,
+) {
+    let len = slice.len();
+
+    assert!(mid <= len, "mid out of bounds");
+
+    if mid == 0 || mid == len {
+        return;
+    }
+
+    if mid == 1 {
+        let value = slice[0];
+        let position = 1 + slice[1..].partition_point(|&x| {
+            benchmark.add_cmp();
+            x < value
+        });
+
+        rotation::block_swap_rotate_with_benchmark(&mut slice[..position], 1, benchmark);
+        return;
+    }
+
+    if mid == len - 1 {
+        let value = slice[mid];
+        let position = slice[..mid].partition_point(|&x| {
+            benchmark.add_cmp();
+            x <= value
+        });
+
+        rotation::block_swap_rotate_with_benchmark(
+            &mut slice[position..],
+            len - 1 - position,
+            benchmark,
+        );
+        return;
+    }
+
+    let half = len / 2;
+    let n = half + mid;
+
+    let (mut start, mut r) = if mid > half {
+        (n - len, half)
+    } else {
+        (0, mid)
+    };
+    let p = n - 1;
+
+    while start < r {
+        let c = (start + r) / 2;
+
+        benchmark.add_cmp();
+
+        if slice[p - c] >= slice[c] {
+            start = c + 1;
+        } else {
+            r = c;
+        }
+    }
+
+    let end = n - start;
+
+    if start < mid && mid < end {
+        rotation::block_swap_rotate_with_benchmark(&mut slice[start..end], mid - start, benchmark);
+    }
+
+    if 0 < start && start < half {
+        sym_merge_with_benchmark(&mut slice[..half], start, benchmark);
+    }
+
+    if half < end && end < len {
+        sym_merge_with_benchmark(&mut slice[half..], end - half, benchmark);
+    }
+}
+
+/// Hwang-Lin binary merge done in place with rotations. The last item of
+/// one part is searched for in the other part by jumping over blocks of
+/// `2^floor(log2(n / m))` items and finishing with binary search, so merging
+/// short part into long one takes close to optimal number of comparisons.
+/// Blocks found to be in place are skipped and the rest is rotated, which
+/// can take quadratic number of swaps for parts of similar length.
+///
+/// Examples:
+/// ```
+/// use algorithms::merging;
+///
+/// let mut slice = [5, 1, 2, 3, 4, 6, 7, 8, 9];
+///
+/// merging::hwang_lin_merge(&mut slice, 1);
+///
+/// assert_eq!([1, 2, 3, 4, 5, 6, 7, 8, 9], slice);
+/// ```
+pub fn hwang_lin_merge<T: Ord + Copy>(slice: &mut [T], mid: usize) {
+    assert!(mid <= slice.len(), "mid out of bounds");
+
+    let mut mid = mid;
+    let mut end = slice.len();
+
+    while mid > 0 && mid < end {
+        let value = slice[mid - 1];
+        let block = block_len(end - mid, mid);
+
+        end = mid + gallop_back(&slice[mid..end], block, |x| x >= value);
+
+        if end == mid {
+            break;
+        }
+
+        let value = slice[end - 1];
+        let block = block_len(mid, end - mid);
+        // last item of left part is known to be bigger than value
+        let start = gallop_back(&slice[..mid - 1], block, |x| x > value);
+
+        rotation::block_swap_rotate(&mut slice[start..end], mid - start);
+
+        end = start + (end - mid);
+        mid = start;
+    }
+}
+
+/// Hwang-Lin merge counting comparisons and swaps made by rotations.
+pub fn hwang_lin_merge_with_benchmark<T: Ord + Copy>(
+    slice: &mut [T],
+    mid: usize,
+    benchmark: &mut impl Benchmark,
+) {
+    assert!(mid <= slice.len(), "mid out of bounds");
+
+    let mut mid = mid;
+    let mut end = slice.len();
+
+    while mid > 0 && mid < end {
+        let value = slice[mid - 1];
+        let block = block_len(end - mid, mid);
+
+        end = mid
+            + gallop_back(&slice[mid..end], block, |x| {
+                benchmark.add_cmp();
+                x >= value
+            });
+
+        if end == mid {
+            break;
+        }
+
+        let value = slice[end - 1];
+        let block = block_len(mid, end - mid);
+        let start = gallop_back(&slice[..mid - 1], block, |x| {
+            benchmark.add_cmp();
+            x > value
+        });
+
+        rotation::block_swap_rotate_with_benchmark(&mut slice[start..end], mid - start, benchmark);
+
+        end = start + (end - mid);
+        mid = start;
+    }
+}
+
+/// Biggest power of two not bigger than `len / other`, at least 1.
+fn block_len(len: usize, other: usize) -> usize {
+    let ratio = (len / other).max(1);
+
+    1 << (usize::BITS - 1 - ratio.leading_zeros())
+}
+
+/// Returns start of the longest suffix whose items satisfy `in_tail`,
+/// which has to hold for every item after the first one satisfying it.
+/// Jumps back by `block` items and then binary searches the last block.
+fn gallop_back<T: Copy>(slice: &[T], block: usize, mut in_tail: impl FnMut(T) -> bool) -> usize {
+    let mut end = slice.len();
+
+    while end >= block && in_tail(slice[end - block]) {
+        end -= block;
+    }
+
+    let start = end.saturating_sub(block - 1);
+
+    start + slice[start..end].partition_point(|&x| !in_tail(x))
+}
+
+/// Merge using caller provided scratch `buffer`. When the shorter part fits
+/// in it, the part is copied there and merged linearly. Otherwise slice is
+/// split with a rotation, like in SymMerge, until parts fit. With empty
+/// buffer it's a plain rotation merge.
+///
+/// Examples:
+/// ```
+/// use algorithms::merging;
+///
+/// let mut slice = [1, 4, 7, 9, 2, 3, 8];
+/// let mut buffer = [0; 2];
+///
+/// merging::buffered_merge(&mut slice, 4, &mut buffer);
+///
+/// assert_eq!([1, 2, 3, 4, 7, 8, 9], slice);
+/// ```
+pub fn buffered_merge<T: Ord + Copy>(slice: &mut [T], mid: usize, buffer: &mut [T]) {
+    let len = slice.len();
+
+    assert!(mid <= len, "mid out of bounds");
+
+    if mid == 0 || mid == len {
+        return;
+    }
+
+    let right_len = len - mid;
+
+    if mid <= right_len && mid <= buffer.len() {
+        merge_low(slice, mid, buffer);
+        return;
+    }
+
+    if right_len < mid && right_len <= buffer.len() {
+        merge_high(slice, mid, buffer);
+        return;
+    }
+
+    if len == 2 {
+        if slice[1] < slice[0] {
+            slice.swap(0, 1);
+        }
+
+        return;
+    }
+
+    let (left_cut, right_cut) = if mid >= right_len {
+        let left_cut = mid / 2;
+        let value = slice[left_cut];
+
+        (left_cut, mid + slice[mid..].partition_point(|&x| x < value))
+    } else {
+        let right_cut = mid + right_len / 2;
+        let value = slice[right_cut];
+
+        (slice[..mid].partition_point(|&x| x <= value), right_cut)
+    };
+
+    rotation::block_swap_rotate(&mut slice[left_cut..right_cut], mid - left_cut);
+
+    let new_mid = left_cut + (right_cut - mid);
+
+    buffered_merge(&mut slice[..new_mid], left_cut, buffer);
+    buffered_merge(&mut slice[new_mid..], mid - left_cut, buffer);
+}
+
+/// Buffered merge counting comparisons, swaps made by rotations and moves
+/// into and out of buffer.
+pub fn buffered_merge_with_benchmark<T: Ord + Copy>(
+    slice: &mut [T],
+    mid: usize,
+    buffer: &mut [T],
+    benchmark: &mut impl Benchmark,
+) {
+    let len = slice.len();
+
+    assert!(mid <= len, "mid out of bounds");
+
+    if mid == 0 || mid == len {
+        return;
+    }
+
+    let right_len = len - mid;
+
+    if mid <= right_len && mid <= buffer.len() {
+        merge_low_with_benchmark(slice, mid, buffer, benchmark);
+        return;
+    }
+
+    if right_len < mid && right_len <= buffer.len() {
+        merge_high_with_benchmark(slice, mid, buffer, benchmark);
+        return;
+    }
+
+    if len == 2 {
+        benchmark.add_cmp();
+
+        if slice[1] < slice[0] {
+            benchmark.add_swap();
+            benchmark.add_writes(2);
+            slice.swap(0, 1);
+        }
+
+        return;
+    }
+
+    let (left_cut, right_cut) = if mid >= right_len {
+        let left_cut = mid / 2;
+        let value = slice[left_cut];
+        let position = slice[mid..].partition_point(|&x| {
+            benchmark.add_cmp();
+            x < value
+        });
+
+        (left_cut, mid + position)
+    } else {
+        let right_cut = mid + right_len / 2;
+        let value = slice[right_cut];
+        let position = slice[..mid].partition_point(|&x| {
+            benchmark.add_cmp();
+            x <= value
+        });
+
+        (position, right_cut)
+    };
+
+    rotation::block_swap_rotate_with_benchmark(
+        &mut slice[left_cut..right_cut],
+        mid - left_cut,
+        benchmark,
+    );
+
+    let new_mid = left_cut + (right_cut - mid);
+
+    buffered_merge_with_benchmark(&mut slice[..new_mid], left_cut, buffer, benchmark);
+    buffered_merge_with_benchmark(&mut slice[new_mid..], mid - left_cut, buffer, benchmark);
+}
+
+/// Merges front to back with left part copied into buffer.
+fn merge_low<T: Ord + Copy>(slice: &mut [T], mid: usize, buffer: &mut [T]) {
+    let buffer = &mut buffer[..mid];
+    buffer.copy_from_slice(&slice[..mid]);
+
+    let mut left = 0;
+    let mut right = mid;
+    let mut dest = 0;
+
+    while left < mid && right < slice.len() {
+        if buffer[left] <= slice[right] {
+            slice[dest] = buffer[left];
+            left += 1;
+        } else {
+            slice[dest] = slice[right];
+            right += 1;
+        }
+
+        dest += 1;
+    }
+
+    slice[dest..dest + mid - left].copy_from_slice(&buffer[left..]);
+}
+
+fn merge_low_with_benchmark<T: Ord + Copy>(
+    slice: &mut [T],
+    mid: usize,
+    buffer: &mut [T],
+    benchmark: &mut impl Benchmark,
+) {
+    let buffer = &mut buffer[..mid];
+    buffer.copy_from_slice(&slice[..mid]);
+
+    for _ in 0..mid {
+        benchmark.add_move();
+        benchmark.add_writes(1);
+    }
+
+    let mut left = 0;
+    let mut right = mid;
+    let mut dest = 0;
+
+    while left < mid && right < slice.len() {
+        benchmark.add_cmp();
+
+        if buffer[left] <= slice[right] {
+            slice[dest] = buffer[left];
+            left += 1;
+        } else {
+            slice[dest] = slice[right];
+            right += 1;
+        }
+
+        dest += 1;
+
+        benchmark.add_move();
+        benchmark.add_writes(1);
+    }
+
+    slice[dest..dest + mid - left].copy_from_slice(&buffer[left..]);
+
+    for _ in left..mid {
+        benchmark.add_move();
+        benchmark.add_writes(1);
+    }
+}
+
+/// Merges back to front with right part copied into buffer.
+fn merge_high<T: Ord + Copy>(slice: &mut [T], mid: usize, buffer: &mut [T]) {
+    let right_len = slice.len() - mid;
+    let buffer = &mut buffer[..right_len];
+    buffer.copy_from_slice(&slice[mid..]);
+
+    let mut left = mid;
+    let mut right = right_len;
+    let mut dest = slice.len();
+
+    while left > 0 && right > 0 {
+        dest -= 1;
+
+        if buffer[right - 1] < slice[left - 1] {
+            slice[dest] = slice[left - 1];
+            left -= 1;
+        } else {
+            slice[dest] = buffer[right - 1];
+            right -= 1;
+        }
+    }
+
+    slice[..right].copy_from_slice(&buffer[..right]);
+}
+
+fn merge_high_with_benchmark<T: Ord + Copy>(
+    slice: &mut [T],
+    mid: usize,
+    buffer: &mut [T],
+    benchmark: &mut impl Benchmark,
+) {
+    let right_len = slice.len() - mid;
+    let buffer = &mut buffer[..right_len];
+    buffer.copy_from_slice(&slice[mid..]);
+
+    for _ in 0..right_len {
+        benchmark.add_move();
+        benchmark.add_writes(1);
+    }
+
+    let mut left = mid;
+    let mut right = right_len;
+    let mut dest = slice.len();
+
+    while left > 0 && right > 0 {
+        dest -= 1;
+
+        benchmark.add_cmp();
+
+        if buffer[right - 1] < slice[left - 1] {
+            slice[dest] = slice[left - 1];
+            left -= 1;
+        } else {
+            slice[dest] = buffer[right - 1];
+            right -= 1;
+        }
+
+        benchmark.add_move();
+        benchmark.add_writes(1);
+    }
+
+    slice[..right].copy_from_slice(&buffer[..right]);
+
+    for _ in 0..right {
+        benchmark.add_move();
+        benchmark.add_writes(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarking::StandardBenchmarker;
+    use crate::conformance::*;
+
+    /// Checks merge on sorted halves of keyed input split at every `mid`.
+    fn merging_test(mut merge: impl FnMut(&mut [Keyed], usize)) {
+        for (n, keys) in [(0, 1), (1, 1), (2, 2), (10, 3), (33, 5), (100, 1000)] {
+            for mid in 0..=n {
+                let mut slice = keyed_input(n, keys, mid as u64);
+
+                slice[..mid].sort();
+                slice[mid..].sort();
+
+                merge(&mut slice, mid);
+
+                assert_stably_sorted(&slice);
+            }
+        }
+    }
+
+    #[test]
+    fn sym_merge_test() {
+        merging_test(sym_merge);
+        merging_test(|slice, mid| {
+            sym_merge_with_benchmark(slice, mid, &mut StandardBenchmarker::default())
+        });
+    }
+
+    #[test]
+    fn hwang_lin_merge_test() {
+        merging_test(hwang_lin_merge);
+        merging_test(|slice, mid| {
+            hwang_lin_merge_with_benchmark(slice, mid, &mut StandardBenchmarker::default())
+        });
+    }
+
+    #[test]
+    fn buffered_merge_test() {
+        for buffer_len in [0, 1, 5, 100] {
+            let mut buffer = vec![Keyed { key: 0, index: 0 }; buffer_len];
+
+            merging_test(|slice, mid| buffered_merge(slice, mid, &mut buffer));
+            merging_test(|slice, mid| {
+                buffered_merge_with_benchmark(
+                    slice,
+                    mid,
+                    &mut buffer,
+                    &mut StandardBenchmarker::default(),
+                )
+            });
+        }
+    }
+
+    #[test]
+    fn hwang_lin_merge_inserts_with_few_comparisons() {
+        let n = 1 << 12;
+        let mut slice = (0..=n as u32).collect::<Vec<_>>();
+        slice.rotate_right(1);
+
+        let mut benchmarker = StandardBenchmarker::default();
+
+        hwang_lin_merge_with_benchmark(&mut slice, 1, &mut benchmarker);
+
+        assert_eq!((0..=n as u32).collect::<Vec<_>>(), slice);
+        // information theoretic lower bound is ceil(log2(n + 1)) = 13
+        assert!(benchmarker.get_stats().comparisons <= 14);
+    }
+
+    #[test]
+    fn buffered_merge_with_big_buffer_is_linear() {
+        let mut slice = (0..100)
+            .step_by(2)
+            .chain((1..100).step_by(2))
+            .collect::<Vec<_>>();
+        let mut buffer = vec![0; 50];
+        let mut benchmarker = StandardBenchmarker::default();
+
+        buffered_merge_with_benchmark(&mut slice, 50, &mut buffer, &mut benchmarker);
+
+        let stats = benchmarker.get_stats();
+
+        assert_eq!((0..100).collect::<Vec<_>>(), slice);
+        assert_eq!(0, stats.swaps);
+        assert_eq!(149, stats.moves);
+        assert!(stats.comparisons < 100);
+    }
+}
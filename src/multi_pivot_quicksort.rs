@@ -0,0 +1,470 @@
+//! Multi-pivot quick sort algorithm implementation.
+
+use std::mem;
+
+use crate::prelude::*;
+use crate::tuning::Parameter;
+
+/// Struct implementing `Sorter` + `BenchmarkingSorter` using quick sort with
+/// `K` pivots to sort items. Pivots are every `s`-th item of sorted sample
+/// of `s * (K + 1) - 1` items, where `s` is
+/// `Parameter::MultiPivotSampleSpacing`, and every item is classified into one of `K + 1`
+/// partitions by binary search over pivots, so it takes `ceil(log2(K + 1))`
+/// comparisons. Classes are remembered and items are then moved into their
+/// partitions in place, like in American flag sort. When sample contains
+/// duplicates, items equal to pivots get partitions of their own, which
+/// aren't sorted any further.
+///
+/// Besides comparisons and swaps, benchmark counts scanned elements, i.e.
+/// items read while scanning over slice, which approximate cache misses.
+/// `K` has to be between 1 and 1000.
+pub struct MultiPivotQuicksort<const K: usize>;
+
+impl<const K: usize> MultiPivotQuicksort<K> {
+    /// Returns length of sample with pivots `spacing` items apart.
+    fn sample_len(spacing: usize) -> usize {
+        spacing * (K + 1) - 1
+    }
+
+    /// Returns length up to which slices are sorted using insertion sort.
+    /// It's at least twice the length of sample, so that sample is always
+    /// spread over the slice.
+    fn insertion_threshold(spacing: usize) -> usize {
+        let sample_len = MultiPivotQuicksort::<K>::sample_len(spacing);
+
+        (2 * sample_len).max(Parameter::MultiPivotInsertionLen.get())
+    }
+
+    fn multi_pivot_quick_sort<T: Ord + Copy>(slice: &mut [T]) {
+        assert!(
+            (1..=1000).contains(&K),
+            "number of pivots has to be between 1 and 1000"
+        );
+
+        let mut oracle = vec![0; slice.len()];
+        // sample and threshold have to be computed from the same spacing
+        let spacing = Parameter::MultiPivotSampleSpacing.get();
+        let threshold = MultiPivotQuicksort::<K>::insertion_threshold(spacing);
+
+        MultiPivotQuicksort::<K>::quick_sort(slice, &mut oracle, spacing, threshold);
+    }
+
+    fn quick_sort<T: Ord + Copy>(
+        mut slice: &mut [T],
+        mut oracle: &mut [u16],
+        spacing: usize,
+        threshold: usize,
+    ) {
+        loop {
+            if slice.len() <= threshold {
+                InsertionSort::sort(slice);
+                return;
+            }
+
+            let (pivots, equal_buckets) = MultiPivotQuicksort::<K>::choose_pivots(slice, spacing);
+            let bounds = MultiPivotQuicksort::<K>::partition(slice, oracle, &pivots, equal_buckets);
+            let largest = MultiPivotQuicksort::<K>::largest_bucket(&bounds);
+
+            // recursing only into shorter buckets keeps stack depth logarithmic
+            for bucket in (0..=K)
+                .map(|pivot| 2 * pivot)
+                .filter(|&bucket| bucket != largest)
+            {
+                let (start, end) = (bounds[bucket], bounds[bucket + 1]);
+
+                MultiPivotQuicksort::<K>::quick_sort(
+                    &mut slice[start..end],
+                    &mut oracle[start..end],
+                    spacing,
+                    threshold,
+                );
+            }
+
+            let (start, end) = (bounds[largest], bounds[largest + 1]);
+
+            slice = &mut mem::take(&mut slice)[start..end];
+            oracle = &mut mem::take(&mut oracle)[start..end];
+        }
+    }
+
+    /// Returns the biggest of buckets which need further sorting.
+    fn largest_bucket(bounds: &[usize]) -> usize {
+        (0..=K)
+            .map(|pivot| 2 * pivot)
+            .max_by_key(|&bucket| bounds[bucket + 1] - bounds[bucket])
+            .unwrap()
+    }
+
+    fn choose_pivots<T: Ord + Copy>(slice: &mut [T], spacing: usize) -> ([T; K], bool) {
+        let sample_len = MultiPivotQuicksort::<K>::sample_len(spacing);
+        let step = slice.len() / sample_len;
+
+        for i in 0..sample_len {
+            slice.swap(i, i * step);
+        }
+
+        let sample = &mut slice[..sample_len];
+
+        InsertionSort::sort(sample);
+
+        let mut pivots = [sample[0]; K];
+
+        for (i, pivot) in pivots.iter_mut().enumerate() {
+            *pivot = sample[(i + 1) * spacing - 1];
+        }
+
+        let equal_buckets = sample.windows(2).any(|pair| pair[0] == pair[1]);
+
+        (pivots, equal_buckets)
+    }
+
+    fn partition<T: Ord + Copy>(
+        slice: &mut [T],
+        oracle: &mut [u16],
+        pivots: &[T; K],
+        equal_buckets: bool,
+    ) -> Vec<usize> {
+        let mut bounds = vec![0; 2 * K + 2];
+
+        for (&value, bucket) in slice.iter().zip(oracle.iter_mut()) {
+            let pivot = pivots.partition_point(|&pivot| pivot < value);
+
+            *bucket = 2 * pivot as u16;
+
+            if equal_buckets && pivot < K && value == pivots[pivot] {
+                *bucket += 1;
+            }
+
+            bounds[*bucket as usize + 1] += 1;
+        }
+
+        for i in 1..bounds.len() {
+            bounds[i] += bounds[i - 1];
+        }
+
+        let mut next = bounds.clone();
+
+        for bucket in 0..2 * K + 1 {
+            while next[bucket] < bounds[bucket + 1] {
+                let i = next[bucket];
+                let target = oracle[i] as usize;
+
+                if target == bucket {
+                    next[bucket] += 1;
+                } else {
+                    let j = next[target];
+
+                    slice.swap(i, j);
+                    oracle.swap(i, j);
+                    next[target] += 1;
+                }
+            }
+        }
+
+        bounds
+    }
+
+    fn multi_pivot_quick_sort_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        benchmark: &mut impl Benchmark,
+    ) {
+        assert!(
+            (1..=1000).contains(&K),
+            "number of pivots has to be between 1 and 1000"
+        );
+
+        let mut oracle = vec![0; slice.len()];
+
+        // sample and threshold have to be computed from the same spacing
+        let spacing = Parameter::MultiPivotSampleSpacing.get();
+        let threshold = MultiPivotQuicksort::<K>::insertion_threshold(spacing);
+
+        benchmark.add_allocation(mem::size_of_val(&oracle[..]));
+
+        MultiPivotQuicksort::<K>::quick_sort_with_benchmark(
+            slice,
+            &mut oracle,
+            spacing,
+            threshold,
+            benchmark,
+        );
+    }
+
+    fn quick_sort_with_benchmark<T: Ord + Copy>(
+        mut slice: &mut [T],
+        mut oracle: &mut [u16],
+        spacing: usize,
+        threshold: usize,
+        benchmark: &mut impl Benchmark,
+    ) {
+        loop {
+            if slice.len() <= threshold {
+                InsertionSort::insertion_sort_with_benchmark(slice, benchmark);
+                return;
+            }
+
+            let (pivots, equal_buckets) =
+                MultiPivotQuicksort::<K>::choose_pivots_with_benchmark(slice, spacing, benchmark);
+            let bounds = MultiPivotQuicksort::<K>::partition_with_benchmark(
+                slice,
+                oracle,
+                &pivots,
+                equal_buckets,
+                benchmark,
+            );
+            let largest = MultiPivotQuicksort::<K>::largest_bucket(&bounds);
+
+            // recursing only into shorter buckets keeps stack depth logarithmic
+            for bucket in (0..=K)
+                .map(|pivot| 2 * pivot)
+                .filter(|&bucket| bucket != largest)
+            {
+                let (start, end) = (bounds[bucket], bounds[bucket + 1]);
+
+                MultiPivotQuicksort::<K>::quick_sort_with_benchmark(
+                    &mut slice[start..end],
+                    &mut oracle[start..end],
+                    spacing,
+                    threshold,
+                    benchmark,
+                );
+            }
+
+            let (start, end) = (bounds[largest], bounds[largest + 1]);
+
+            slice = &mut mem::take(&mut slice)[start..end];
+            oracle = &mut mem::take(&mut oracle)[start..end];
+        }
+    }
+
+    fn choose_pivots_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        spacing: usize,
+        benchmark: &mut impl Benchmark,
+    ) -> ([T; K], bool) {
+        let sample_len = MultiPivotQuicksort::<K>::sample_len(spacing);
+        let step = slice.len() / sample_len;
+
+        for i in 0..sample_len {
+            benchmark.add_swap();
+            benchmark.add_writes(2);
+            slice.swap(i, i * step);
+        }
+
+        let sample = &mut slice[..sample_len];
+
+        InsertionSort::insertion_sort_with_benchmark(sample, benchmark);
+
+        let mut pivots = [sample[0]; K];
+
+        for (i, pivot) in pivots.iter_mut().enumerate() {
+            *pivot = sample[(i + 1) * spacing - 1];
+        }
+
+        let equal_buckets = sample.windows(2).any(|pair| {
+            benchmark.add_cmp();
+            pair[0] == pair[1]
+        });
+
+        (pivots, equal_buckets)
+    }
+
+    fn partition_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        oracle: &mut [u16],
+        pivots: &[T; K],
+        equal_buckets: bool,
+        benchmark: &mut impl Benchmark,
+    ) -> Vec<usize> {
+        let mut bounds = vec![0; 2 * K + 2];
+
+        benchmark.add_allocation(mem::size_of_val(&bounds[..]));
+        benchmark.add_pass();
+
+        for (&value, bucket) in slice.iter().zip(oracle.iter_mut()) {
+            benchmark.add_scan();
+
+            let pivot = pivots.partition_point(|&pivot| {
+                benchmark.add_cmp();
+                pivot < value
+            });
+
+            *bucket = 2 * pivot as u16;
+
+            if equal_buckets && pivot < K {
+                benchmark.add_cmp();
+
+                if value == pivots[pivot] {
+                    *bucket += 1;
+                }
+            }
+
+            bounds[*bucket as usize + 1] += 1;
+        }
+
+        for i in 1..bounds.len() {
+            bounds[i] += bounds[i - 1];
+        }
+
+        let mut next = bounds.clone();
+
+        benchmark.add_allocation(mem::size_of_val(&next[..]));
+        benchmark.add_pass();
+
+        for bucket in 0..2 * K + 1 {
+            while next[bucket] < bounds[bucket + 1] {
+                let i = next[bucket];
+                let target = oracle[i] as usize;
+
+                benchmark.add_scan();
+
+                if target == bucket {
+                    next[bucket] += 1;
+                } else {
+                    let j = next[target];
+
+                    benchmark.add_swap();
+                    benchmark.add_writes(2);
+
+                    slice.swap(i, j);
+                    oracle.swap(i, j);
+                    next[target] += 1;
+                }
+            }
+        }
+
+        bounds
+    }
+}
+
+impl<const K: usize> Sorter for MultiPivotQuicksort<K> {
+    /// Multi-pivot quick sort implementation working on types implementing
+    /// `Ord` + `Copy` so it's mostly usefull for primitive types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    ///
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// MultiPivotQuicksort::<3>::sort(&mut slice);
+    ///
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// ```
+    fn sort<T: Ord + Copy>(slice: &mut [T]) {
+        MultiPivotQuicksort::<K>::multi_pivot_quick_sort(slice);
+    }
+}
+
+impl<const K: usize> BenchmarkingSorter for MultiPivotQuicksort<K> {
+    /// Multi-pivot quick sort implementation with additional benchmarking
+    /// capabilities. Classifying items and moving them into partitions are
+    /// counted as separate passes.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::StandardBenchmarker;
+    ///
+    /// let mut benchmarker = StandardBenchmarker::default();
+    /// let mut slice = (0..30).rev().collect::<Vec<_>>();
+    ///
+    /// MultiPivotQuicksort::<3>::sort_with_benchmark(&mut slice, &mut benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!((0..30).collect::<Vec<_>>(), slice);
+    /// assert_eq!(2, stats.passes);
+    /// assert_eq!(60, stats.scanned_elements);
+    /// ```
+    fn sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        benchmark.start_timer();
+
+        MultiPivotQuicksort::<K>::multi_pivot_quick_sort_with_benchmark(slice, benchmark);
+
+        benchmark.stop_timer();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarking::{StandardBenchmarker, Stats};
+    use crate::generator::*;
+
+    mod one_pivot {
+        use super::*;
+
+        crate::sorter_conformance_tests!(MultiPivotQuicksort<1>);
+    }
+
+    mod two_pivots {
+        use super::*;
+
+        crate::sorter_conformance_tests!(MultiPivotQuicksort<2>);
+    }
+
+    mod three_pivots {
+        use super::*;
+
+        crate::sorter_conformance_tests!(MultiPivotQuicksort<3>);
+    }
+
+    mod five_pivots {
+        use super::*;
+
+        crate::sorter_conformance_tests!(MultiPivotQuicksort<5>);
+    }
+
+    mod fifteen_pivots {
+        use super::*;
+
+        crate::sorter_conformance_tests!(MultiPivotQuicksort<15>);
+    }
+
+    fn stats<S: BenchmarkingSorter>(slice: &mut [u32]) -> Stats {
+        let mut benchmarker = StandardBenchmarker::default();
+
+        S::sort_with_benchmark(slice, &mut benchmarker);
+
+        benchmarker.get_stats()
+    }
+
+    #[test]
+    fn more_pivots_scan_fewer_elements() {
+        let input = RandomGenerator::generate_seeded(100000, 0);
+
+        let one = stats::<MultiPivotQuicksort<1>>(&mut input.clone());
+        let three = stats::<MultiPivotQuicksort<3>>(&mut input.clone());
+        let fifteen = stats::<MultiPivotQuicksort<15>>(&mut input.clone());
+
+        assert!(three.scanned_elements < one.scanned_elements);
+        assert!(fifteen.scanned_elements < three.scanned_elements);
+        assert!(3 * fifteen.passes < one.passes);
+    }
+
+    #[test]
+    fn few_distinct_values_use_equal_buckets() {
+        let n = 100000;
+        let mut slice = RandomGenerator::generate_seeded(n, 0)
+            .into_iter()
+            .map(|value| value % 3)
+            .collect::<Vec<_>>();
+
+        let stats = stats::<MultiPivotQuicksort<2>>(&mut slice);
+
+        assert!(slice.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(stats.scanned_elements < 4 * n);
+    }
+
+    #[test]
+    #[should_panic(expected = "number of pivots")]
+    fn zero_pivots_are_rejected() {
+        MultiPivotQuicksort::<0>::sort(&mut [2, 1]);
+    }
+
+    #[test]
+    fn sorted_input_keeps_stack_shallow() {
+        crate::conformance::shallow_stack_sorting_test::<MultiPivotQuicksort<3>>(1_000_000);
+    }
+}
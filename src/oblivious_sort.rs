@@ -0,0 +1,1105 @@
+//! Data-oblivious sorting algorithms: bitonic sort, Batcher's odd-even merge
+//! sort and odd-even transposition sort. Sequence of compare-exchanges they
+//! perform depends only on length of slice, never on its items, so they are
+//! the baseline for constant-time sorting.
+
+use std::mem;
+use std::thread;
+
+use crate::benchmarking::AtomicBenchmarker;
+use crate::prelude::*;
+
+/// Parallel stages split slice only into chunks at least this long, shorter
+/// chunks aren't worth spawning a thread.
+const MIN_CHUNK_LEN: usize = 1 << 12;
+
+/// Minimal chunk of parallel transposition sort. Threads apply batches of
+/// rounds there, which is about `chunk_len^2 / 4` comparisons per thread.
+const MIN_TRANSPOSITION_CHUNK_LEN: usize = 1 << 8;
+
+/// One layer of comparators applied to slice starting `offset` items into
+/// it. Compared segments of a stage don't overlap, so they can be processed
+/// in any order or in parallel. Comparators reaching past the end of slice
+/// are left out, as if slice was padded by items bigger than all others.
+#[derive(Clone, Copy, Debug)]
+enum Stage {
+    /// Compares `distance` items starting every `2 * distance` items with
+    /// `distance` items following them, unless the second segment starts
+    /// at a multiple of `merge`, i.e. segments belong to different merges.
+    Shift {
+        offset: usize,
+        distance: usize,
+        merge: usize,
+    },
+    /// Compares item `x` of every block of `block` items with item
+    /// `block - 1 - x` of the same block.
+    Mirror { block: usize },
+}
+
+impl Stage {
+    fn offset(self) -> usize {
+        match self {
+            Stage::Shift { offset, .. } => offset,
+            Stage::Mirror { .. } => 0,
+        }
+    }
+
+    /// Number of items after which comparators of the stage repeat.
+    fn period(self) -> usize {
+        match self {
+            Stage::Shift { distance, .. } => 2 * distance,
+            Stage::Mirror { block } => block,
+        }
+    }
+
+    /// Calls `f(first, second, len, reversed)` for every pair of compared
+    /// segments of `len` items in slice of `slice_len` items, which begins
+    /// `base` items after the start of the whole sorted slice. Item `i` of
+    /// the first segment is compared with item `i` of the second segment,
+    /// or with item `len - 1 - i` if `reversed`.
+    fn for_each_segment(
+        self,
+        slice_len: usize,
+        base: usize,
+        mut f: impl FnMut(usize, usize, usize, bool),
+    ) {
+        match self {
+            Stage::Shift {
+                distance, merge, ..
+            } => {
+                for first in (0..slice_len).step_by(2 * distance) {
+                    let second = first + distance;
+
+                    if second < slice_len && !(base + second).is_multiple_of(merge) {
+                        f(first, second, distance.min(slice_len - second), false);
+                    }
+                }
+            }
+            Stage::Mirror { block } => {
+                for start in (0..slice_len).step_by(block) {
+                    let mid = start + block / 2;
+
+                    if mid < slice_len {
+                        let len = (block / 2).min(slice_len - mid);
+
+                        f(mid - len, mid, len, true);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Stages of bitonic sort. Every merge first compares items symmetric
+/// around the middle of block, then halves resulting bitonic sequences.
+fn bitonic_stages(len: usize) -> Vec<Stage> {
+    let mut stages = Vec::new();
+    let mut block = 2;
+
+    while block <= len.next_power_of_two() {
+        stages.push(Stage::Mirror { block });
+
+        let mut distance = block / 4;
+
+        while distance > 0 {
+            stages.push(Stage::Shift {
+                offset: 0,
+                distance,
+                merge: usize::MAX,
+            });
+
+            distance /= 2;
+        }
+
+        block *= 2;
+    }
+
+    stages
+}
+
+/// Stages of Batcher's odd-even merge sort. Merges of blocks of `2 * p`
+/// items compare items `distance` apart for `distance` going from `p`
+/// down to 1.
+fn odd_even_merge_stages(len: usize) -> Vec<Stage> {
+    let mut stages = Vec::new();
+    let mut p = 1;
+
+    while p < len {
+        let mut distance = p;
+
+        while distance > 0 {
+            stages.push(Stage::Shift {
+                offset: distance % p,
+                distance,
+                merge: 2 * p,
+            });
+
+            distance /= 2;
+        }
+
+        p *= 2;
+    }
+
+    stages
+}
+
+/// Stages of odd-even transposition sort, `len` rounds alternately
+/// comparing neighbours starting at even and odd indices.
+fn transposition_stages(len: usize) -> Vec<Stage> {
+    (0..len)
+        .map(|round| Stage::Shift {
+            offset: round % 2,
+            distance: 1,
+            merge: usize::MAX,
+        })
+        .collect()
+}
+
+/// Compare-exchange without branch on result of comparison.
+fn compare_exchange<T: Ord + Copy>(a: &mut T, b: &mut T) {
+    let (x, y) = (*a, *b);
+    let swap = y < x;
+
+    *a = if swap { y } else { x };
+    *b = if swap { x } else { y };
+}
+
+fn compare_segments<T: Ord + Copy>(first: &mut [T], second: &mut [T], reversed: bool) {
+    if reversed {
+        for (a, b) in first.iter_mut().zip(second.iter_mut().rev()) {
+            compare_exchange(a, b);
+        }
+    } else {
+        for (a, b) in first.iter_mut().zip(second.iter_mut()) {
+            compare_exchange(a, b);
+        }
+    }
+}
+
+fn compare_segments_with_benchmark<T: Ord + Copy>(
+    first: &mut [T],
+    second: &mut [T],
+    reversed: bool,
+    benchmark: &mut impl Benchmark,
+) {
+    let mut compare_exchange = |a: &mut T, b: &mut T| {
+        let (x, y) = (*a, *b);
+        let swap = y < x;
+
+        benchmark.add_cmp();
+        // both items are stored whether they are swapped or not
+        benchmark.add_writes(2);
+
+        if swap {
+            benchmark.add_swap();
+        }
+
+        *a = if swap { y } else { x };
+        *b = if swap { x } else { y };
+    };
+
+    if reversed {
+        for (a, b) in first.iter_mut().zip(second.iter_mut().rev()) {
+            compare_exchange(a, b);
+        }
+    } else {
+        for (a, b) in first.iter_mut().zip(second.iter_mut()) {
+            compare_exchange(a, b);
+        }
+    }
+}
+
+/// Applies stage to slice beginning `base` items after the start of the
+/// whole sorted slice.
+fn apply_stage<T: Ord + Copy>(slice: &mut [T], base: usize, stage: Stage) {
+    stage.for_each_segment(slice.len(), base, |first, second, len, reversed| {
+        let (head, tail) = slice.split_at_mut(second);
+
+        compare_segments(&mut head[first..first + len], &mut tail[..len], reversed);
+    });
+}
+
+fn apply_stage_with_benchmark<T: Ord + Copy>(
+    slice: &mut [T],
+    base: usize,
+    stage: Stage,
+    benchmark: &mut impl Benchmark,
+) {
+    stage.for_each_segment(slice.len(), base, |first, second, len, reversed| {
+        let (head, tail) = slice.split_at_mut(second);
+
+        compare_segments_with_benchmark(
+            &mut head[first..first + len],
+            &mut tail[..len],
+            reversed,
+            benchmark,
+        );
+    });
+}
+
+/// Splits slice into mutable pairs of compared segments of the stage.
+fn split_segments<T>(
+    slice: &mut [T],
+    base: usize,
+    stage: Stage,
+) -> Vec<(&mut [T], &mut [T], bool)> {
+    let mut bounds = Vec::new();
+
+    stage.for_each_segment(slice.len(), base, |first, second, len, reversed| {
+        bounds.push((first, second, len, reversed));
+    });
+
+    let mut segments = Vec::with_capacity(bounds.len());
+    let mut rest = slice;
+    let mut consumed = 0;
+
+    for (first, second, len, reversed) in bounds {
+        let (_, tail) = mem::take(&mut rest).split_at_mut(first - consumed);
+        let (first_segment, tail) = tail.split_at_mut(len);
+        let (_, tail) = tail.split_at_mut(second - first - len);
+        let (second_segment, tail) = tail.split_at_mut(len);
+
+        segments.push((first_segment, second_segment, reversed));
+
+        rest = tail;
+        consumed = second + len;
+    }
+
+    segments
+}
+
+fn sort_stages<T: Ord + Copy>(slice: &mut [T], stages: &[Stage]) {
+    for &stage in stages {
+        let offset = stage.offset().min(slice.len());
+
+        apply_stage(&mut slice[offset..], offset, stage);
+    }
+}
+
+fn sort_stages_with_benchmark<T: Ord + Copy>(
+    slice: &mut [T],
+    stages: &[Stage],
+    benchmark: &mut impl Benchmark,
+) {
+    for &stage in stages {
+        let offset = stage.offset().min(slice.len());
+
+        apply_stage_with_benchmark(&mut slice[offset..], offset, stage, benchmark);
+
+        benchmark.add_pass();
+    }
+}
+
+/// Applies every stage on `threads` threads. Stages repeating more often
+/// than once per chunk are applied to whole chunks in parallel, longer
+/// compared segments are split into pieces compared in parallel.
+fn parallel_sort_stages<T: Ord + Copy + Send>(slice: &mut [T], stages: &[Stage], threads: usize) {
+    let chunk_len = slice.len().div_ceil(threads).max(MIN_CHUNK_LEN);
+
+    for &stage in stages {
+        let offset = stage.offset().min(slice.len());
+        let slice = &mut slice[offset..];
+
+        if threads == 1 || slice.len() <= chunk_len {
+            apply_stage(slice, offset, stage);
+        } else if stage.period() <= chunk_len {
+            let chunk_len = chunk_len.next_multiple_of(stage.period());
+
+            thread::scope(|scope| {
+                for (i, chunk) in slice.chunks_mut(chunk_len).enumerate() {
+                    scope.spawn(move || apply_stage(chunk, offset + i * chunk_len, stage));
+                }
+            });
+        } else {
+            let piece_len = chunk_len / 2;
+
+            thread::scope(|scope| {
+                for (first, second, reversed) in split_segments(slice, offset, stage) {
+                    let pieces = first.chunks_mut(piece_len);
+
+                    let pairs: Vec<_> = if reversed {
+                        pieces.zip(second.rchunks_mut(piece_len)).collect()
+                    } else {
+                        pieces.zip(second.chunks_mut(piece_len)).collect()
+                    };
+
+                    for (first, second) in pairs {
+                        scope.spawn(move || compare_segments(first, second, reversed));
+                    }
+                }
+            });
+        }
+    }
+}
+
+fn parallel_sort_stages_with_benchmark<T: Ord + Copy + Send>(
+    slice: &mut [T],
+    stages: &[Stage],
+    threads: usize,
+    benchmark: &AtomicBenchmarker,
+) {
+    let chunk_len = slice.len().div_ceil(threads).max(MIN_CHUNK_LEN);
+
+    for &stage in stages {
+        let offset = stage.offset().min(slice.len());
+        let slice = &mut slice[offset..];
+
+        if threads == 1 || slice.len() <= chunk_len {
+            let mut benchmark = benchmark;
+
+            apply_stage_with_benchmark(slice, offset, stage, &mut benchmark);
+        } else if stage.period() <= chunk_len {
+            let chunk_len = chunk_len.next_multiple_of(stage.period());
+
+            thread::scope(|scope| {
+                for (i, chunk) in slice.chunks_mut(chunk_len).enumerate() {
+                    scope.spawn(move || {
+                        let mut benchmark = benchmark;
+
+                        apply_stage_with_benchmark(
+                            chunk,
+                            offset + i * chunk_len,
+                            stage,
+                            &mut benchmark,
+                        );
+                    });
+                }
+            });
+        } else {
+            let piece_len = chunk_len / 2;
+
+            thread::scope(|scope| {
+                for (first, second, reversed) in split_segments(slice, offset, stage) {
+                    let pieces = first.chunks_mut(piece_len);
+
+                    let pairs: Vec<_> = if reversed {
+                        pieces.zip(second.rchunks_mut(piece_len)).collect()
+                    } else {
+                        pieces.zip(second.chunks_mut(piece_len)).collect()
+                    };
+
+                    for (first, second) in pairs {
+                        scope.spawn(move || {
+                            let mut benchmark = benchmark;
+
+                            compare_segments_with_benchmark(
+                                first,
+                                second,
+                                reversed,
+                                &mut benchmark,
+                            );
+                        });
+                    }
+                }
+            });
+        }
+
+        let mut benchmark = benchmark;
+
+        benchmark.add_pass();
+    }
+}
+
+/// Applies round `round` of transposition sort to neighbours `(i, i + 1)`
+/// of slice with `lower <= i` and `i + 1 < upper`. Slice begins `base`
+/// items after the start of the whole sorted slice.
+fn transposition_round<T: Ord + Copy>(
+    slice: &mut [T],
+    base: usize,
+    round: usize,
+    lower: usize,
+    upper: usize,
+) {
+    let first = lower + (base + lower + round) % 2;
+
+    for i in (first..upper.min(slice.len()).saturating_sub(1)).step_by(2) {
+        if let [a, b] = &mut slice[i..i + 2] {
+            compare_exchange(a, b);
+        }
+    }
+}
+
+fn transposition_round_with_benchmark<T: Ord + Copy>(
+    slice: &mut [T],
+    base: usize,
+    round: usize,
+    lower: usize,
+    upper: usize,
+    benchmark: &mut impl Benchmark,
+) {
+    let first = lower + (base + lower + round) % 2;
+
+    for i in (first..upper.min(slice.len()).saturating_sub(1)).step_by(2) {
+        let (a, b) = slice[i..i + 2].split_at_mut(1);
+
+        compare_segments_with_benchmark(a, b, false, benchmark);
+    }
+}
+
+/// Odd-even transposition sort on `threads` threads. Rounds are applied in
+/// batches of `batch` rounds, so threads aren't spawned for every round.
+/// Every thread first applies comparators of the batch which don't depend on
+/// items of neighbouring chunks, i.e. the ones shrinking by one item on both
+/// sides of the chunk every round. Then windows of `2 * batch` items around
+/// chunk boundaries are finished in parallel. Comparators are applied in the
+/// same order as by sequential sort for every item.
+fn parallel_transposition_sort<T: Ord + Copy + Send>(slice: &mut [T], threads: usize) {
+    let len = slice.len();
+    let chunk_len = len
+        .div_ceil(threads)
+        .max(MIN_TRANSPOSITION_CHUNK_LEN)
+        .next_multiple_of(2);
+
+    if threads == 1 || len <= chunk_len {
+        sort_stages(slice, &transposition_stages(len));
+        return;
+    }
+
+    let batch = chunk_len / 2;
+
+    for start in (0..len).step_by(batch) {
+        let rounds = start..(start + batch).min(len);
+
+        thread::scope(|scope| {
+            for (i, chunk) in slice.chunks_mut(chunk_len).enumerate() {
+                let rounds = rounds.clone();
+
+                scope.spawn(move || {
+                    let base = i * chunk_len;
+                    let chunk_len = chunk.len();
+
+                    for (t, round) in rounds.enumerate() {
+                        let lower = if base == 0 { 0 } else { t };
+                        let upper = if base + chunk_len == len {
+                            chunk_len
+                        } else {
+                            chunk_len - t
+                        };
+
+                        transposition_round(chunk, base, round, lower, upper);
+                    }
+                });
+            }
+        });
+
+        thread::scope(|scope| {
+            let mut rest = &mut slice[..];
+            let mut consumed = 0;
+
+            for boundary in (chunk_len..len).step_by(chunk_len) {
+                let (_, tail) = mem::take(&mut rest).split_at_mut(boundary - batch - consumed);
+                let (window, tail) = tail.split_at_mut((2 * batch).min(tail.len()));
+
+                rest = tail;
+                consumed = boundary - batch + window.len();
+
+                let rounds = rounds.clone();
+
+                scope.spawn(move || {
+                    for (t, round) in rounds.enumerate() {
+                        transposition_round(
+                            window,
+                            boundary - batch,
+                            round,
+                            batch - t - 1,
+                            batch + t + 1,
+                        );
+                    }
+                });
+            }
+        });
+    }
+}
+
+fn parallel_transposition_sort_with_benchmark<T: Ord + Copy + Send>(
+    slice: &mut [T],
+    threads: usize,
+    benchmark: &AtomicBenchmarker,
+) {
+    let len = slice.len();
+    let chunk_len = len
+        .div_ceil(threads)
+        .max(MIN_TRANSPOSITION_CHUNK_LEN)
+        .next_multiple_of(2);
+
+    if threads == 1 || len <= chunk_len {
+        let mut benchmark = benchmark;
+
+        sort_stages_with_benchmark(slice, &transposition_stages(len), &mut benchmark);
+        return;
+    }
+
+    let batch = chunk_len / 2;
+
+    for start in (0..len).step_by(batch) {
+        let rounds = start..(start + batch).min(len);
+
+        thread::scope(|scope| {
+            for (i, chunk) in slice.chunks_mut(chunk_len).enumerate() {
+                let rounds = rounds.clone();
+
+                scope.spawn(move || {
+                    let mut benchmark = benchmark;
+                    let base = i * chunk_len;
+                    let chunk_len = chunk.len();
+
+                    for (t, round) in rounds.enumerate() {
+                        let lower = if base == 0 { 0 } else { t };
+                        let upper = if base + chunk_len == len {
+                            chunk_len
+                        } else {
+                            chunk_len - t
+                        };
+
+                        transposition_round_with_benchmark(
+                            chunk,
+                            base,
+                            round,
+                            lower,
+                            upper,
+                            &mut benchmark,
+                        );
+                    }
+                });
+            }
+        });
+
+        thread::scope(|scope| {
+            let mut rest = &mut slice[..];
+            let mut consumed = 0;
+
+            for boundary in (chunk_len..len).step_by(chunk_len) {
+                let (_, tail) = mem::take(&mut rest).split_at_mut(boundary - batch - consumed);
+                let (window, tail) = tail.split_at_mut((2 * batch).min(tail.len()));
+
+                rest = tail;
+                consumed = boundary - batch + window.len();
+
+                let rounds = rounds.clone();
+
+                scope.spawn(move || {
+                    let mut benchmark = benchmark;
+
+                    for (t, round) in rounds.enumerate() {
+                        transposition_round_with_benchmark(
+                            window,
+                            boundary - batch,
+                            round,
+                            batch - t - 1,
+                            batch + t + 1,
+                            &mut benchmark,
+                        );
+                    }
+                });
+            }
+        });
+
+        let mut benchmark = benchmark;
+
+        for _ in rounds {
+            benchmark.add_pass();
+        }
+    }
+}
+
+/// Struct implementing `Sorter` + `BenchmarkingSorter` using bitonic sort.
+/// It performs `O(n log^2 n)` comparisons for any input, slices which
+/// aren't a power of two long are sorted as if they were padded.
+pub struct BitonicSort;
+
+impl Sorter for BitonicSort {
+    /// Bitonic sort implementation working on types implementing
+    /// `Ord` + `Copy` so it's mostly usefull for primitive types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::oblivious_sort::BitonicSort;
+    ///
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// BitonicSort::sort(&mut slice);
+    ///
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// ```
+    fn sort<T: Ord + Copy>(slice: &mut [T]) {
+        sort_stages(slice, &bitonic_stages(slice.len()));
+    }
+}
+
+impl BenchmarkingSorter for BitonicSort {
+    /// Bitonic sort implementation with additional benchmarking
+    /// capabilities. Every stage of the network is counted as a pass.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::StandardBenchmarker;
+    /// use algorithms::oblivious_sort::BitonicSort;
+    ///
+    /// let mut benchmarker = StandardBenchmarker::default();
+    /// let mut slice = [1, 8, 2, 3, 9, 5, 7, 4];
+    ///
+    /// BitonicSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!([1, 2, 3, 4, 5, 7, 8, 9], slice);
+    /// assert_eq!(24, stats.comparisons);
+    /// assert_eq!(6, stats.passes);
+    /// ```
+    fn sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        benchmark.start_timer();
+
+        sort_stages_with_benchmark(slice, &bitonic_stages(slice.len()), benchmark);
+
+        benchmark.stop_timer();
+    }
+}
+
+/// Struct implementing `ParallelSorter` + `BenchmarkingParallelSorter`
+/// using bitonic sort on `THREADS` threads. Every stage is split between
+/// threads, which are joined before the next stage.
+pub struct ParallelBitonicSort<const THREADS: usize>;
+
+impl<const THREADS: usize> ParallelSorter for ParallelBitonicSort<THREADS> {
+    /// Parallel bitonic sort implementation working on types implementing
+    /// `Ord` + `Copy` + `Send` + `Sync` so it's mostly usefull for
+    /// primitive types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::oblivious_sort::ParallelBitonicSort;
+    ///
+    /// let mut slice = (0..10000).rev().collect::<Vec<_>>();
+    ///
+    /// ParallelBitonicSort::<4>::sort(&mut slice);
+    ///
+    /// assert_eq!((0..10000).collect::<Vec<_>>(), slice);
+    /// ```
+    fn sort<T: Ord + Copy + Send + Sync>(slice: &mut [T]) {
+        parallel_sort_stages(slice, &bitonic_stages(slice.len()), THREADS);
+    }
+}
+
+impl<const THREADS: usize> BenchmarkingParallelSorter for ParallelBitonicSort<THREADS> {
+    /// Parallel bitonic sort implementation with additional benchmarking
+    /// capabilities. It counts the same comparisons as `BitonicSort`.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::AtomicBenchmarker;
+    /// use algorithms::oblivious_sort::ParallelBitonicSort;
+    ///
+    /// let benchmarker = AtomicBenchmarker::default();
+    /// let mut slice = (0..8).rev().collect::<Vec<_>>();
+    ///
+    /// ParallelBitonicSort::<4>::sort_with_benchmark(&mut slice, &benchmarker);
+    ///
+    /// assert_eq!((0..8).collect::<Vec<_>>(), slice);
+    /// assert_eq!(24, benchmarker.get_stats().comparisons);
+    /// ```
+    fn sort_with_benchmark<T: Ord + Copy + Send + Sync>(
+        slice: &mut [T],
+        benchmark: &AtomicBenchmarker,
+    ) {
+        let mut timer = benchmark;
+
+        timer.start_timer();
+
+        parallel_sort_stages_with_benchmark(
+            slice,
+            &bitonic_stages(slice.len()),
+            THREADS,
+            benchmark,
+        );
+
+        timer.stop_timer();
+    }
+}
+
+/// Struct implementing `Sorter` + `BenchmarkingSorter` using Batcher's
+/// odd-even merge sort. It performs `O(n log^2 n)` comparisons for any
+/// input, fewer than `BitonicSort`, slices which aren't a power of two long
+/// are sorted as if they were padded.
+pub struct OddEvenMergeSort;
+
+impl Sorter for OddEvenMergeSort {
+    /// Odd-even merge sort implementation working on types implementing
+    /// `Ord` + `Copy` so it's mostly usefull for primitive types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::oblivious_sort::OddEvenMergeSort;
+    ///
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// OddEvenMergeSort::sort(&mut slice);
+    ///
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// ```
+    fn sort<T: Ord + Copy>(slice: &mut [T]) {
+        sort_stages(slice, &odd_even_merge_stages(slice.len()));
+    }
+}
+
+impl BenchmarkingSorter for OddEvenMergeSort {
+    /// Odd-even merge sort implementation with additional benchmarking
+    /// capabilities. Every stage of the network is counted as a pass.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::StandardBenchmarker;
+    /// use algorithms::oblivious_sort::OddEvenMergeSort;
+    ///
+    /// let mut benchmarker = StandardBenchmarker::default();
+    /// let mut slice = [1, 8, 2, 3, 9, 5, 7, 4];
+    ///
+    /// OddEvenMergeSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!([1, 2, 3, 4, 5, 7, 8, 9], slice);
+    /// assert_eq!(19, stats.comparisons);
+    /// assert_eq!(6, stats.passes);
+    /// ```
+    fn sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        benchmark.start_timer();
+
+        sort_stages_with_benchmark(slice, &odd_even_merge_stages(slice.len()), benchmark);
+
+        benchmark.stop_timer();
+    }
+}
+
+/// Struct implementing `ParallelSorter` + `BenchmarkingParallelSorter`
+/// using odd-even merge sort on `THREADS` threads. Every stage is split
+/// between threads, which are joined before the next stage.
+pub struct ParallelOddEvenMergeSort<const THREADS: usize>;
+
+impl<const THREADS: usize> ParallelSorter for ParallelOddEvenMergeSort<THREADS> {
+    /// Parallel odd-even merge sort implementation working on types
+    /// implementing `Ord` + `Copy` + `Send` + `Sync` so it's mostly usefull
+    /// for primitive types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::oblivious_sort::ParallelOddEvenMergeSort;
+    ///
+    /// let mut slice = (0..10000).rev().collect::<Vec<_>>();
+    ///
+    /// ParallelOddEvenMergeSort::<4>::sort(&mut slice);
+    ///
+    /// assert_eq!((0..10000).collect::<Vec<_>>(), slice);
+    /// ```
+    fn sort<T: Ord + Copy + Send + Sync>(slice: &mut [T]) {
+        parallel_sort_stages(slice, &odd_even_merge_stages(slice.len()), THREADS);
+    }
+}
+
+impl<const THREADS: usize> BenchmarkingParallelSorter for ParallelOddEvenMergeSort<THREADS> {
+    /// Parallel odd-even merge sort implementation with additional
+    /// benchmarking capabilities. It counts the same comparisons as
+    /// `OddEvenMergeSort`.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::AtomicBenchmarker;
+    /// use algorithms::oblivious_sort::ParallelOddEvenMergeSort;
+    ///
+    /// let benchmarker = AtomicBenchmarker::default();
+    /// let mut slice = (0..8).rev().collect::<Vec<_>>();
+    ///
+    /// ParallelOddEvenMergeSort::<4>::sort_with_benchmark(&mut slice, &benchmarker);
+    ///
+    /// assert_eq!((0..8).collect::<Vec<_>>(), slice);
+    /// assert_eq!(19, benchmarker.get_stats().comparisons);
+    /// ```
+    fn sort_with_benchmark<T: Ord + Copy + Send + Sync>(
+        slice: &mut [T],
+        benchmark: &AtomicBenchmarker,
+    ) {
+        let mut timer = benchmark;
+
+        timer.start_timer();
+
+        parallel_sort_stages_with_benchmark(
+            slice,
+            &odd_even_merge_stages(slice.len()),
+            THREADS,
+            benchmark,
+        );
+
+        timer.stop_timer();
+    }
+}
+
+/// Struct implementing `Sorter` + `BenchmarkingSorter` using odd-even
+/// transposition sort. It performs `n` rounds comparing neighbours, which is
+/// `O(n^2)` comparisons for any input, but every round is fully parallel.
+pub struct OddEvenTranspositionSort;
+
+impl Sorter for OddEvenTranspositionSort {
+    /// Odd-even transposition sort implementation working on types
+    /// implementing `Ord` + `Copy` so it's mostly usefull for primitive
+    /// types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::oblivious_sort::OddEvenTranspositionSort;
+    ///
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// OddEvenTranspositionSort::sort(&mut slice);
+    ///
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// ```
+    fn sort<T: Ord + Copy>(slice: &mut [T]) {
+        sort_stages(slice, &transposition_stages(slice.len()));
+    }
+}
+
+impl BenchmarkingSorter for OddEvenTranspositionSort {
+    /// Odd-even transposition sort implementation with additional
+    /// benchmarking capabilities. Every round is counted as a pass.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::StandardBenchmarker;
+    /// use algorithms::oblivious_sort::OddEvenTranspositionSort;
+    ///
+    /// let mut benchmarker = StandardBenchmarker::default();
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// OddEvenTranspositionSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// assert_eq!(15, stats.comparisons);
+    /// assert_eq!(6, stats.passes);
+    /// ```
+    fn sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        benchmark.start_timer();
+
+        sort_stages_with_benchmark(slice, &transposition_stages(slice.len()), benchmark);
+
+        benchmark.stop_timer();
+    }
+}
+
+/// Struct implementing `ParallelSorter` + `BenchmarkingParallelSorter`
+/// using odd-even transposition sort on `THREADS` threads. Rounds are split
+/// between threads in batches, so threads are joined only twice per batch.
+pub struct ParallelOddEvenTranspositionSort<const THREADS: usize>;
+
+impl<const THREADS: usize> ParallelSorter for ParallelOddEvenTranspositionSort<THREADS> {
+    /// Parallel odd-even transposition sort implementation working on types
+    /// implementing `Ord` + `Copy` + `Send` + `Sync` so it's mostly usefull
+    /// for primitive types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::oblivious_sort::ParallelOddEvenTranspositionSort;
+    ///
+    /// let mut slice = (0..1000).rev().collect::<Vec<_>>();
+    ///
+    /// ParallelOddEvenTranspositionSort::<4>::sort(&mut slice);
+    ///
+    /// assert_eq!((0..1000).collect::<Vec<_>>(), slice);
+    /// ```
+    fn sort<T: Ord + Copy + Send + Sync>(slice: &mut [T]) {
+        parallel_transposition_sort(slice, THREADS);
+    }
+}
+
+impl<const THREADS: usize> BenchmarkingParallelSorter
+    for ParallelOddEvenTranspositionSort<THREADS>
+{
+    /// Parallel odd-even transposition sort implementation with additional
+    /// benchmarking capabilities. It counts the same comparisons as
+    /// `OddEvenTranspositionSort`.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::AtomicBenchmarker;
+    /// use algorithms::oblivious_sort::ParallelOddEvenTranspositionSort;
+    ///
+    /// let benchmarker = AtomicBenchmarker::default();
+    /// let mut slice = (0..6).rev().collect::<Vec<_>>();
+    ///
+    /// ParallelOddEvenTranspositionSort::<4>::sort_with_benchmark(&mut slice, &benchmarker);
+    ///
+    /// assert_eq!((0..6).collect::<Vec<_>>(), slice);
+    /// assert_eq!(15, benchmarker.get_stats().comparisons);
+    /// ```
+    fn sort_with_benchmark<T: Ord + Copy + Send + Sync>(
+        slice: &mut [T],
+        benchmark: &AtomicBenchmarker,
+    ) {
+        let mut timer = benchmark;
+
+        timer.start_timer();
+
+        parallel_transposition_sort_with_benchmark(slice, THREADS, benchmark);
+
+        timer.stop_timer();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarking::{StandardBenchmarker, Stats};
+    use crate::generator::{AscendingGenerator, RandomGenerator, SeededGenerator};
+    use crate::sorting_network::SortingNetwork;
+
+    mod bitonic {
+        use super::*;
+
+        crate::sorter_conformance_tests!(BitonicSort);
+    }
+
+    mod parallel_bitonic {
+        use super::*;
+
+        crate::parallel_sorter_conformance_tests!(ParallelBitonicSort<4>);
+    }
+
+    mod odd_even_merge {
+        use super::*;
+
+        crate::sorter_conformance_tests!(OddEvenMergeSort);
+    }
+
+    mod parallel_odd_even_merge {
+        use super::*;
+
+        crate::parallel_sorter_conformance_tests!(ParallelOddEvenMergeSort<4>);
+    }
+
+    mod transposition {
+        use super::*;
+
+        crate::sorter_conformance_tests!(OddEvenTranspositionSort);
+    }
+
+    mod parallel_transposition {
+        use super::*;
+
+        crate::parallel_sorter_conformance_tests!(ParallelOddEvenTranspositionSort<4>);
+    }
+
+    fn stats<S: BenchmarkingSorter>(input: &[u32]) -> Stats {
+        let mut benchmarker = StandardBenchmarker::default();
+
+        S::sort_with_benchmark(&mut input.to_vec(), &mut benchmarker);
+
+        benchmarker.get_stats()
+    }
+
+    fn parallel_stats<S: BenchmarkingParallelSorter>(input: &[u32]) -> Stats {
+        let benchmarker = AtomicBenchmarker::default();
+        let mut slice = input.to_vec();
+
+        S::sort_with_benchmark(&mut slice, &benchmarker);
+
+        assert!(slice.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        benchmarker.get_stats()
+    }
+
+    #[test]
+    fn comparisons_match_networks() {
+        for n in 0..=70 {
+            let input = RandomGenerator::generate_seeded(n, n as u64);
+
+            assert_eq!(
+                SortingNetwork::bitonic(n).size(),
+                stats::<BitonicSort>(&input).comparisons
+            );
+            assert_eq!(
+                SortingNetwork::odd_even_merge(n).size(),
+                stats::<OddEvenMergeSort>(&input).comparisons
+            );
+            assert_eq!(
+                n * n.saturating_sub(1) / 2,
+                stats::<OddEvenTranspositionSort>(&input).comparisons
+            );
+        }
+    }
+
+    #[test]
+    fn comparisons_do_not_depend_on_input() {
+        let n = 1000;
+        let random = RandomGenerator::generate_seeded(n, 1);
+        let ascending = AscendingGenerator::generate_seeded(n, 1);
+
+        assert_eq!(
+            stats::<BitonicSort>(&random).comparisons,
+            stats::<BitonicSort>(&ascending).comparisons
+        );
+        assert_eq!(
+            stats::<OddEvenMergeSort>(&random).comparisons,
+            stats::<OddEvenMergeSort>(&ascending).comparisons
+        );
+        assert_eq!(
+            stats::<OddEvenTranspositionSort>(&random).comparisons,
+            stats::<OddEvenTranspositionSort>(&ascending).comparisons
+        );
+    }
+
+    #[test]
+    fn parallel_sorts_count_like_sequential() {
+        let input = RandomGenerator::generate_seeded(5 * MIN_CHUNK_LEN + 123, 2);
+        let bitonic = stats::<BitonicSort>(&input);
+        let odd_even_merge = stats::<OddEvenMergeSort>(&input);
+
+        for stats in [
+            parallel_stats::<ParallelBitonicSort<3>>(&input),
+            parallel_stats::<ParallelBitonicSort<8>>(&input),
+        ] {
+            assert_eq!(bitonic.comparisons, stats.comparisons);
+            assert_eq!(bitonic.swaps, stats.swaps);
+            assert_eq!(bitonic.passes, stats.passes);
+        }
+
+        for stats in [
+            parallel_stats::<ParallelOddEvenMergeSort<3>>(&input),
+            parallel_stats::<ParallelOddEvenMergeSort<8>>(&input),
+        ] {
+            assert_eq!(odd_even_merge.comparisons, stats.comparisons);
+            assert_eq!(odd_even_merge.swaps, stats.swaps);
+            assert_eq!(odd_even_merge.passes, stats.passes);
+        }
+    }
+
+    #[test]
+    fn parallel_transposition_sort_counts_like_sequential() {
+        let chunk_len = MIN_TRANSPOSITION_CHUNK_LEN;
+
+        for n in [
+            2 * chunk_len + 1,
+            3 * chunk_len + chunk_len / 3,
+            9 * chunk_len - 1,
+        ] {
+            let input = RandomGenerator::generate_seeded(n, 3);
+            let sequential = stats::<OddEvenTranspositionSort>(&input);
+            let parallel = parallel_stats::<ParallelOddEvenTranspositionSort<8>>(&input);
+
+            assert_eq!(sequential.comparisons, parallel.comparisons);
+            assert_eq!(sequential.swaps, parallel.swaps);
+            assert_eq!(sequential.passes, parallel.passes);
+        }
+    }
+}
@@ -0,0 +1,757 @@
+//! Parallel merge sort and quick sort implementations using scoped threads.
+
+use std::mem;
+use std::thread;
+
+use crate::benchmarking::{AtomicBenchmarker, NestedBenchmarker};
+use crate::merge_sort;
+use crate::prelude::*;
+
+/// Finds where diagonal `diagonal` of merge path crosses, i.e. how many of
+/// first `diagonal` merged items come from `left`. Items of `left` go before
+/// equal items of `right`, so merging stays stable.
+fn merge_path_split<T: Ord + Copy>(left: &[T], right: &[T], diagonal: usize) -> usize {
+    let mut low = diagonal.saturating_sub(right.len());
+    let mut high = diagonal.min(left.len());
+
+    while low < high {
+        let mid = (low + high) / 2;
+
+        if left[mid] <= right[diagonal - mid - 1] {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+
+    low
+}
+
+fn merge_path_split_with_benchmark<T: Ord + Copy>(
+    left: &[T],
+    right: &[T],
+    diagonal: usize,
+    benchmark: &mut impl Benchmark,
+) -> usize {
+    let mut low = diagonal.saturating_sub(right.len());
+    let mut high = diagonal.min(left.len());
+
+    while low < high {
+        let mid = (low + high) / 2;
+
+        benchmark.add_cmp();
+
+        if left[mid] <= right[diagonal - mid - 1] {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+
+    low
+}
+
+/// Struct implementing `ParallelSorter` + `BenchmarkingParallelSorter` using
+/// merge sort on `THREADS` threads. Halves are sorted in parallel and merged
+/// by merge path, which splits the output into equally long parts merged
+/// by separate threads. Slices up to `CUTOFF` items or getting only one
+/// thread are sorted sequentially using `MergeSort`. `sort_with_threads`
+/// overrides `THREADS` with thread count chosen at runtime.
+pub struct ParallelMergeSort<const THREADS: usize, const CUTOFF: usize>;
+
+impl<const THREADS: usize, const CUTOFF: usize> ParallelMergeSort<THREADS, CUTOFF> {
+    /// Sorts `slice` on `threads` threads chosen at runtime instead of
+    /// `THREADS`, e.g. by `std::thread::available_parallelism`.
+    ///
+    /// Examples:
+    /// ```
+    /// use std::thread;
+    ///
+    /// use algorithms::parallel_sort::ParallelMergeSort;
+    ///
+    /// let threads = thread::available_parallelism().map_or(1, |threads| threads.get());
+    /// let mut slice = (0..10000).rev().collect::<Vec<_>>();
+    ///
+    /// ParallelMergeSort::<1, 1000>::sort_with_threads(&mut slice, threads);
+    ///
+    /// assert_eq!((0..10000).collect::<Vec<_>>(), slice);
+    /// ```
+    pub fn sort_with_threads<T: Ord + Copy + Send + Sync>(slice: &mut [T], threads: usize) {
+        assert!(threads > 0, "there has to be at least one thread");
+
+        let mut buffer = slice.to_vec();
+
+        ParallelMergeSort::<THREADS, CUTOFF>::merge_sort(slice, &mut buffer, threads);
+    }
+
+    fn merge_sort<T: Ord + Copy + Send + Sync>(slice: &mut [T], buffer: &mut [T], threads: usize) {
+        if threads == 1 || slice.len() <= CUTOFF.max(1) {
+            MergeSort::sort(slice);
+            return;
+        }
+
+        let mid = slice.len() / 2;
+        let left_threads = threads / 2;
+
+        let (left, right) = slice.split_at_mut(mid);
+        let (left_buffer, right_buffer) = buffer.split_at_mut(mid);
+
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                ParallelMergeSort::<THREADS, CUTOFF>::merge_sort(left, left_buffer, left_threads);
+            });
+
+            ParallelMergeSort::<THREADS, CUTOFF>::merge_sort(
+                right,
+                right_buffer,
+                threads - left_threads,
+            );
+        });
+
+        ParallelMergeSort::<THREADS, CUTOFF>::parallel_merge(slice, mid, buffer, threads);
+
+        slice.copy_from_slice(buffer);
+    }
+
+    /// Merges sorted `slice[..mid]` and `slice[mid..]` into `dest` using
+    /// `threads` threads, each producing equally long part of `dest`.
+    fn parallel_merge<T: Ord + Copy + Send + Sync>(
+        slice: &[T],
+        mid: usize,
+        dest: &mut [T],
+        threads: usize,
+    ) {
+        let (left, right) = slice.split_at(mid);
+
+        let splits = (0..=threads)
+            .map(|worker| {
+                let diagonal = worker * slice.len() / threads;
+                let left_split = merge_path_split(left, right, diagonal);
+
+                (left_split, diagonal - left_split)
+            })
+            .collect::<Vec<_>>();
+
+        thread::scope(|scope| {
+            let mut dest = dest;
+
+            for bounds in splits.windows(2) {
+                let ((left_start, right_start), (left_end, right_end)) = (bounds[0], bounds[1]);
+                let len = left_end - left_start + right_end - right_start;
+
+                let (part, rest) = mem::take(&mut dest).split_at_mut(len);
+                dest = rest;
+
+                let left = &left[left_start..left_end];
+                let right = &right[right_start..right_end];
+
+                scope.spawn(move || merge_sort::merge_into(left, right, part));
+            }
+        });
+    }
+
+    /// Sorts `slice` on `threads` threads chosen at runtime instead of
+    /// `THREADS` with additional benchmarking capabilities.
+    pub fn sort_with_threads_and_benchmark<T: Ord + Copy + Send + Sync>(
+        slice: &mut [T],
+        threads: usize,
+        benchmark: &AtomicBenchmarker,
+    ) {
+        assert!(threads > 0, "there has to be at least one thread");
+
+        let mut timer = benchmark;
+
+        timer.start_timer();
+
+        let mut buffer = slice.to_vec();
+
+        (&*benchmark).add_allocation(mem::size_of_val(&buffer[..]));
+
+        ParallelMergeSort::<THREADS, CUTOFF>::merge_sort_with_benchmark(
+            slice,
+            &mut buffer,
+            threads,
+            benchmark,
+        );
+
+        timer.stop_timer();
+    }
+
+    fn merge_sort_with_benchmark<T: Ord + Copy + Send + Sync>(
+        slice: &mut [T],
+        buffer: &mut [T],
+        threads: usize,
+        benchmark: &AtomicBenchmarker,
+    ) {
+        if threads == 1 || slice.len() <= CUTOFF.max(1) {
+            let mut benchmark = benchmark;
+
+            MergeSort::sort_with_benchmark(slice, &mut NestedBenchmarker::new(&mut benchmark));
+            return;
+        }
+
+        let mid = slice.len() / 2;
+        let left_threads = threads / 2;
+
+        let (left, right) = slice.split_at_mut(mid);
+        let (left_buffer, right_buffer) = buffer.split_at_mut(mid);
+
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                ParallelMergeSort::<THREADS, CUTOFF>::merge_sort_with_benchmark(
+                    left,
+                    left_buffer,
+                    left_threads,
+                    benchmark,
+                );
+            });
+
+            ParallelMergeSort::<THREADS, CUTOFF>::merge_sort_with_benchmark(
+                right,
+                right_buffer,
+                threads - left_threads,
+                benchmark,
+            );
+        });
+
+        ParallelMergeSort::<THREADS, CUTOFF>::parallel_merge_with_benchmark(
+            slice, mid, buffer, threads, benchmark,
+        );
+
+        slice.copy_from_slice(buffer);
+
+        let mut benchmark = benchmark;
+
+        for _ in 0..slice.len() {
+            benchmark.add_move();
+            benchmark.add_writes(1);
+        }
+    }
+
+    fn parallel_merge_with_benchmark<T: Ord + Copy + Send + Sync>(
+        slice: &[T],
+        mid: usize,
+        dest: &mut [T],
+        threads: usize,
+        benchmark: &AtomicBenchmarker,
+    ) {
+        let (left, right) = slice.split_at(mid);
+
+        let splits = (0..=threads)
+            .map(|worker| {
+                let diagonal = worker * slice.len() / threads;
+                let left_split =
+                    merge_path_split_with_benchmark(left, right, diagonal, &mut &*benchmark);
+
+                (left_split, diagonal - left_split)
+            })
+            .collect::<Vec<_>>();
+
+        (&*benchmark).add_pass();
+
+        thread::scope(|scope| {
+            let mut dest = dest;
+
+            for bounds in splits.windows(2) {
+                let ((left_start, right_start), (left_end, right_end)) = (bounds[0], bounds[1]);
+                let len = left_end - left_start + right_end - right_start;
+
+                let (part, rest) = mem::take(&mut dest).split_at_mut(len);
+                dest = rest;
+
+                let left = &left[left_start..left_end];
+                let right = &right[right_start..right_end];
+
+                scope.spawn(move || {
+                    let mut benchmark = benchmark;
+
+                    merge_sort::merge_into_with_benchmark(left, right, part, &mut benchmark);
+                });
+            }
+        });
+    }
+}
+
+impl<const THREADS: usize, const CUTOFF: usize> ParallelSorter
+    for ParallelMergeSort<THREADS, CUTOFF>
+{
+    /// Parallel merge sort implementation working on types implementing
+    /// `Ord` + `Copy` + `Send` + `Sync` so it's mostly usefull for primitive
+    /// types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::parallel_sort::ParallelMergeSort;
+    ///
+    /// let mut slice = (0..10000).rev().collect::<Vec<_>>();
+    ///
+    /// ParallelMergeSort::<4, 1000>::sort(&mut slice);
+    ///
+    /// assert_eq!((0..10000).collect::<Vec<_>>(), slice);
+    /// ```
+    fn sort<T: Ord + Copy + Send + Sync>(slice: &mut [T]) {
+        ParallelMergeSort::<THREADS, CUTOFF>::sort_with_threads(slice, THREADS);
+    }
+}
+
+impl<const THREADS: usize, const CUTOFF: usize> BenchmarkingParallelSorter
+    for ParallelMergeSort<THREADS, CUTOFF>
+{
+    /// Parallel merge sort implementation with additional benchmarking
+    /// capabilities. Counters of all threads are summed up and every merge
+    /// path merge is counted as a pass.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::AtomicBenchmarker;
+    /// use algorithms::parallel_sort::ParallelMergeSort;
+    ///
+    /// let benchmarker = AtomicBenchmarker::default();
+    /// let mut slice = (0..10000).rev().collect::<Vec<_>>();
+    ///
+    /// ParallelMergeSort::<4, 1000>::sort_with_benchmark(&mut slice, &benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!((0..10000).collect::<Vec<_>>(), slice);
+    /// assert!(stats.comparisons > 0);
+    /// assert_eq!(3, stats.passes);
+    /// ```
+    fn sort_with_benchmark<T: Ord + Copy + Send + Sync>(
+        slice: &mut [T],
+        benchmark: &AtomicBenchmarker,
+    ) {
+        ParallelMergeSort::<THREADS, CUTOFF>::sort_with_threads_and_benchmark(
+            slice, THREADS, benchmark,
+        );
+    }
+}
+
+/// Struct implementing `ParallelSorter` + `BenchmarkingParallelSorter` using
+/// quick sort on `THREADS` threads. Slice is partitioned around median of
+/// three sequentially and both parts are sorted in parallel, threads being
+/// divided between them by their lengths. Slices up to `CUTOFF` items or
+/// getting only one thread are sorted sequentially using `PdqSort`.
+/// `sort_with_threads` overrides `THREADS` with thread count chosen at
+/// runtime.
+pub struct ParallelQuickSort<const THREADS: usize, const CUTOFF: usize>;
+
+impl<const THREADS: usize, const CUTOFF: usize> ParallelQuickSort<THREADS, CUTOFF> {
+    /// Sorts `slice` on `threads` threads chosen at runtime instead of
+    /// `THREADS`, e.g. by `std::thread::available_parallelism`.
+    ///
+    /// Examples:
+    /// ```
+    /// use std::thread;
+    ///
+    /// use algorithms::parallel_sort::ParallelQuickSort;
+    ///
+    /// let threads = thread::available_parallelism().map_or(1, |threads| threads.get());
+    /// let mut slice = (0..10000).rev().collect::<Vec<_>>();
+    ///
+    /// ParallelQuickSort::<1, 1000>::sort_with_threads(&mut slice, threads);
+    ///
+    /// assert_eq!((0..10000).collect::<Vec<_>>(), slice);
+    /// ```
+    pub fn sort_with_threads<T: Ord + Copy + Send + Sync>(slice: &mut [T], threads: usize) {
+        assert!(threads > 0, "there has to be at least one thread");
+
+        ParallelQuickSort::<THREADS, CUTOFF>::quick_sort(slice, threads);
+    }
+
+    /// Sorts `slice` on `threads` threads chosen at runtime instead of
+    /// `THREADS` with additional benchmarking capabilities.
+    pub fn sort_with_threads_and_benchmark<T: Ord + Copy + Send + Sync>(
+        slice: &mut [T],
+        threads: usize,
+        benchmark: &AtomicBenchmarker,
+    ) {
+        assert!(threads > 0, "there has to be at least one thread");
+
+        let mut timer = benchmark;
+
+        timer.start_timer();
+
+        ParallelQuickSort::<THREADS, CUTOFF>::quick_sort_with_benchmark(slice, threads, benchmark);
+
+        timer.stop_timer();
+    }
+
+    /// Divides `threads` between parts of given lengths, giving each of them
+    /// at least one.
+    fn left_threads(threads: usize, left_len: usize, right_len: usize) -> usize {
+        let len = left_len + right_len;
+
+        ((threads * left_len + len / 2) / len).clamp(1, threads - 1)
+    }
+
+    fn quick_sort<T: Ord + Copy + Send + Sync>(slice: &mut [T], threads: usize) {
+        if threads == 1 || slice.len() <= CUTOFF.max(2) {
+            PdqSort::sort(slice);
+            return;
+        }
+
+        let pivot_index = ParallelQuickSort::<THREADS, CUTOFF>::partition(slice);
+
+        let (left, right) = slice.split_at_mut(pivot_index);
+        let right = &mut right[1..];
+        let left_threads =
+            ParallelQuickSort::<THREADS, CUTOFF>::left_threads(threads, left.len(), right.len());
+
+        thread::scope(|scope| {
+            scope.spawn(|| ParallelQuickSort::<THREADS, CUTOFF>::quick_sort(left, left_threads));
+
+            ParallelQuickSort::<THREADS, CUTOFF>::quick_sort(right, threads - left_threads);
+        });
+    }
+
+    /// Moves median of first, middle and last item to the end and partitions
+    /// slice around it using Lomuto partition scheme.
+    fn partition<T: Ord + Copy>(slice: &mut [T]) -> usize {
+        let last = slice.len() - 1;
+        let mid = slice.len() / 2;
+
+        if slice[mid] < slice[0] {
+            slice.swap(mid, 0);
+        }
+
+        if slice[last] < slice[0] {
+            slice.swap(last, 0);
+        }
+
+        if slice[mid] < slice[last] {
+            slice.swap(mid, last);
+        }
+
+        let pivot = slice[last];
+
+        let mut l = 0;
+
+        for r in 0..last {
+            if slice[r] < pivot {
+                slice.swap(l, r);
+                l += 1;
+            }
+        }
+
+        slice.swap(l, last);
+
+        l
+    }
+
+    fn quick_sort_with_benchmark<T: Ord + Copy + Send + Sync>(
+        slice: &mut [T],
+        threads: usize,
+        benchmark: &AtomicBenchmarker,
+    ) {
+        if threads == 1 || slice.len() <= CUTOFF.max(2) {
+            let mut benchmark = benchmark;
+
+            PdqSort::sort_with_benchmark(slice, &mut NestedBenchmarker::new(&mut benchmark));
+            return;
+        }
+
+        let pivot_index =
+            ParallelQuickSort::<THREADS, CUTOFF>::partition_with_benchmark(slice, &mut &*benchmark);
+
+        let (left, right) = slice.split_at_mut(pivot_index);
+        let right = &mut right[1..];
+        let left_threads =
+            ParallelQuickSort::<THREADS, CUTOFF>::left_threads(threads, left.len(), right.len());
+
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                ParallelQuickSort::<THREADS, CUTOFF>::quick_sort_with_benchmark(
+                    left,
+                    left_threads,
+                    benchmark,
+                );
+            });
+
+            ParallelQuickSort::<THREADS, CUTOFF>::quick_sort_with_benchmark(
+                right,
+                threads - left_threads,
+                benchmark,
+            );
+        });
+    }
+
+    fn partition_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        benchmark: &mut impl Benchmark,
+    ) -> usize {
+        let last = slice.len() - 1;
+        let mid = slice.len() / 2;
+
+        benchmark.add_cmp();
+
+        if slice[mid] < slice[0] {
+            benchmark.add_swap();
+            benchmark.add_writes(2);
+            slice.swap(mid, 0);
+        }
+
+        benchmark.add_cmp();
+
+        if slice[last] < slice[0] {
+            benchmark.add_swap();
+            benchmark.add_writes(2);
+            slice.swap(last, 0);
+        }
+
+        benchmark.add_cmp();
+
+        if slice[mid] < slice[last] {
+            benchmark.add_swap();
+            benchmark.add_writes(2);
+            slice.swap(mid, last);
+        }
+
+        let pivot = slice[last];
+
+        let mut l = 0;
+
+        for r in 0..last {
+            benchmark.add_cmp();
+
+            if slice[r] < pivot {
+                benchmark.add_swap();
+                benchmark.add_writes(2);
+                slice.swap(l, r);
+                l += 1;
+            }
+        }
+
+        benchmark.add_swap();
+        benchmark.add_writes(2);
+        slice.swap(l, last);
+
+        l
+    }
+}
+
+impl<const THREADS: usize, const CUTOFF: usize> ParallelSorter
+    for ParallelQuickSort<THREADS, CUTOFF>
+{
+    /// Parallel quick sort implementation working on types implementing
+    /// `Ord` + `Copy` + `Send` + `Sync` so it's mostly usefull for primitive
+    /// types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::parallel_sort::ParallelQuickSort;
+    ///
+    /// let mut slice = (0..10000).rev().collect::<Vec<_>>();
+    ///
+    /// ParallelQuickSort::<4, 1000>::sort(&mut slice);
+    ///
+    /// assert_eq!((0..10000).collect::<Vec<_>>(), slice);
+    /// ```
+    fn sort<T: Ord + Copy + Send + Sync>(slice: &mut [T]) {
+        ParallelQuickSort::<THREADS, CUTOFF>::sort_with_threads(slice, THREADS);
+    }
+}
+
+impl<const THREADS: usize, const CUTOFF: usize> BenchmarkingParallelSorter
+    for ParallelQuickSort<THREADS, CUTOFF>
+{
+    /// Parallel quick sort implementation with additional benchmarking
+    /// capabilities. Counters of all threads are summed up.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::AtomicBenchmarker;
+    /// use algorithms::parallel_sort::ParallelQuickSort;
+    ///
+    /// let benchmarker = AtomicBenchmarker::default();
+    /// let mut slice = (0..10000).rev().collect::<Vec<_>>();
+    ///
+    /// ParallelQuickSort::<4, 1000>::sort_with_benchmark(&mut slice, &benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!((0..10000).collect::<Vec<_>>(), slice);
+    /// assert!(stats.comparisons > 0);
+    /// assert!(stats.swaps > 0);
+    /// ```
+    fn sort_with_benchmark<T: Ord + Copy + Send + Sync>(
+        slice: &mut [T],
+        benchmark: &AtomicBenchmarker,
+    ) {
+        ParallelQuickSort::<THREADS, CUTOFF>::sort_with_threads_and_benchmark(
+            slice, THREADS, benchmark,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarking::StandardBenchmarker;
+    use crate::conformance::{assert_stably_sorted, keyed_input};
+    use crate::generator::*;
+
+    mod merge_sort_single_thread {
+        use super::*;
+
+        crate::parallel_sorter_conformance_tests!(ParallelMergeSort<1, 64>);
+    }
+
+    mod merge_sort_many_threads {
+        use super::*;
+
+        crate::parallel_sorter_conformance_tests!(ParallelMergeSort<7, 16>);
+    }
+
+    mod quick_sort_single_thread {
+        use super::*;
+
+        crate::parallel_sorter_conformance_tests!(ParallelQuickSort<1, 64>);
+    }
+
+    mod quick_sort_many_threads {
+        use super::*;
+
+        crate::parallel_sorter_conformance_tests!(ParallelQuickSort<7, 16>);
+    }
+
+    #[test]
+    fn merge_path_split_is_stable() {
+        let left = [1, 2, 2, 3];
+        let right = [2, 2, 4];
+
+        assert_eq!(0, merge_path_split(&left, &right, 0));
+        assert_eq!(1, merge_path_split(&left, &right, 1));
+        assert_eq!(3, merge_path_split(&left, &right, 3));
+        assert_eq!(3, merge_path_split(&left, &right, 5));
+        assert_eq!(4, merge_path_split(&left, &right, 6));
+        assert_eq!(4, merge_path_split(&left, &right, 7));
+    }
+
+    #[test]
+    fn parallel_merge_sort_is_stable() {
+        for (seed, n) in [10, 100, 1000, 10000].into_iter().enumerate() {
+            for keys in [1, 2, 8] {
+                let input = keyed_input(n, keys, seed as u64);
+
+                let mut slice = input.clone();
+                ParallelMergeSort::<5, 16>::sort(&mut slice);
+                assert_stably_sorted(&slice);
+
+                let mut slice = input;
+                ParallelMergeSort::<5, 16>::sort_with_benchmark(
+                    &mut slice,
+                    &AtomicBenchmarker::default(),
+                );
+                assert_stably_sorted(&slice);
+            }
+        }
+    }
+
+    #[test]
+    fn single_thread_counts_like_sequential_sorter() {
+        let input = RandomGenerator::generate_seeded(10000, 0);
+
+        let parallel = AtomicBenchmarker::default();
+        ParallelMergeSort::<1, 64>::sort_with_benchmark(&mut input.clone(), &parallel);
+
+        let mut sequential = StandardBenchmarker::default();
+        MergeSort::sort_with_benchmark(&mut input.clone(), &mut sequential);
+
+        assert_eq!(
+            sequential.get_stats().comparisons,
+            parallel.get_stats().comparisons
+        );
+        assert_eq!(sequential.get_stats().swaps, parallel.get_stats().swaps);
+    }
+
+    #[test]
+    fn counters_of_all_threads_are_aggregated() {
+        let input = RandomGenerator::generate_seeded(100000, 0);
+
+        let stats = |sort: fn(&mut [u32], &AtomicBenchmarker)| {
+            let benchmarker = AtomicBenchmarker::default();
+
+            sort(&mut input.clone(), &benchmarker);
+
+            benchmarker.get_stats()
+        };
+
+        let merge = stats(ParallelMergeSort::<8, 1000>::sort_with_benchmark);
+        let quick = stats(ParallelQuickSort::<8, 1000>::sort_with_benchmark);
+
+        for _ in 0..3 {
+            let again = stats(ParallelMergeSort::<8, 1000>::sort_with_benchmark);
+
+            assert_eq!(merge.comparisons, again.comparisons);
+            assert_eq!(merge.swaps, again.swaps);
+
+            let again = stats(ParallelQuickSort::<8, 1000>::sort_with_benchmark);
+
+            assert_eq!(quick.comparisons, again.comparisons);
+            assert_eq!(quick.swaps, again.swaps);
+        }
+
+        // every item is merged on each of about log2(n) levels
+        assert!(merge.comparisons > 100000 * 14);
+    }
+
+    #[test]
+    fn runtime_threads_match_const_threads() {
+        let input = RandomGenerator::generate_seeded(10000, 0);
+        let mut expected = input.clone();
+        expected.sort();
+
+        for threads in [1, 3, 8] {
+            let mut slice = input.clone();
+            ParallelMergeSort::<1, 64>::sort_with_threads(&mut slice, threads);
+            assert_eq!(expected, slice);
+
+            let mut slice = input.clone();
+            ParallelQuickSort::<1, 64>::sort_with_threads(&mut slice, threads);
+            assert_eq!(expected, slice);
+        }
+
+        let runtime = AtomicBenchmarker::default();
+        let constant = AtomicBenchmarker::default();
+
+        ParallelMergeSort::<1, 64>::sort_with_threads_and_benchmark(
+            &mut input.clone(),
+            3,
+            &runtime,
+        );
+        ParallelMergeSort::<3, 64>::sort_with_benchmark(&mut input.clone(), &constant);
+
+        assert_eq!(
+            constant.get_stats().comparisons,
+            runtime.get_stats().comparisons
+        );
+        assert_eq!(constant.get_stats().passes, runtime.get_stats().passes);
+
+        let runtime = AtomicBenchmarker::default();
+        let constant = AtomicBenchmarker::default();
+
+        ParallelQuickSort::<1, 64>::sort_with_threads_and_benchmark(
+            &mut input.clone(),
+            3,
+            &runtime,
+        );
+        ParallelQuickSort::<3, 64>::sort_with_benchmark(&mut input.clone(), &constant);
+
+        assert_eq!(
+            constant.get_stats().comparisons,
+            runtime.get_stats().comparisons
+        );
+        assert_eq!(constant.get_stats().swaps, runtime.get_stats().swaps);
+    }
+
+    #[test]
+    #[should_panic(expected = "there has to be at least one thread")]
+    fn zero_runtime_threads_panic() {
+        ParallelQuickSort::<4, 64>::sort_with_threads(&mut [2, 1], 0);
+    }
+}
@@ -0,0 +1,807 @@
+//! Pattern-defeating quicksort algorithm implementation.
+
+use crate::prelude::*;
+use crate::tuning::Parameter;
+
+/// Struct implementing `Sorter` + `BenchmarkingSorter` using pattern-defeating
+/// quicksort to sort items. It's the recommended unstable sorter of this crate:
+/// it guards against quadratic behaviour with pattern breaking and heapsort
+/// fallback, runs in linear time on sorted or reversed input and handles
+/// many duplicates by partitioning out elements equal to pivot.
+pub struct PdqSort;
+
+/// Values of tunable parameters used by one sort, read once at its start.
+#[derive(Clone, Copy)]
+struct Parameters {
+    /// Slices up to this length are sorted using insertion sort.
+    insertion_len: usize,
+    /// Slices of at least this length choose pivot as Tukey's ninther.
+    ninther_len: usize,
+}
+
+impl Parameters {
+    fn current() -> Self {
+        Parameters {
+            insertion_len: Parameter::PdqInsertionLen.get(),
+            ninther_len: Parameter::PdqNintherLen.get(),
+        }
+    }
+}
+
+impl PdqSort {
+    /// Number of swaps after which pivot choice assumes reversed input.
+    const MAX_SWAPS: usize = 4 * 3;
+    /// Maximal number of out of order pairs fixed by partial insertion sort.
+    const MAX_STEPS: usize = 5;
+    /// Slices shorter than this aren't fixed by partial insertion sort.
+    const SHORTEST_SHIFTING: usize = 50;
+
+    fn pdq_sort<T: Ord + Copy>(slice: &mut [T]) {
+        let limit = usize::BITS - slice.len().leading_zeros();
+
+        PdqSort::recurse(slice, None, limit, Parameters::current());
+    }
+
+    fn recurse<T: Ord + Copy>(
+        mut slice: &mut [T],
+        mut predecessor: Option<T>,
+        mut limit: u32,
+        parameters: Parameters,
+    ) {
+        let mut was_balanced = true;
+        let mut was_partitioned = true;
+
+        loop {
+            let len = slice.len();
+
+            if len <= parameters.insertion_len {
+                InsertionSort::sort(slice);
+                return;
+            }
+
+            if limit == 0 {
+                HeapSort::sort(slice);
+                return;
+            }
+
+            if !was_balanced {
+                PdqSort::break_patterns(slice);
+                limit -= 1;
+            }
+
+            let (pivot, likely_sorted) = PdqSort::choose_pivot(slice, parameters.ninther_len);
+
+            if was_balanced
+                && was_partitioned
+                && likely_sorted
+                && PdqSort::partial_insertion_sort(slice)
+            {
+                return;
+            }
+
+            // pivot equal to predecessor means it's the smallest element of
+            // the slice, so all elements equal to it can be skipped at once
+            if let Some(predecessor) = predecessor {
+                if predecessor >= slice[pivot] {
+                    let mid = PdqSort::partition_equal(slice, pivot);
+                    slice = &mut std::mem::take(&mut slice)[mid..];
+                    continue;
+                }
+            }
+
+            let (mid, already_partitioned) = PdqSort::partition(slice, pivot);
+
+            was_balanced = mid.min(len - mid) >= len / 8;
+            was_partitioned = already_partitioned;
+
+            let (left, right) = std::mem::take(&mut slice).split_at_mut(mid);
+            let (pivot, right) = right.split_first_mut().unwrap();
+            let pivot = *pivot;
+
+            // recursing only into shorter side keeps stack depth logarithmic
+            if left.len() < right.len() {
+                PdqSort::recurse(left, predecessor, limit, parameters);
+                slice = right;
+                predecessor = Some(pivot);
+            } else {
+                PdqSort::recurse(right, Some(pivot), limit, parameters);
+                slice = left;
+            }
+        }
+    }
+
+    /// Partitions slice into elements smaller than pivot, pivot and elements
+    /// greater or equal to pivot. Returns position of pivot and whether slice
+    /// was already partitioned.
+    fn partition<T: Ord + Copy>(slice: &mut [T], pivot: usize) -> (usize, bool) {
+        slice.swap(0, pivot);
+
+        let pivot = slice[0];
+        let rest = &mut slice[1..];
+
+        let mut l = 0;
+        let mut r = rest.len();
+
+        while l < r && rest[l] < pivot {
+            l += 1;
+        }
+
+        while l < r && rest[r - 1] >= pivot {
+            r -= 1;
+        }
+
+        let was_partitioned = l >= r;
+
+        loop {
+            while l < r && rest[l] < pivot {
+                l += 1;
+            }
+
+            while l < r && rest[r - 1] >= pivot {
+                r -= 1;
+            }
+
+            if l >= r {
+                break;
+            }
+
+            r -= 1;
+            rest.swap(l, r);
+            l += 1;
+        }
+
+        slice.swap(0, l);
+
+        (l, was_partitioned)
+    }
+
+    /// Partitions slice into elements equal to pivot and greater than pivot,
+    /// assuming there are no elements smaller than pivot. Returns number of
+    /// elements equal to pivot.
+    fn partition_equal<T: Ord + Copy>(slice: &mut [T], pivot: usize) -> usize {
+        slice.swap(0, pivot);
+
+        let pivot = slice[0];
+        let rest = &mut slice[1..];
+
+        let mut l = 0;
+        let mut r = rest.len();
+
+        loop {
+            while l < r && pivot >= rest[l] {
+                l += 1;
+            }
+
+            while l < r && pivot < rest[r - 1] {
+                r -= 1;
+            }
+
+            if l >= r {
+                break;
+            }
+
+            r -= 1;
+            rest.swap(l, r);
+            l += 1;
+        }
+
+        l + 1
+    }
+
+    /// Chooses pivot as median of three or, for longer slices, Tukey's ninther.
+    /// Reverses slice if it looks descending. Returns pivot index and whether
+    /// slice is likely already sorted.
+    fn choose_pivot<T: Ord + Copy>(slice: &mut [T], ninther_len: usize) -> (usize, bool) {
+        let len = slice.len();
+
+        let mut a = len / 4;
+        let mut b = len / 4 * 2;
+        let mut c = len / 4 * 3;
+
+        let mut swaps = 0;
+
+        if len >= 8 {
+            if len >= ninther_len {
+                PdqSort::sort_adjacent(slice, &mut a, &mut swaps);
+                PdqSort::sort_adjacent(slice, &mut b, &mut swaps);
+                PdqSort::sort_adjacent(slice, &mut c, &mut swaps);
+            }
+
+            PdqSort::sort3(slice, &mut a, &mut b, &mut c, &mut swaps);
+        }
+
+        if swaps < PdqSort::MAX_SWAPS {
+            (b, swaps == 0)
+        } else {
+            slice.reverse();
+            (len - 1 - b, true)
+        }
+    }
+
+    fn sort_adjacent<T: Ord + Copy>(slice: &[T], a: &mut usize, swaps: &mut usize) {
+        let mut left = *a - 1;
+        let mut right = *a + 1;
+
+        PdqSort::sort3(slice, &mut left, a, &mut right, swaps);
+    }
+
+    fn sort3<T: Ord + Copy>(
+        slice: &[T],
+        a: &mut usize,
+        b: &mut usize,
+        c: &mut usize,
+        swaps: &mut usize,
+    ) {
+        PdqSort::sort2(slice, a, b, swaps);
+        PdqSort::sort2(slice, b, c, swaps);
+        PdqSort::sort2(slice, a, b, swaps);
+    }
+
+    fn sort2<T: Ord + Copy>(slice: &[T], a: &mut usize, b: &mut usize, swaps: &mut usize) {
+        if slice[*b] < slice[*a] {
+            std::mem::swap(a, b);
+            *swaps += 1;
+        }
+    }
+
+    /// Sorts nearly sorted slice by fixing few out of order pairs. Returns
+    /// whether slice got sorted.
+    fn partial_insertion_sort<T: Ord + Copy>(slice: &mut [T]) -> bool {
+        let len = slice.len();
+        let mut i = 1;
+
+        for _ in 0..PdqSort::MAX_STEPS {
+            while i < len && slice[i] >= slice[i - 1] {
+                i += 1;
+            }
+
+            if i == len {
+                return true;
+            }
+
+            if len < PdqSort::SHORTEST_SHIFTING {
+                return false;
+            }
+
+            slice.swap(i - 1, i);
+
+            PdqSort::shift_tail(&mut slice[..i]);
+            PdqSort::shift_head(&mut slice[i..]);
+        }
+
+        false
+    }
+
+    /// Moves last element left until it's in place.
+    fn shift_tail<T: Ord + Copy>(slice: &mut [T]) {
+        let mut j = slice.len() - 1;
+        let elem = slice[j];
+
+        while j > 0 && slice[j - 1] > elem {
+            slice[j] = slice[j - 1];
+            j -= 1;
+        }
+
+        if j != slice.len() - 1 {
+            slice[j] = elem;
+        }
+    }
+
+    /// Moves first element right until it's in place.
+    fn shift_head<T: Ord + Copy>(slice: &mut [T]) {
+        let mut j = 0;
+        let elem = slice[0];
+
+        while j + 1 < slice.len() && slice[j + 1] < elem {
+            slice[j] = slice[j + 1];
+            j += 1;
+        }
+
+        if j != 0 {
+            slice[j] = elem;
+        }
+    }
+
+    /// Swaps few elements around the middle at pseudorandom positions
+    /// to break patterns causing unbalanced partitions.
+    fn break_patterns<T: Ord + Copy>(slice: &mut [T]) {
+        let len = slice.len();
+
+        if len < 8 {
+            return;
+        }
+
+        let mut random = len as u32;
+        let modulus = len.next_power_of_two();
+        let position = len / 4 * 2;
+
+        for i in 0..3 {
+            // xorshift32
+            random ^= random << 13;
+            random ^= random >> 17;
+            random ^= random << 5;
+
+            let mut other = random as usize & (modulus - 1);
+
+            if other >= len {
+                other -= len;
+            }
+
+            slice.swap(position - 1 + i, other);
+        }
+    }
+
+    fn pdq_sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        let limit = usize::BITS - slice.len().leading_zeros();
+
+        PdqSort::recurse_with_benchmark(slice, None, limit, Parameters::current(), benchmark);
+    }
+
+    fn recurse_with_benchmark<T: Ord + Copy>(
+        mut slice: &mut [T],
+        mut predecessor: Option<T>,
+        mut limit: u32,
+        parameters: Parameters,
+        benchmark: &mut impl Benchmark,
+    ) {
+        let mut was_balanced = true;
+        let mut was_partitioned = true;
+
+        loop {
+            let len = slice.len();
+
+            if len <= parameters.insertion_len {
+                InsertionSort::insertion_sort_with_benchmark(slice, benchmark);
+                return;
+            }
+
+            if limit == 0 {
+                HeapSort::heap_sort_with_benchmark(slice, benchmark);
+                return;
+            }
+
+            if !was_balanced {
+                PdqSort::break_patterns_with_benchmark(slice, benchmark);
+                limit -= 1;
+            }
+
+            let (pivot, likely_sorted) =
+                PdqSort::choose_pivot_with_benchmark(slice, parameters.ninther_len, benchmark);
+
+            if was_balanced
+                && was_partitioned
+                && likely_sorted
+                && PdqSort::partial_insertion_sort_with_benchmark(slice, benchmark)
+            {
+                return;
+            }
+
+            if let Some(predecessor) = predecessor {
+                benchmark.add_cmp();
+
+                if predecessor >= slice[pivot] {
+                    let mid = PdqSort::partition_equal_with_benchmark(slice, pivot, benchmark);
+                    slice = &mut std::mem::take(&mut slice)[mid..];
+                    continue;
+                }
+            }
+
+            let (mid, already_partitioned) =
+                PdqSort::partition_with_benchmark(slice, pivot, benchmark);
+
+            was_balanced = mid.min(len - mid) >= len / 8;
+            was_partitioned = already_partitioned;
+
+            let (left, right) = std::mem::take(&mut slice).split_at_mut(mid);
+            let (pivot, right) = right.split_first_mut().unwrap();
+            let pivot = *pivot;
+
+            if left.len() < right.len() {
+                PdqSort::recurse_with_benchmark(left, predecessor, limit, parameters, benchmark);
+                slice = right;
+                predecessor = Some(pivot);
+            } else {
+                PdqSort::recurse_with_benchmark(right, Some(pivot), limit, parameters, benchmark);
+                slice = left;
+            }
+        }
+    }
+
+    fn partition_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        pivot: usize,
+        benchmark: &mut impl Benchmark,
+    ) -> (usize, bool) {
+        benchmark.add_swap();
+        benchmark.add_writes(2);
+        slice.swap(0, pivot);
+
+        let pivot = slice[0];
+        let rest = &mut slice[1..];
+
+        let mut l = 0;
+        let mut r = rest.len();
+
+        while l < r && PdqSort::less_with_benchmark(rest[l], pivot, benchmark) {
+            l += 1;
+        }
+
+        while l < r && !PdqSort::less_with_benchmark(rest[r - 1], pivot, benchmark) {
+            r -= 1;
+        }
+
+        let was_partitioned = l >= r;
+
+        loop {
+            while l < r && PdqSort::less_with_benchmark(rest[l], pivot, benchmark) {
+                l += 1;
+            }
+
+            while l < r && !PdqSort::less_with_benchmark(rest[r - 1], pivot, benchmark) {
+                r -= 1;
+            }
+
+            if l >= r {
+                break;
+            }
+
+            r -= 1;
+            benchmark.add_swap();
+            benchmark.add_writes(2);
+            rest.swap(l, r);
+            l += 1;
+        }
+
+        benchmark.add_swap();
+        benchmark.add_writes(2);
+        slice.swap(0, l);
+
+        (l, was_partitioned)
+    }
+
+    fn partition_equal_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        pivot: usize,
+        benchmark: &mut impl Benchmark,
+    ) -> usize {
+        benchmark.add_swap();
+        benchmark.add_writes(2);
+        slice.swap(0, pivot);
+
+        let pivot = slice[0];
+        let rest = &mut slice[1..];
+
+        let mut l = 0;
+        let mut r = rest.len();
+
+        loop {
+            while l < r && !PdqSort::less_with_benchmark(pivot, rest[l], benchmark) {
+                l += 1;
+            }
+
+            while l < r && PdqSort::less_with_benchmark(pivot, rest[r - 1], benchmark) {
+                r -= 1;
+            }
+
+            if l >= r {
+                break;
+            }
+
+            r -= 1;
+            benchmark.add_swap();
+            benchmark.add_writes(2);
+            rest.swap(l, r);
+            l += 1;
+        }
+
+        l + 1
+    }
+
+    fn choose_pivot_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        ninther_len: usize,
+        benchmark: &mut impl Benchmark,
+    ) -> (usize, bool) {
+        let len = slice.len();
+
+        let mut a = len / 4;
+        let mut b = len / 4 * 2;
+        let mut c = len / 4 * 3;
+
+        let mut swaps = 0;
+
+        if len >= 8 {
+            if len >= ninther_len {
+                PdqSort::sort_adjacent_with_benchmark(slice, &mut a, &mut swaps, benchmark);
+                PdqSort::sort_adjacent_with_benchmark(slice, &mut b, &mut swaps, benchmark);
+                PdqSort::sort_adjacent_with_benchmark(slice, &mut c, &mut swaps, benchmark);
+            }
+
+            PdqSort::sort3_with_benchmark(slice, &mut a, &mut b, &mut c, &mut swaps, benchmark);
+        }
+
+        if swaps < PdqSort::MAX_SWAPS {
+            (b, swaps == 0)
+        } else {
+            for _ in 0..len / 2 {
+                benchmark.add_swap();
+                benchmark.add_writes(2);
+            }
+
+            slice.reverse();
+            (len - 1 - b, true)
+        }
+    }
+
+    fn sort_adjacent_with_benchmark<T: Ord + Copy>(
+        slice: &[T],
+        a: &mut usize,
+        swaps: &mut usize,
+        benchmark: &mut impl Benchmark,
+    ) {
+        let mut left = *a - 1;
+        let mut right = *a + 1;
+
+        PdqSort::sort3_with_benchmark(slice, &mut left, a, &mut right, swaps, benchmark);
+    }
+
+    fn sort3_with_benchmark<T: Ord + Copy>(
+        slice: &[T],
+        a: &mut usize,
+        b: &mut usize,
+        c: &mut usize,
+        swaps: &mut usize,
+        benchmark: &mut impl Benchmark,
+    ) {
+        PdqSort::sort2_with_benchmark(slice, a, b, swaps, benchmark);
+        PdqSort::sort2_with_benchmark(slice, b, c, swaps, benchmark);
+        PdqSort::sort2_with_benchmark(slice, a, b, swaps, benchmark);
+    }
+
+    fn sort2_with_benchmark<T: Ord + Copy>(
+        slice: &[T],
+        a: &mut usize,
+        b: &mut usize,
+        swaps: &mut usize,
+        benchmark: &mut impl Benchmark,
+    ) {
+        if PdqSort::less_with_benchmark(slice[*b], slice[*a], benchmark) {
+            std::mem::swap(a, b);
+            *swaps += 1;
+        }
+    }
+
+    fn partial_insertion_sort_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        benchmark: &mut impl Benchmark,
+    ) -> bool {
+        let len = slice.len();
+        let mut i = 1;
+
+        for _ in 0..PdqSort::MAX_STEPS {
+            while i < len && !PdqSort::less_with_benchmark(slice[i], slice[i - 1], benchmark) {
+                i += 1;
+            }
+
+            if i == len {
+                return true;
+            }
+
+            if len < PdqSort::SHORTEST_SHIFTING {
+                return false;
+            }
+
+            benchmark.add_swap();
+            benchmark.add_writes(2);
+            slice.swap(i - 1, i);
+
+            PdqSort::shift_tail_with_benchmark(&mut slice[..i], benchmark);
+            PdqSort::shift_head_with_benchmark(&mut slice[i..], benchmark);
+        }
+
+        false
+    }
+
+    fn shift_tail_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        let mut j = slice.len() - 1;
+        let elem = slice[j];
+
+        while j > 0 && PdqSort::less_with_benchmark(elem, slice[j - 1], benchmark) {
+            benchmark.add_swap();
+            benchmark.add_writes(1);
+            slice[j] = slice[j - 1];
+            j -= 1;
+        }
+
+        if j != slice.len() - 1 {
+            slice[j] = elem;
+            benchmark.add_writes(1);
+        }
+    }
+
+    fn shift_head_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        let mut j = 0;
+        let elem = slice[0];
+
+        while j + 1 < slice.len() && PdqSort::less_with_benchmark(slice[j + 1], elem, benchmark) {
+            benchmark.add_swap();
+            benchmark.add_writes(1);
+            slice[j] = slice[j + 1];
+            j += 1;
+        }
+
+        if j != 0 {
+            slice[j] = elem;
+            benchmark.add_writes(1);
+        }
+    }
+
+    fn break_patterns_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        benchmark: &mut impl Benchmark,
+    ) {
+        let len = slice.len();
+
+        if len < 8 {
+            return;
+        }
+
+        let mut random = len as u32;
+        let modulus = len.next_power_of_two();
+        let position = len / 4 * 2;
+
+        for i in 0..3 {
+            random ^= random << 13;
+            random ^= random >> 17;
+            random ^= random << 5;
+
+            let mut other = random as usize & (modulus - 1);
+
+            if other >= len {
+                other -= len;
+            }
+
+            benchmark.add_swap();
+            benchmark.add_writes(2);
+            slice.swap(position - 1 + i, other);
+        }
+    }
+
+    fn less_with_benchmark<T: Ord + Copy>(a: T, b: T, benchmark: &mut impl Benchmark) -> bool {
+        benchmark.add_cmp();
+
+        a < b
+    }
+}
+
+impl Sorter for PdqSort {
+    /// Pattern-defeating quicksort implementation working on types implementing
+    /// `Ord` + `Copy` so it's mostly usefull for primitive types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    ///
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// PdqSort::sort(&mut slice);
+    ///
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// ```
+    fn sort<T: Ord + Copy>(slice: &mut [T]) {
+        PdqSort::pdq_sort(slice);
+    }
+}
+
+impl BenchmarkingSorter for PdqSort {
+    /// Pattern-defeating quicksort implementation with additional benchmarking
+    /// capabilities.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::StandardBenchmarker;
+    ///
+    /// let mut benchmarker = StandardBenchmarker::default();
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// PdqSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!(4, stats.comparisons);
+    /// assert_eq!(4, stats.swaps);
+    /// ```
+    fn sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        benchmark.start_timer();
+
+        PdqSort::pdq_sort_with_benchmark(slice, benchmark);
+
+        benchmark.stop_timer();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarking::StandardBenchmarker;
+    use crate::generator::*;
+
+    crate::sorter_conformance_tests!(PdqSort);
+
+    fn comparisons(slice: &mut [u32]) -> usize {
+        let mut benchmarker = StandardBenchmarker::default();
+
+        PdqSort::sort_with_benchmark(slice, &mut benchmarker);
+
+        benchmarker.get_stats().comparisons
+    }
+
+    #[test]
+    fn pdq_sort_is_linear_on_sorted_input() {
+        let n = 100000;
+
+        assert!(comparisons(&mut AscendingGenerator::generate_seeded(n, 0)) < 2 * n);
+        assert!(comparisons(&mut (0..n as u32).collect::<Vec<_>>()) < 2 * n);
+        assert!(comparisons(&mut (0..n as u32).rev().collect::<Vec<_>>()) < 2 * n);
+    }
+
+    #[test]
+    fn pdq_sort_is_linear_on_few_distinct_values() {
+        let n = 100000;
+        let mut slice = RandomGenerator::generate_seeded(n, 0)
+            .into_iter()
+            .map(|value| value % 4)
+            .collect::<Vec<_>>();
+
+        assert!(comparisons(&mut slice) < 8 * n);
+    }
+
+    #[test]
+    fn pdq_sort_stays_n_log_n_on_patterns() {
+        let n: usize = 100000;
+        let bound = 3 * n * (usize::BITS - n.leading_zeros()) as usize;
+
+        let mut sawtooth = (0..n as u32).map(|i| i % 1000).collect::<Vec<_>>();
+        let mut organ_pipe = (0..n as u32)
+            .map(|i| i.min(n as u32 - i))
+            .collect::<Vec<_>>();
+        let mut random = RandomGenerator::generate_seeded(n, 0);
+
+        assert!(comparisons(&mut sawtooth) < bound);
+        assert!(comparisons(&mut organ_pipe) < bound);
+        assert!(comparisons(&mut random) < bound);
+    }
+
+    #[test]
+    fn heap_sort_fallback_sorts() {
+        let mut slice = RandomGenerator::generate_seeded(1000, 0);
+        let mut expected = slice.clone();
+        expected.sort();
+
+        // exhausted limit of bad partitions makes it heap sort right away
+        PdqSort::recurse(&mut slice, None, 0, Parameters::current());
+
+        assert_eq!(expected, slice);
+
+        let mut slice = RandomGenerator::generate_seeded(1000, 1);
+
+        PdqSort::recurse_with_benchmark(
+            &mut slice,
+            None,
+            0,
+            Parameters::current(),
+            &mut StandardBenchmarker::default(),
+        );
+
+        expected = RandomGenerator::generate_seeded(1000, 1);
+        expected.sort();
+
+        assert_eq!(expected, slice);
+    }
+
+    #[test]
+    fn ten_million_sorted_items_keep_stack_shallow() {
+        crate::conformance::shallow_stack_sorting_test::<PdqSort>(10_000_000);
+    }
+}
@@ -1,21 +1,85 @@
 //! Convenience re-export of common members and declaration of
 //! sorting traits.
 
-pub use crate::benchmarking::Benchmark;
-pub use crate::insertion_sort::InsertionSort;
-pub use crate::merge_sort::MergeSort;
-pub use crate::quick_sort::QuickSort;
-pub use crate::dual_pivot_quicksort::DualPivotQuicksort;
-pub use crate::timsort::TimSort;
+pub use crate::benchmarking::{AtomicBenchmarker, Benchmark};
+pub use crate::insertion_sort::{BinaryInsertionSort, InsertionSort};
+pub use crate::merge_sort::{
+    BottomUpMergeSort, InPlaceMergeSort, MergeSort, NaturalMergeSort, PingPongMergeSort,
+};
+pub use crate::quick_sort::{Partitioning, QuickSort, QuickSortWith};
+pub use crate::dual_pivot_quicksort::{
+    DualPivotPartitioning, DualPivotQuicksort, DualPivotQuicksortWith,
+};
+pub use crate::timsort::{TimSort, TimSortWith};
+pub use crate::block_sort::BlockSort;
+pub use crate::multi_pivot_quicksort::MultiPivotQuicksort;
+pub use crate::three_way_quicksort::ThreeWayQuicksort;
+pub use crate::pdqsort::PdqSort;
+pub use crate::sample_sort::SampleSort;
+pub use crate::sorting_network::NetworkSort;
+pub use crate::smooth_sort::SmoothSort;
+pub use crate::weak_heap_sort::{AdaptiveWeakHeapSort, WeakHeapSort};
+pub use crate::heap_sort::HeapSort;
+pub use crate::selection_sort::SelectionSort;
+pub use crate::cycle_sort::CycleSort;
+pub use crate::merge_insertion_sort::MergeInsertionSort;
+pub use crate::shell_sort::{GapSequence, ShellSort};
+pub use crate::radix_sort::{LsdRadixSort, MsdRadixSort, RadixKey};
+pub use crate::counting_sort::CountingSort;
+pub use crate::bucket_sort::BucketSort;
+pub use crate::adaptive_sort::{AdaptiveIntegerSort, AdaptiveSort};
 
 /// Trait for sorting algorithms.
-
 pub trait Sorter {
     fn sort<T: Ord + Copy>(slice: &mut [T]);
 }
 
 /// Trait for sorting algorithms with benchmarking capabilities.
-
 pub trait BenchmarkingSorter {
     fn sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark);
 }
+
+/// Trait for sorting algorithms working only on integer keys.
+pub trait IntegerSorter {
+    fn sort<T: RadixKey>(slice: &mut [T]);
+}
+
+/// Trait for integer sorting algorithms with benchmarking capabilities.
+pub trait BenchmarkingIntegerSorter {
+    fn sort_with_benchmark<T: RadixKey>(slice: &mut [T], benchmark: &mut impl Benchmark);
+}
+
+/// Trait for sorting algorithms using multiple threads, so items have to be
+/// safe to share and send between threads.
+pub trait ParallelSorter {
+    fn sort<T: Ord + Copy + Send + Sync>(slice: &mut [T]);
+}
+
+/// Trait for parallel sorting algorithms with benchmarking capabilities.
+/// All threads count into one shared `AtomicBenchmarker`.
+pub trait BenchmarkingParallelSorter {
+    fn sort_with_benchmark<T: Ord + Copy + Send + Sync>(
+        slice: &mut [T],
+        benchmark: &AtomicBenchmarker,
+    );
+}
+
+/// Marker trait for sorting algorithms which are stable, i.e. preserve
+/// relative order of equal elements. Generic code can require it to get
+/// stability guaranteed at compile time.
+///
+/// Examples:
+/// ```
+/// use algorithms::prelude::*;
+///
+/// fn sort_by_priority<S: StableSorter>(tasks: &mut [(u8, char)]) {
+///     S::sort(tasks);
+/// }
+///
+/// let mut tasks = [(2, 'a'), (1, 'b'), (2, 'c')];
+///
+/// sort_by_priority::<MergeSort>(&mut tasks);
+///
+/// assert_eq!([(1, 'b'), (2, 'a'), (2, 'c')], tasks);
+/// ```
+pub trait StableSorter: Sorter {}
@@ -0,0 +1,287 @@
+//! Property-based differential testing of sorters against `slice::sort`.
+//!
+//! Inputs are drawn from every `Generator` with seeded random number
+//! generators, so every failure can be reproduced from the seed alone.
+//! Failing inputs are shrunk to a minimal reproducer before being reported.
+
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+use rand::prelude::*;
+use rand_pcg::Pcg64Mcg;
+
+use crate::benchmarking::{StandardBenchmarker, Stats};
+use crate::generator::*;
+use crate::prelude::*;
+
+/// Configuration of a single property check run.
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// Number of random inputs checked.
+    pub cases: usize,
+    /// Maximal length of generated input.
+    pub max_len: usize,
+    /// Seed from which all inputs are derived.
+    pub seed: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            cases: 2000,
+            max_len: 512,
+            seed: 0x5eed,
+        }
+    }
+}
+
+/// Reason why sorter failed on given input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Failure {
+    /// Sorter panicked.
+    Panicked,
+    /// Output isn't ordered.
+    NotSorted,
+    /// Output isn't a permutation of the input.
+    NotPermutation,
+    /// Benchmarking run returned different output than `Sorter::sort`.
+    BenchmarkMismatch,
+    /// Benchmark counters exceeded bounds for given input length.
+    CountersOutOfBounds { comparisons: usize, swaps: usize },
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Panicked => write!(f, "sorter panicked"),
+            Failure::NotSorted => write!(f, "output isn't sorted"),
+            Failure::NotPermutation => write!(f, "output isn't a permutation of the input"),
+            Failure::BenchmarkMismatch => {
+                write!(f, "benchmarking sort returned different output")
+            }
+            Failure::CountersOutOfBounds { comparisons, swaps } => write!(
+                f,
+                "counters out of bounds ({} comparisons, {} swaps)",
+                comparisons, swaps
+            ),
+        }
+    }
+}
+
+/// Failing input together with its minimal reproducer.
+#[derive(Clone, Debug)]
+pub struct Counterexample {
+    /// Seed of the run which found the failure.
+    pub seed: u64,
+    /// Index of failing case in the run.
+    pub case: usize,
+    /// Input as it was generated.
+    pub original: Vec<u32>,
+    /// Input after shrinking.
+    pub shrunk: Vec<u32>,
+    /// Failure observed on shrunk input.
+    pub failure: Failure,
+}
+
+impl fmt::Display for Counterexample {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} on {:?} (shrunk from {} elements, seed {}, case {})",
+            self.failure,
+            self.shrunk,
+            self.original.len(),
+            self.seed,
+            self.case
+        )
+    }
+}
+
+/// Upper bound on comparisons and swaps for input of length `n`. It allows
+/// every pair of elements to be handled twice plus linear bookkeeping overhead
+/// (e.g. moving pivots), so every sorter in crate stays below it even in its
+/// worst case.
+pub fn counter_bound(n: usize) -> usize {
+    if n <= 1 {
+        0
+    } else {
+        n * (n + 1)
+    }
+}
+
+/// Generates input for case `case` of run seeded with `seed`. Sizes are
+/// biased towards small inputs which exercise most edge cases.
+pub fn generate_case(seed: u64, case: usize, max_len: usize) -> Vec<u32> {
+    let mut rng = Pcg64Mcg::seed_from_u64(seed ^ (case as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+
+    let n = match rng.gen_range(0..20) {
+        0..=9 => rng.gen_range(0..=max_len.min(16)),
+        10..=16 => rng.gen_range(0..=max_len.min(128)),
+        _ => rng.gen_range(0..=max_len),
+    };
+
+    match case % 3 {
+        0 => RandomGenerator::generate_with_rng(n, &mut rng),
+        1 => AscendingGenerator::generate_with_rng(n, &mut rng),
+        _ => DescendingGenerator::generate_with_rng(n, &mut rng),
+    }
+}
+
+/// Sorts copies of `input` with `S` and checks them against `slice::sort`.
+pub fn check_input<S: Sorter + BenchmarkingSorter>(input: &[u32]) -> Result<(), Failure> {
+    let mut expected = input.to_vec();
+    expected.sort();
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let mut sorted = input.to_vec();
+        S::sort(&mut sorted);
+
+        let mut benchmarked = input.to_vec();
+        let mut benchmarker = StandardBenchmarker::default();
+        S::sort_with_benchmark(&mut benchmarked, &mut benchmarker);
+
+        (sorted, benchmarked, benchmarker.get_stats())
+    }));
+
+    let (sorted, benchmarked, stats) = result.map_err(|_| Failure::Panicked)?;
+
+    check_output(&expected, &sorted)?;
+
+    if sorted != benchmarked {
+        return Err(Failure::BenchmarkMismatch);
+    }
+
+    let Stats {
+        comparisons, swaps, ..
+    } = stats;
+
+    let bound = counter_bound(input.len());
+
+    if comparisons > bound || swaps > bound {
+        return Err(Failure::CountersOutOfBounds { comparisons, swaps });
+    }
+
+    Ok(())
+}
+
+fn check_output(expected: &[u32], output: &[u32]) -> Result<(), Failure> {
+    if output.windows(2).any(|pair| pair[0] > pair[1]) {
+        return Err(Failure::NotSorted);
+    }
+
+    if output != expected {
+        return Err(Failure::NotPermutation);
+    }
+
+    Ok(())
+}
+
+/// Runs `config.cases` seeded cases against `S`. Returns shrunk
+/// counterexample for the first failing case.
+pub fn check<S: Sorter + BenchmarkingSorter>(config: &Config) -> Result<(), Counterexample> {
+    for case in 0..config.cases {
+        let input = generate_case(config.seed, case, config.max_len);
+
+        if let Err(failure) = check_input::<S>(&input) {
+            let (shrunk, failure) = shrink::<S>(input.clone(), failure);
+
+            return Err(Counterexample {
+                seed: config.seed,
+                case,
+                original: input,
+                shrunk,
+                failure,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Greedily shrinks failing `input` by removing chunks of elements and
+/// lowering values for as long as `S` keeps failing on it.
+pub fn shrink<S: Sorter + BenchmarkingSorter>(
+    mut input: Vec<u32>,
+    mut failure: Failure,
+) -> (Vec<u32>, Failure) {
+    'shrinking: loop {
+        let mut chunk = input.len();
+
+        while chunk > 0 {
+            let mut start = 0;
+
+            while start + chunk <= input.len() {
+                let mut candidate = input.clone();
+                candidate.drain(start..start + chunk);
+
+                if let Err(candidate_failure) = check_input::<S>(&candidate) {
+                    input = candidate;
+                    failure = candidate_failure;
+                    continue 'shrinking;
+                }
+
+                start += chunk;
+            }
+
+            chunk /= 2;
+        }
+
+        for i in 0..input.len() {
+            let value = input[i];
+
+            for smaller in [0, value / 2, value.saturating_sub(1)] {
+                if smaller >= value {
+                    continue;
+                }
+
+                let mut candidate = input.clone();
+                candidate[i] = smaller;
+
+                if let Err(candidate_failure) = check_input::<S>(&candidate) {
+                    input = candidate;
+                    failure = candidate_failure;
+                    continue 'shrinking;
+                }
+            }
+        }
+
+        return (input, failure);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sorter forgetting to place the last element.
+    struct BrokenSort;
+
+    impl Sorter for BrokenSort {
+        fn sort<T: Ord + Copy>(slice: &mut [T]) {
+            if let Some((_, rest)) = slice.split_last_mut() {
+                rest.sort();
+            }
+        }
+    }
+
+    impl BenchmarkingSorter for BrokenSort {
+        fn sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], _benchmark: &mut impl Benchmark) {
+            BrokenSort::sort(slice);
+        }
+    }
+
+    #[test]
+    fn failing_input_is_shrunk() {
+        let counterexample = check::<BrokenSort>(&Config::default()).unwrap_err();
+
+        assert_eq!(Failure::NotSorted, counterexample.failure);
+        assert_eq!(vec![1, 0], counterexample.shrunk);
+    }
+
+    #[test]
+    fn generated_cases_are_reproducible() {
+        for case in 0..100 {
+            assert_eq!(generate_case(42, case, 64), generate_case(42, case, 64));
+        }
+    }
+}
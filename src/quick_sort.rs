@@ -1,99 +1,517 @@
-//! Quick sort algorithm implementation.
+//! Quick sort algorithm implementation with selectable partition schemes.
+
+use std::marker::PhantomData;
 
 use crate::prelude::*;
+use crate::tuning::{Parameter, Tuning};
 
-/// Struct implementing `Sorter` + `BenchmarkingSorter` using quick sort
-/// to sort items.I t uses Lomuto partition scheme choosing last item as pivot.
-pub struct QuickSort;
+/// Scheme of partitioning slice around single pivot used by
+/// `QuickSortWith`. All schemes compare every item with pivot exactly once,
+/// so they differ only in number of swaps and in branching.
+pub trait Partitioning {
+    /// Partitions slice of at least 2 items around its last item taken as
+    /// pivot. Returns final index of pivot, items smaller than pivot are
+    /// before it, the rest after it. Schemes with tunable parameters take
+    /// them from `tuning` read at start of the sort.
+    fn partition<T: Ord + Copy>(slice: &mut [T], tuning: &Tuning) -> usize;
 
-impl QuickSort {
-    fn quick_sort<T: Ord + Copy>(slice: &mut [T]) {
-        if slice.len() <= 1 {
-            return;
-        }
+    /// Partitioning with additional benchmarking capabilities.
+    fn partition_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        tuning: &Tuning,
+        benchmark: &mut impl Benchmark,
+    ) -> usize;
+}
+
+/// Lomuto partition scheme. Single scan from the left swaps every item
+/// smaller than pivot to the end of smaller items.
+pub struct LomutoPartitioning;
 
-        let pivot_index = QuickSort::partition(slice);
+impl Partitioning for LomutoPartitioning {
+    fn partition<T: Ord + Copy>(slice: &mut [T], _tuning: &Tuning) -> usize {
+        let pivot = slice[slice.len() - 1];
 
-        QuickSort::quick_sort(&mut slice[..pivot_index]);
+        let mut l = 0;
 
-        if pivot_index < slice.len() - 1 {
-            QuickSort::quick_sort(&mut slice[pivot_index + 1..]);
+        for r in 0..slice.len() - 1 {
+            if slice[r] < pivot {
+                slice.swap(l, r);
+                l += 1;
+            }
         }
+
+        slice.swap(l, slice.len() - 1);
+
+        l
     }
 
-    fn partition<T: Ord + Copy>(slice: &mut [T]) -> usize {
+    fn partition_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        _tuning: &Tuning,
+        benchmark: &mut impl Benchmark,
+    ) -> usize {
         let pivot = slice[slice.len() - 1];
 
         let mut l = 0;
 
         for r in 0..slice.len() - 1 {
+            benchmark.add_cmp();
             if slice[r] < pivot {
+                benchmark.add_swap();
+                benchmark.add_writes(2);
                 slice.swap(l, r);
                 l += 1;
             }
         }
 
+        benchmark.add_swap();
+        benchmark.add_writes(2);
         slice.swap(l, slice.len() - 1);
 
         l
     }
+}
 
-    fn quick_sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
-        if slice.len() <= 1 {
-            return;
-        }
+/// Hoare partition scheme. Scans from both ends stop at misplaced items,
+/// which are swapped with each other, until the scans meet.
+pub struct HoarePartitioning;
 
-        let pivot_index = QuickSort::partition_with_benchmark(slice, benchmark);
+impl Partitioning for HoarePartitioning {
+    fn partition<T: Ord + Copy>(slice: &mut [T], _tuning: &Tuning) -> usize {
+        let last = slice.len() - 1;
+        let pivot = slice[last];
 
-        QuickSort::quick_sort_with_benchmark(&mut slice[..pivot_index], benchmark);
+        let mut l = 0;
+        let mut r = last;
 
-        if pivot_index < slice.len() - 1 {
-            QuickSort::quick_sort_with_benchmark(&mut slice[pivot_index + 1..], benchmark);
+        loop {
+            while l < r && slice[l] < pivot {
+                l += 1;
+            }
+
+            // item at `l` was already compared, if it's not past `r`
+            while l + 1 < r && slice[r - 1] >= pivot {
+                r -= 1;
+            }
+
+            if l + 1 >= r {
+                break;
+            }
+
+            slice.swap(l, r - 1);
+            l += 1;
+            r -= 1;
         }
+
+        slice.swap(l, last);
+
+        l
     }
 
-    fn partition_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) -> usize {
-        let pivot = slice[slice.len() - 1];
+    fn partition_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        _tuning: &Tuning,
+        benchmark: &mut impl Benchmark,
+    ) -> usize {
+        let last = slice.len() - 1;
+        let pivot = slice[last];
 
         let mut l = 0;
+        let mut r = last;
 
-        for r in 0..slice.len() - 1 {
-            benchmark.add_cmp();
-            if slice[r] < pivot {
-                benchmark.add_swap();
-                slice.swap(l, r);
+        loop {
+            while l < r && {
+                benchmark.add_cmp();
+                slice[l] < pivot
+            } {
                 l += 1;
             }
+
+            // item at `l` was already compared, if it's not past `r`
+            while l + 1 < r && {
+                benchmark.add_cmp();
+                slice[r - 1] >= pivot
+            } {
+                r -= 1;
+            }
+
+            if l + 1 >= r {
+                break;
+            }
+
+            benchmark.add_swap();
+            benchmark.add_writes(2);
+            slice.swap(l, r - 1);
+            l += 1;
+            r -= 1;
         }
 
         benchmark.add_swap();
-        slice.swap(l, slice.len() - 1);
+        benchmark.add_writes(2);
+        slice.swap(l, last);
 
         l
     }
 }
 
-impl Sorter for QuickSort {
+/// BlockQuicksort partition scheme. Results of comparisons in blocks from
+/// both ends are stored as offsets of misplaced items without branching on
+/// them, then misplaced items from both blocks are swapped pairwise, so
+/// the only branches depending on data are loop conditions.
+pub struct BlockPartitioning;
+
+impl BlockPartitioning {
+    /// Maximal number of items in one block, offsets in it fit into `u8`.
+    /// Blocks have `Parameter::BlockPartitioningBlock` items.
+    const MAX_BLOCK: usize = 256;
+}
+
+impl Partitioning for BlockPartitioning {
+    fn partition<T: Ord + Copy>(slice: &mut [T], tuning: &Tuning) -> usize {
+        let last = slice.len() - 1;
+        let pivot = slice[last];
+
+        let block = tuning.get(Parameter::BlockPartitioningBlock);
+
+        let mut l = 0;
+        let mut block_l = block;
+        let mut offsets_l = [0u8; BlockPartitioning::MAX_BLOCK];
+        let (mut start_l, mut end_l) = (0, 0);
+
+        let mut r = last;
+        let mut block_r = block;
+        let mut offsets_r = [0u8; BlockPartitioning::MAX_BLOCK];
+        let (mut start_r, mut end_r) = (0, 0);
+
+        loop {
+            let is_done = r - l <= 2 * block;
+
+            if is_done {
+                // the rest is divided between blocks, block with offsets
+                // left from previous round is kept whole
+                let mut rest = r - l;
+
+                if start_l < end_l || start_r < end_r {
+                    rest -= block;
+                }
+
+                if start_l < end_l {
+                    block_r = rest;
+                } else if start_r < end_r {
+                    block_l = rest;
+                } else {
+                    block_l = rest / 2;
+                    block_r = rest - block_l;
+                }
+            }
+
+            if start_l == end_l {
+                start_l = 0;
+                end_l = 0;
+
+                for i in 0..block_l {
+                    offsets_l[end_l] = i as u8;
+                    end_l += (slice[l + i] >= pivot) as usize;
+                }
+            }
+
+            if start_r == end_r {
+                start_r = 0;
+                end_r = 0;
+
+                for i in 0..block_r {
+                    offsets_r[end_r] = i as u8;
+                    end_r += (slice[r - 1 - i] < pivot) as usize;
+                }
+            }
+
+            let count = (end_l - start_l).min(end_r - start_r);
+
+            for k in 0..count {
+                slice.swap(
+                    l + offsets_l[start_l + k] as usize,
+                    r - 1 - offsets_r[start_r + k] as usize,
+                );
+            }
+
+            start_l += count;
+            start_r += count;
+
+            if start_l == end_l {
+                l += block_l;
+            }
+
+            if start_r == end_r {
+                r -= block_r;
+            }
+
+            if is_done {
+                break;
+            }
+        }
+
+        // at most one block has misplaced items left, they are moved
+        // to the side of the other block
+        let mid = if start_l < end_l {
+            while start_l < end_l {
+                end_l -= 1;
+                slice.swap(l + offsets_l[end_l] as usize, r - 1);
+                r -= 1;
+            }
+
+            r
+        } else {
+            while start_r < end_r {
+                end_r -= 1;
+                slice.swap(l, r - 1 - offsets_r[end_r] as usize);
+                l += 1;
+            }
+
+            l
+        };
+
+        slice.swap(mid, last);
+
+        mid
+    }
+
+    fn partition_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        tuning: &Tuning,
+        benchmark: &mut impl Benchmark,
+    ) -> usize {
+        let last = slice.len() - 1;
+        let pivot = slice[last];
+
+        let block = tuning.get(Parameter::BlockPartitioningBlock);
+
+        let mut l = 0;
+        let mut block_l = block;
+        let mut offsets_l = [0u8; BlockPartitioning::MAX_BLOCK];
+        let (mut start_l, mut end_l) = (0, 0);
+
+        let mut r = last;
+        let mut block_r = block;
+        let mut offsets_r = [0u8; BlockPartitioning::MAX_BLOCK];
+        let (mut start_r, mut end_r) = (0, 0);
+
+        loop {
+            let is_done = r - l <= 2 * block;
+
+            if is_done {
+                // the rest is divided between blocks, block with offsets
+                // left from previous round is kept whole
+                let mut rest = r - l;
+
+                if start_l < end_l || start_r < end_r {
+                    rest -= block;
+                }
+
+                if start_l < end_l {
+                    block_r = rest;
+                } else if start_r < end_r {
+                    block_l = rest;
+                } else {
+                    block_l = rest / 2;
+                    block_r = rest - block_l;
+                }
+            }
+
+            if start_l == end_l {
+                start_l = 0;
+                end_l = 0;
+
+                for i in 0..block_l {
+                    benchmark.add_cmp();
+                    offsets_l[end_l] = i as u8;
+                    end_l += (slice[l + i] >= pivot) as usize;
+                }
+            }
+
+            if start_r == end_r {
+                start_r = 0;
+                end_r = 0;
+
+                for i in 0..block_r {
+                    benchmark.add_cmp();
+                    offsets_r[end_r] = i as u8;
+                    end_r += (slice[r - 1 - i] < pivot) as usize;
+                }
+            }
+
+            let count = (end_l - start_l).min(end_r - start_r);
+
+            for k in 0..count {
+                benchmark.add_swap();
+                benchmark.add_writes(2);
+                slice.swap(
+                    l + offsets_l[start_l + k] as usize,
+                    r - 1 - offsets_r[start_r + k] as usize,
+                );
+            }
+
+            start_l += count;
+            start_r += count;
+
+            if start_l == end_l {
+                l += block_l;
+            }
+
+            if start_r == end_r {
+                r -= block_r;
+            }
+
+            if is_done {
+                break;
+            }
+        }
+
+        // at most one block has misplaced items left, they are moved
+        // to the side of the other block
+        let mid = if start_l < end_l {
+            while start_l < end_l {
+                end_l -= 1;
+                benchmark.add_swap();
+                benchmark.add_writes(2);
+                slice.swap(l + offsets_l[end_l] as usize, r - 1);
+                r -= 1;
+            }
+
+            r
+        } else {
+            while start_r < end_r {
+                end_r -= 1;
+                benchmark.add_swap();
+                benchmark.add_writes(2);
+                slice.swap(l, r - 1 - offsets_r[end_r] as usize);
+                l += 1;
+            }
+
+            l
+        };
+
+        benchmark.add_swap();
+        benchmark.add_writes(2);
+        slice.swap(mid, last);
+
+        mid
+    }
+}
+
+/// Returns number of partitioning levels after which quicksorts of slice
+/// with `len` items fall back to heap sort, i.e. `2 log2 len`.
+pub(crate) fn depth_limit(len: usize) -> u32 {
+    2 * len.max(1).ilog2()
+}
+
+/// Struct implementing `Sorter` + `BenchmarkingSorter` using quick sort
+/// to sort items. It chooses last item as pivot and partitions slice
+/// using partition scheme `P`. Recursion goes into the shorter side only,
+/// so stack depth is `O(log n)` on every input. Like in introsort, parts
+/// still unsorted after `2 log2 n` levels of partitioning are sorted by
+/// `HeapSort`, so inputs where fixed pivot splits badly, e.g. sorted input,
+/// take `O(n log n)` time too.
+pub struct QuickSortWith<P> {
+    partitioning: PhantomData<P>,
+}
+
+/// Quick sort using Lomuto partition scheme.
+pub type QuickSort = QuickSortWith<LomutoPartitioning>;
+
+impl<P: Partitioning> QuickSortWith<P> {
+    fn quick_sort<T: Ord + Copy>(slice: &mut [T]) {
+        let limit = depth_limit(slice.len());
+
+        QuickSortWith::<P>::introsort(slice, limit, &Tuning::current());
+    }
+
+    fn introsort<T: Ord + Copy>(mut slice: &mut [T], mut limit: u32, tuning: &Tuning) {
+        while slice.len() > 1 {
+            if limit == 0 {
+                HeapSort::sort(slice);
+                return;
+            }
+
+            limit -= 1;
+
+            let pivot_index = P::partition(slice, tuning);
+
+            let (left, right) = std::mem::take(&mut slice).split_at_mut(pivot_index);
+            let right = &mut right[1..];
+
+            // recursing only into shorter side keeps stack depth logarithmic
+            if left.len() < right.len() {
+                QuickSortWith::<P>::introsort(left, limit, tuning);
+                slice = right;
+            } else {
+                QuickSortWith::<P>::introsort(right, limit, tuning);
+                slice = left;
+            }
+        }
+    }
+
+    fn quick_sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        let limit = depth_limit(slice.len());
+
+        QuickSortWith::<P>::introsort_with_benchmark(slice, limit, &Tuning::current(), benchmark);
+    }
+
+    fn introsort_with_benchmark<T: Ord + Copy>(
+        mut slice: &mut [T],
+        mut limit: u32,
+        tuning: &Tuning,
+        benchmark: &mut impl Benchmark,
+    ) {
+        while slice.len() > 1 {
+            if limit == 0 {
+                HeapSort::heap_sort_with_benchmark(slice, benchmark);
+                return;
+            }
+
+            limit -= 1;
+
+            let pivot_index = P::partition_with_benchmark(slice, tuning, benchmark);
+
+            let (left, right) = std::mem::take(&mut slice).split_at_mut(pivot_index);
+            let right = &mut right[1..];
+
+            // recursing only into shorter side keeps stack depth logarithmic
+            if left.len() < right.len() {
+                QuickSortWith::<P>::introsort_with_benchmark(left, limit, tuning, benchmark);
+                slice = right;
+            } else {
+                QuickSortWith::<P>::introsort_with_benchmark(right, limit, tuning, benchmark);
+                slice = left;
+            }
+        }
+    }
+}
+
+impl<P: Partitioning> Sorter for QuickSortWith<P> {
     /// Quick sort implementation working on types implementing
     /// `Ord` + `Copy` so it's mostly usefull for primitive types.
-    /// It uses Lomuto partition scheme choosing last item as pivot.
+    /// It chooses last item as pivot.
     ///
     /// Examples:
     /// ```
     /// use algorithms::prelude::*;
+    /// use algorithms::quick_sort::BlockPartitioning;
     ///
     /// let mut slice = [1, 8, 2, 3, 9, 5];
+    /// let mut block_slice = slice;
     ///
     /// QuickSort::sort(&mut slice);
+    /// QuickSortWith::<BlockPartitioning>::sort(&mut block_slice);
     ///
     /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// assert_eq!([1, 2, 3, 5, 8, 9], block_slice);
     /// ```
     fn sort<T: Ord + Copy>(slice: &mut [T]) {
-        QuickSort::quick_sort(slice);
+        QuickSortWith::<P>::quick_sort(slice);
     }
 }
 
-impl BenchmarkingSorter for QuickSort {
+impl<P: Partitioning> BenchmarkingSorter for QuickSortWith<P> {
     /// Quick sort implementation with additional benchmarking capabilities.
     ///
     /// Examples:
@@ -114,7 +532,7 @@ impl BenchmarkingSorter for QuickSort {
     fn sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
         benchmark.start_timer();
 
-        QuickSort::quick_sort_with_benchmark(slice, benchmark);
+        QuickSortWith::<P>::quick_sort_with_benchmark(slice, benchmark);
 
         benchmark.stop_timer();
     }
@@ -123,25 +541,103 @@ impl BenchmarkingSorter for QuickSort {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tests::*;
+    use crate::benchmarking::StandardBenchmarker;
+    use crate::generator::*;
+
+    crate::sorter_conformance_tests!(QuickSort);
+
+    mod hoare {
+        use super::*;
+
+        crate::sorter_conformance_tests!(QuickSortWith<HoarePartitioning>);
+    }
+
+    mod block {
+        use super::*;
+
+        crate::sorter_conformance_tests!(QuickSortWith<BlockPartitioning>);
+    }
+
+    fn check_partition<P: Partitioning>(input: &[u32]) -> (usize, usize, usize) {
+        let pivot = input[input.len() - 1];
+        let mut plain = input.to_vec();
+        let mut benchmarked = input.to_vec();
+        let mut benchmarker = StandardBenchmarker::default();
+
+        let index = P::partition(&mut plain, &Tuning::DEFAULT);
+
+        assert_eq!(
+            index,
+            P::partition_with_benchmark(&mut benchmarked, &Tuning::DEFAULT, &mut benchmarker)
+        );
+        assert_eq!(plain, benchmarked);
+        assert_eq!(pivot, plain[index]);
+        assert!(plain[..index].iter().all(|&item| item < pivot));
+        assert!(plain[index + 1..].iter().all(|&item| item >= pivot));
+
+        let mut sorted_input = input.to_vec();
+        let mut sorted_output = plain;
+
+        sorted_input.sort();
+        sorted_output.sort();
+
+        assert_eq!(sorted_input, sorted_output);
+
+        let stats = benchmarker.get_stats();
+
+        (index, stats.comparisons, stats.swaps)
+    }
 
     #[test]
-    fn quick_sort_basic_sorting_test() {
-        basic_sorting_test::<QuickSort>();
+    fn partitionings_compare_every_item_once() {
+        for n in [2, 3, 10, 255, 256, 257, 300, 1000, 4097] {
+            for seed in 0..5 {
+                let mut input = RandomGenerator::generate_seeded(n, seed);
+
+                if seed == 4 {
+                    input.iter_mut().for_each(|item| *item %= 3);
+                }
+
+                let (lomuto_index, lomuto_comps, _) = check_partition::<LomutoPartitioning>(&input);
+                let (hoare_index, hoare_comps, _) = check_partition::<HoarePartitioning>(&input);
+                let (block_index, block_comps, _) = check_partition::<BlockPartitioning>(&input);
+
+                assert_eq!(n - 1, lomuto_comps);
+                assert_eq!(lomuto_comps, hoare_comps);
+                assert_eq!(lomuto_comps, block_comps);
+                assert_eq!(lomuto_index, hoare_index);
+                assert_eq!(lomuto_index, block_index);
+            }
+        }
     }
 
     #[test]
-    fn quick_sort_empty_sorting_test() {
-        empty_sorting_test::<QuickSort>();
+    fn block_partitioning_swaps_less_than_lomuto() {
+        let input = RandomGenerator::generate_seeded(10000, 0);
+
+        let (_, _, lomuto_swaps) = check_partition::<LomutoPartitioning>(&input);
+        let (_, _, block_swaps) = check_partition::<BlockPartitioning>(&input);
+
+        assert!(2 * block_swaps < lomuto_swaps);
     }
 
     #[test]
-    fn quick_sort_sorted_sorting_test() {
-        sorted_sorting_test::<QuickSort>();
+    fn ten_million_sorted_items_keep_stack_shallow() {
+        use crate::conformance::shallow_stack_sorting_test;
+
+        shallow_stack_sorting_test::<QuickSort>(10_000_000);
+        shallow_stack_sorting_test::<QuickSortWith<HoarePartitioning>>(10_000_000);
+        shallow_stack_sorting_test::<QuickSortWith<BlockPartitioning>>(10_000_000);
     }
 
     #[test]
-    fn quick_sort_sorted_backwards_sorting_test() {
-        sorted_backwards_sorting_test::<QuickSort>();
+    fn sorted_input_falls_back_to_heap_sort() {
+        let n = 100000;
+        let mut slice = (0..n as u32).collect::<Vec<_>>();
+        let mut benchmarker = StandardBenchmarker::default();
+
+        QuickSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+
+        assert!(benchmarker.get_stats().comparisons < 2 * depth_limit(n) as usize * n);
     }
 }
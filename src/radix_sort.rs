@@ -0,0 +1,544 @@
+//! Radix sort algorithms implementation for integer keys.
+
+use std::mem;
+
+use crate::prelude::*;
+use crate::tuning::Parameter;
+
+/// Trait for integer types which can be sorted digit by digit. `key` has to
+/// map values to unsigned keys preserving their order and `from_key` has
+/// to be its inverse.
+pub trait RadixKey: Ord + Copy {
+    /// Number of significant bits of the key.
+    const BITS: u32;
+
+    fn key(self) -> u64;
+
+    fn from_key(key: u64) -> Self;
+}
+
+macro_rules! impl_radix_key_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl RadixKey for $t {
+                const BITS: u32 = <$t>::BITS;
+
+                fn key(self) -> u64 {
+                    self as u64
+                }
+
+                fn from_key(key: u64) -> Self {
+                    key as $t
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_radix_key_signed {
+    ($($t:ty => $u:ty),*) => {
+        $(
+            impl RadixKey for $t {
+                const BITS: u32 = <$t>::BITS;
+
+                fn key(self) -> u64 {
+                    // flipping sign bit moves negative numbers below positive ones
+                    ((self as $u) ^ (1 << (<$u>::BITS - 1))) as u64
+                }
+
+                fn from_key(key: u64) -> Self {
+                    ((key as $u) ^ (1 << (<$u>::BITS - 1))) as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_radix_key_unsigned!(u8, u16, u32, u64, usize);
+impl_radix_key_signed!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, isize => usize);
+
+/// Turns counts of items per key into offsets where items with that key
+/// start, i.e. replaces every count with sum of counts before it.
+pub(crate) fn counts_to_offsets(counts: &mut [usize]) {
+    let mut offset = 0;
+
+    for count in counts.iter_mut() {
+        let next_offset = offset + *count;
+        *count = offset;
+        offset = next_offset;
+    }
+}
+
+/// Struct implementing `IntegerSorter` + `BenchmarkingIntegerSorter` using
+/// least significant digit radix sort. Every pass distributes elements by
+/// `RADIX_BITS` bits wide digit between input slice and single buffer
+/// allocated up front (ping-pong buffers).
+pub struct LsdRadixSort<const RADIX_BITS: u32>;
+
+impl<const RADIX_BITS: u32> LsdRadixSort<RADIX_BITS> {
+    fn lsd_radix_sort<T: RadixKey>(slice: &mut [T]) {
+        assert!(
+            (1..=16).contains(&RADIX_BITS),
+            "radix bits have to be in range 1..=16"
+        );
+
+        if slice.len() <= 1 {
+            return;
+        }
+
+        let mut buffer = slice.to_vec();
+        let mut counts = vec![0; 1 << RADIX_BITS];
+        let mut in_buffer = false;
+
+        for shift in (0..T::BITS).step_by(RADIX_BITS as usize) {
+            let distributed = if in_buffer {
+                LsdRadixSort::<RADIX_BITS>::distribute(&buffer, slice, &mut counts, shift)
+            } else {
+                LsdRadixSort::<RADIX_BITS>::distribute(slice, &mut buffer, &mut counts, shift)
+            };
+
+            if distributed {
+                in_buffer = !in_buffer;
+            }
+        }
+
+        if in_buffer {
+            slice.copy_from_slice(&buffer);
+        }
+    }
+
+    /// Stably distributes `source` into `destination` by digit at `shift`.
+    /// Returns `false` without moving anything if all digits are equal.
+    fn distribute<T: RadixKey>(
+        source: &[T],
+        destination: &mut [T],
+        counts: &mut [usize],
+        shift: u32,
+    ) -> bool {
+        counts.fill(0);
+
+        for &value in source {
+            counts[LsdRadixSort::<RADIX_BITS>::digit(value, shift)] += 1;
+        }
+
+        if counts.contains(&source.len()) {
+            return false;
+        }
+
+        counts_to_offsets(counts);
+
+        for &value in source {
+            let digit = LsdRadixSort::<RADIX_BITS>::digit(value, shift);
+
+            destination[counts[digit]] = value;
+            counts[digit] += 1;
+        }
+
+        true
+    }
+
+    fn lsd_radix_sort_with_benchmark<T: RadixKey>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        assert!(
+            (1..=16).contains(&RADIX_BITS),
+            "radix bits have to be in range 1..=16"
+        );
+
+        if slice.len() <= 1 {
+            return;
+        }
+
+        let mut buffer = slice.to_vec();
+        let mut counts = vec![0; 1 << RADIX_BITS];
+        let mut in_buffer = false;
+
+        benchmark.add_allocation(mem::size_of_val(slice));
+        benchmark.add_allocation(counts.len() * mem::size_of::<usize>());
+
+        for shift in (0..T::BITS).step_by(RADIX_BITS as usize) {
+            let distributed = if in_buffer {
+                LsdRadixSort::<RADIX_BITS>::distribute_with_benchmark(
+                    &buffer,
+                    slice,
+                    &mut counts,
+                    shift,
+                    benchmark,
+                )
+            } else {
+                LsdRadixSort::<RADIX_BITS>::distribute_with_benchmark(
+                    slice,
+                    &mut buffer,
+                    &mut counts,
+                    shift,
+                    benchmark,
+                )
+            };
+
+            if distributed {
+                in_buffer = !in_buffer;
+            }
+        }
+
+        if in_buffer {
+            slice.copy_from_slice(&buffer);
+
+            for _ in 0..slice.len() {
+                benchmark.add_move();
+                benchmark.add_writes(1);
+            }
+        }
+    }
+
+    fn distribute_with_benchmark<T: RadixKey>(
+        source: &[T],
+        destination: &mut [T],
+        counts: &mut [usize],
+        shift: u32,
+        benchmark: &mut impl Benchmark,
+    ) -> bool {
+        counts.fill(0);
+
+        benchmark.add_pass();
+
+        for &value in source {
+            counts[LsdRadixSort::<RADIX_BITS>::digit(value, shift)] += 1;
+        }
+
+        if counts.contains(&source.len()) {
+            return false;
+        }
+
+        benchmark.add_pass();
+
+        counts_to_offsets(counts);
+
+        for &value in source {
+            let digit = LsdRadixSort::<RADIX_BITS>::digit(value, shift);
+
+            destination[counts[digit]] = value;
+            counts[digit] += 1;
+
+            benchmark.add_move();
+            benchmark.add_writes(1);
+        }
+
+        true
+    }
+
+    fn digit<T: RadixKey>(value: T, shift: u32) -> usize {
+        ((value.key() >> shift) & ((1 << RADIX_BITS) - 1)) as usize
+    }
+}
+
+impl<const RADIX_BITS: u32> IntegerSorter for LsdRadixSort<RADIX_BITS> {
+    /// LSD radix sort implementation working on integer types. Panics
+    /// if `RADIX_BITS` isn't in range `1..=16`.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    ///
+    /// let mut slice = [1, -8, 2, 3, 9, -5];
+    ///
+    /// LsdRadixSort::<8>::sort(&mut slice);
+    ///
+    /// assert_eq!([-8, -5, 1, 2, 3, 9], slice);
+    /// ```
+    fn sort<T: RadixKey>(slice: &mut [T]) {
+        LsdRadixSort::<RADIX_BITS>::lsd_radix_sort(slice);
+    }
+}
+
+impl<const RADIX_BITS: u32> BenchmarkingIntegerSorter for LsdRadixSort<RADIX_BITS> {
+    /// LSD radix sort implementation with additional benchmarking capabilities.
+    /// Every scan over data is counted as a pass and every element written
+    /// to or from buffer as a move.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::StandardBenchmarker;
+    ///
+    /// let mut benchmarker = StandardBenchmarker::default();
+    /// let mut slice: [u16; 6] = [1, 8, 2, 3, 9, 5];
+    ///
+    /// LsdRadixSort::<8>::sort_with_benchmark(&mut slice, &mut benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!(0, stats.comparisons);
+    /// assert_eq!(3, stats.passes);
+    /// assert_eq!(12, stats.moves);
+    /// assert_eq!(12 + 256 * 8, stats.allocated_bytes);
+    /// ```
+    fn sort_with_benchmark<T: RadixKey>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        benchmark.start_timer();
+
+        LsdRadixSort::<RADIX_BITS>::lsd_radix_sort_with_benchmark(slice, benchmark);
+
+        benchmark.stop_timer();
+    }
+}
+
+/// Struct implementing `IntegerSorter` + `BenchmarkingIntegerSorter` using
+/// in-place most significant digit radix sort (American flag sort) with
+/// byte sized digits. Small buckets are sorted by insertion sort.
+pub struct MsdRadixSort;
+
+impl MsdRadixSort {
+    const RADIX_BITS: u32 = 8;
+
+    fn msd_radix_sort<T: RadixKey>(slice: &mut [T], shift: u32, insertion_len: usize) {
+        if slice.len() <= insertion_len {
+            InsertionSort::sort(slice);
+            return;
+        }
+
+        let mut counts = [0; 1 << MsdRadixSort::RADIX_BITS];
+
+        for &value in slice.iter() {
+            counts[MsdRadixSort::digit(value, shift)] += 1;
+        }
+
+        let (mut heads, tails) = MsdRadixSort::bucket_bounds(&counts);
+
+        for bucket in 0..counts.len() {
+            while heads[bucket] < tails[bucket] {
+                let digit = MsdRadixSort::digit(slice[heads[bucket]], shift);
+
+                if digit == bucket {
+                    heads[bucket] += 1;
+                } else {
+                    slice.swap(heads[bucket], heads[digit]);
+                    heads[digit] += 1;
+                }
+            }
+        }
+
+        if shift == 0 {
+            return;
+        }
+
+        let mut start = 0;
+
+        for count in counts {
+            MsdRadixSort::msd_radix_sort(
+                &mut slice[start..start + count],
+                shift - MsdRadixSort::RADIX_BITS,
+                insertion_len,
+            );
+
+            start += count;
+        }
+    }
+
+    fn msd_radix_sort_with_benchmark<T: RadixKey>(
+        slice: &mut [T],
+        shift: u32,
+        insertion_len: usize,
+        benchmark: &mut impl Benchmark,
+    ) {
+        if slice.len() <= insertion_len {
+            InsertionSort::insertion_sort_with_benchmark(slice, benchmark);
+            return;
+        }
+
+        let mut counts = [0; 1 << MsdRadixSort::RADIX_BITS];
+
+        benchmark.add_pass();
+
+        for &value in slice.iter() {
+            counts[MsdRadixSort::digit(value, shift)] += 1;
+        }
+
+        let (mut heads, tails) = MsdRadixSort::bucket_bounds(&counts);
+
+        benchmark.add_pass();
+
+        for bucket in 0..counts.len() {
+            while heads[bucket] < tails[bucket] {
+                let digit = MsdRadixSort::digit(slice[heads[bucket]], shift);
+
+                if digit == bucket {
+                    heads[bucket] += 1;
+                } else {
+                    slice.swap(heads[bucket], heads[digit]);
+                    heads[digit] += 1;
+
+                    benchmark.add_swap();
+                    benchmark.add_writes(2);
+                }
+            }
+        }
+
+        if shift == 0 {
+            return;
+        }
+
+        let mut start = 0;
+
+        for count in counts {
+            MsdRadixSort::msd_radix_sort_with_benchmark(
+                &mut slice[start..start + count],
+                shift - MsdRadixSort::RADIX_BITS,
+                insertion_len,
+                benchmark,
+            );
+
+            start += count;
+        }
+    }
+
+    fn digit<T: RadixKey>(value: T, shift: u32) -> usize {
+        ((value.key() >> shift) & ((1 << MsdRadixSort::RADIX_BITS) - 1)) as usize
+    }
+
+    fn bucket_bounds(counts: &[usize; 256]) -> ([usize; 256], [usize; 256]) {
+        let mut heads = [0; 256];
+        let mut tails = [0; 256];
+
+        let mut offset = 0;
+
+        for bucket in 0..counts.len() {
+            heads[bucket] = offset;
+            offset += counts[bucket];
+            tails[bucket] = offset;
+        }
+
+        (heads, tails)
+    }
+
+    fn top_shift<T: RadixKey>() -> u32 {
+        T::BITS.saturating_sub(MsdRadixSort::RADIX_BITS)
+    }
+}
+
+impl IntegerSorter for MsdRadixSort {
+    /// MSD radix sort implementation working on integer types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    ///
+    /// let mut slice = [1, -8, 2, 3, 9, -5];
+    ///
+    /// MsdRadixSort::sort(&mut slice);
+    ///
+    /// assert_eq!([-8, -5, 1, 2, 3, 9], slice);
+    /// ```
+    fn sort<T: RadixKey>(slice: &mut [T]) {
+        MsdRadixSort::msd_radix_sort(
+            slice,
+            MsdRadixSort::top_shift::<T>(),
+            Parameter::MsdRadixInsertionLen.get(),
+        );
+    }
+}
+
+impl BenchmarkingIntegerSorter for MsdRadixSort {
+    /// MSD radix sort implementation with additional benchmarking capabilities.
+    /// Counting and permuting scans of every bucket are counted as passes.
+    /// It doesn't allocate any buffers.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::StandardBenchmarker;
+    ///
+    /// let mut benchmarker = StandardBenchmarker::default();
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// MsdRadixSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!(0, stats.passes);
+    /// assert_eq!(0, stats.allocated_bytes);
+    /// ```
+    fn sort_with_benchmark<T: RadixKey>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        benchmark.start_timer();
+
+        MsdRadixSort::msd_radix_sort_with_benchmark(
+            slice,
+            MsdRadixSort::top_shift::<T>(),
+            Parameter::MsdRadixInsertionLen.get(),
+            benchmark,
+        );
+
+        benchmark.stop_timer();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarking::StandardBenchmarker;
+
+    mod lsd_radix_sort {
+        use super::*;
+
+        crate::integer_sorter_conformance_tests!(LsdRadixSort<8>);
+    }
+
+    mod lsd_radix_sort_narrow_digits {
+        use super::*;
+
+        crate::integer_sorter_conformance_tests!(LsdRadixSort<3>);
+    }
+
+    mod lsd_radix_sort_wide_digits {
+        use super::*;
+
+        crate::integer_sorter_conformance_tests!(LsdRadixSort<16>);
+    }
+
+    mod msd_radix_sort {
+        use super::*;
+
+        crate::integer_sorter_conformance_tests!(MsdRadixSort);
+    }
+
+    #[test]
+    fn signed_keys_preserve_order() {
+        let values = [i32::MIN, -1, 0, 1, i32::MAX];
+
+        for pair in values.windows(2) {
+            assert!(pair[0].key() < pair[1].key());
+        }
+    }
+
+    #[test]
+    fn from_key_inverts_key() {
+        for value in [i64::MIN, -1, 0, 1, i64::MAX] {
+            assert_eq!(value, i64::from_key(value.key()));
+        }
+
+        for value in [i8::MIN, -1, 0, 1, i8::MAX] {
+            assert_eq!(value, i8::from_key(value.key()));
+        }
+
+        for value in [0, 1, u64::MAX] {
+            assert_eq!(value, u64::from_key(value.key()));
+        }
+    }
+
+    #[test]
+    fn lsd_radix_sort_skips_passes_over_equal_digits() {
+        let mut benchmarker = StandardBenchmarker::default();
+        let mut slice: Vec<u64> = (0..200).rev().collect();
+
+        LsdRadixSort::<8>::sort_with_benchmark(&mut slice, &mut benchmarker);
+
+        let stats = benchmarker.get_stats();
+
+        // one counting and one distributing pass for lowest byte,
+        // only counting passes for remaining seven bytes
+        assert_eq!(9, stats.passes);
+        assert_eq!(400, stats.moves);
+    }
+
+    #[test]
+    #[should_panic(expected = "radix bits")]
+    fn lsd_radix_sort_rejects_zero_radix_bits() {
+        LsdRadixSort::<0>::sort(&mut [2, 1]);
+    }
+}
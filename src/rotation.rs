@@ -0,0 +1,285 @@
+//! In-place rotation algorithms.
+//!
+//! Every function rotates `slice` to the left by `mid` places, just like
+//! `slice::rotate_left`, so that `slice[mid]` becomes first item. Panics
+//! if `mid` is bigger than slice length.
+
+use crate::benchmarking::Benchmark;
+
+/// Rotates by reversing both parts and then the whole slice.
+///
+/// Examples:
+/// ```
+/// use algorithms::rotation;
+///
+/// let mut slice = [1, 2, 3, 4, 5];
+///
+/// rotation::reversal_rotate(&mut slice, 2);
+///
+/// assert_eq!([3, 4, 5, 1, 2], slice);
+/// ```
+pub fn reversal_rotate<T: Copy>(slice: &mut [T], mid: usize) {
+    slice[..mid].reverse();
+    slice[mid..].reverse();
+    slice.reverse();
+}
+
+/// Reversal rotation counting every swap.
+pub fn reversal_rotate_with_benchmark<T: Copy>(
+    slice: &mut [T],
+    mid: usize,
+    benchmark: &mut impl Benchmark,
+) {
+    reverse_with_benchmark(&mut slice[..mid], benchmark);
+    reverse_with_benchmark(&mut slice[mid..], benchmark);
+    reverse_with_benchmark(slice, benchmark);
+}
+
+fn reverse_with_benchmark<T: Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+    for _ in 0..slice.len() / 2 {
+        benchmark.add_swap();
+        benchmark.add_writes(2);
+    }
+
+    slice.reverse();
+}
+
+/// Rotates by moving items along `gcd(mid, len)` cycles, writing every item
+/// exactly once.
+///
+/// Examples:
+/// ```
+/// use algorithms::rotation;
+///
+/// let mut slice = [1, 2, 3, 4, 5];
+///
+/// rotation::juggling_rotate(&mut slice, 2);
+///
+/// assert_eq!([3, 4, 5, 1, 2], slice);
+/// ```
+pub fn juggling_rotate<T: Copy>(slice: &mut [T], mid: usize) {
+    let len = slice.len();
+
+    assert!(mid <= len, "mid out of bounds");
+
+    if mid == 0 || mid == len {
+        return;
+    }
+
+    for start in 0..gcd(mid, len) {
+        let elem = slice[start];
+        let mut i = start;
+
+        loop {
+            let mut next = i + mid;
+
+            if next >= len {
+                next -= len;
+            }
+
+            if next == start {
+                break;
+            }
+
+            slice[i] = slice[next];
+            i = next;
+        }
+
+        slice[i] = elem;
+    }
+}
+
+/// Juggling rotation counting every item write as a move.
+pub fn juggling_rotate_with_benchmark<T: Copy>(
+    slice: &mut [T],
+    mid: usize,
+    benchmark: &mut impl Benchmark,
+) {
+    let len = slice.len();
+
+    assert!(mid <= len, "mid out of bounds");
+
+    if mid == 0 || mid == len {
+        return;
+    }
+
+    for start in 0..gcd(mid, len) {
+        let elem = slice[start];
+        let mut i = start;
+
+        loop {
+            let mut next = i + mid;
+
+            if next >= len {
+                next -= len;
+            }
+
+            if next == start {
+                break;
+            }
+
+            slice[i] = slice[next];
+            i = next;
+
+            benchmark.add_move();
+            benchmark.add_writes(1);
+        }
+
+        slice[i] = elem;
+
+        benchmark.add_move();
+        benchmark.add_writes(1);
+    }
+}
+
+fn gcd(mut a: usize, mut b: usize) -> usize {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    a
+}
+
+/// Rotates by repeatedly swapping the shorter part with equally long end
+/// of the longer one (Gries-Mills block swap).
+///
+/// Examples:
+/// ```
+/// use algorithms::rotation;
+///
+/// let mut slice = [1, 2, 3, 4, 5];
+///
+/// rotation::block_swap_rotate(&mut slice, 2);
+///
+/// assert_eq!([3, 4, 5, 1, 2], slice);
+/// ```
+pub fn block_swap_rotate<T: Copy>(slice: &mut [T], mid: usize) {
+    assert!(mid <= slice.len(), "mid out of bounds");
+
+    let mut start = 0;
+    let mut left = mid;
+    let mut right = slice.len() - mid;
+
+    while left > 0 && right > 0 {
+        if left < right {
+            swap_blocks(slice, start, start + right, left);
+            right -= left;
+        } else {
+            swap_blocks(slice, start, start + left, right);
+            start += right;
+            left -= right;
+        }
+    }
+}
+
+/// Block swap rotation counting every swap.
+pub fn block_swap_rotate_with_benchmark<T: Copy>(
+    slice: &mut [T],
+    mid: usize,
+    benchmark: &mut impl Benchmark,
+) {
+    assert!(mid <= slice.len(), "mid out of bounds");
+
+    let mut start = 0;
+    let mut left = mid;
+    let mut right = slice.len() - mid;
+
+    while left > 0 && right > 0 {
+        if left < right {
+            swap_blocks_with_benchmark(slice, start, start + right, left, benchmark);
+            right -= left;
+        } else {
+            swap_blocks_with_benchmark(slice, start, start + left, right, benchmark);
+            start += right;
+            left -= right;
+        }
+    }
+}
+
+fn swap_blocks<T: Copy>(slice: &mut [T], a: usize, b: usize, len: usize) {
+    for i in 0..len {
+        slice.swap(a + i, b + i);
+    }
+}
+
+fn swap_blocks_with_benchmark<T: Copy>(
+    slice: &mut [T],
+    a: usize,
+    b: usize,
+    len: usize,
+    benchmark: &mut impl Benchmark,
+) {
+    for i in 0..len {
+        slice.swap(a + i, b + i);
+
+        benchmark.add_swap();
+        benchmark.add_writes(2);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarking::StandardBenchmarker;
+
+    fn rotation_test(rotate: fn(&mut [u32], usize)) {
+        for len in [0, 1, 2, 3, 7, 12, 30] {
+            for mid in 0..=len {
+                let mut slice = (0..len as u32).collect::<Vec<_>>();
+                let mut expected = slice.clone();
+                expected.rotate_left(mid);
+
+                rotate(&mut slice, mid);
+
+                assert_eq!(expected, slice, "len {}, mid {}", len, mid);
+            }
+        }
+    }
+
+    #[test]
+    fn reversal_rotate_test() {
+        rotation_test(reversal_rotate);
+        rotation_test(|slice, mid| {
+            reversal_rotate_with_benchmark(slice, mid, &mut StandardBenchmarker::default())
+        });
+    }
+
+    #[test]
+    fn juggling_rotate_test() {
+        rotation_test(juggling_rotate);
+        rotation_test(|slice, mid| {
+            juggling_rotate_with_benchmark(slice, mid, &mut StandardBenchmarker::default())
+        });
+    }
+
+    #[test]
+    fn block_swap_rotate_test() {
+        rotation_test(block_swap_rotate);
+        rotation_test(|slice, mid| {
+            block_swap_rotate_with_benchmark(slice, mid, &mut StandardBenchmarker::default())
+        });
+    }
+
+    #[test]
+    fn rotation_counters_test() {
+        let mut slice = (0..12).collect::<Vec<_>>();
+
+        let mut benchmarker = StandardBenchmarker::default();
+        reversal_rotate_with_benchmark(&mut slice, 4, &mut benchmarker);
+        assert_eq!(2 + 4 + 6, benchmarker.get_stats().swaps);
+
+        let mut benchmarker = StandardBenchmarker::default();
+        juggling_rotate_with_benchmark(&mut slice, 4, &mut benchmarker);
+        assert_eq!(12, benchmarker.get_stats().moves);
+
+        let mut benchmarker = StandardBenchmarker::default();
+        block_swap_rotate_with_benchmark(&mut slice, 4, &mut benchmarker);
+        assert_eq!(8, benchmarker.get_stats().swaps);
+    }
+
+    #[test]
+    #[should_panic(expected = "mid out of bounds")]
+    fn rotation_rejects_mid_out_of_bounds() {
+        block_swap_rotate(&mut [1, 2, 3], 4);
+    }
+}
@@ -0,0 +1,1358 @@
+//! Samplesort algorithm implementations: super scalar samplesort and its
+//! in-place parallel variant (IPS4o).
+
+use std::mem;
+use std::sync::Mutex;
+use std::thread;
+
+use crate::benchmarking::{AtomicBenchmarker, NestedBenchmarker};
+use crate::prelude::*;
+use crate::tuning::Parameter;
+
+/// Values of tunable parameters used by one sort. They're read once at its
+/// start, as classification and permutation of blocks rely on them staying
+/// the same.
+#[derive(Clone, Copy)]
+struct Parameters {
+    /// Slices up to this length are sorted using insertion sort.
+    base_case_len: usize,
+    /// Binary logarithm of maximal number of buckets.
+    max_log_buckets: u32,
+    /// Number of items in blocks moved by `Ips4oSort`.
+    block_len: usize,
+}
+
+impl Parameters {
+    fn current<T>() -> Self {
+        let block_bytes = Parameter::Ips4oBlockBytes.get();
+
+        Parameters {
+            base_case_len: Parameter::SampleSortBaseCaseLen.get(),
+            max_log_buckets: Parameter::SampleSortMaxLogBuckets.get() as u32,
+            block_len: (block_bytes / mem::size_of::<T>().max(1)).max(1),
+        }
+    }
+}
+
+/// Decision tree classifying items into buckets by splitters taken from
+/// sorted sample. Tree is stored implicitly in array, children of node `i`
+/// are nodes `2 * i` and `2 * i + 1`, so item descends the tree without
+/// branching on result of comparison. When sample contains duplicates,
+/// items equal to splitters get equality buckets of their own, which don't
+/// need any further sorting.
+struct Classifier<T> {
+    tree: Vec<T>,
+    splitters: Vec<T>,
+    log_buckets: u32,
+    equal_buckets: bool,
+}
+
+impl<T: Ord + Copy> Classifier<T> {
+    fn new(splitters: Vec<T>, log_buckets: u32, equal_buckets: bool) -> Self {
+        let mut tree = vec![splitters[0]; 1 << log_buckets];
+
+        Classifier::build_tree(&mut tree, &splitters, 1);
+
+        Classifier {
+            tree,
+            splitters,
+            log_buckets,
+            equal_buckets,
+        }
+    }
+
+    fn build_tree(tree: &mut [T], splitters: &[T], node: usize) {
+        if node >= tree.len() {
+            return;
+        }
+
+        let mid = splitters.len() / 2;
+
+        tree[node] = splitters[mid];
+
+        Classifier::build_tree(tree, &splitters[..mid], 2 * node);
+        Classifier::build_tree(tree, &splitters[mid + 1..], 2 * node + 1);
+    }
+
+    /// Number of classes items are classified into. Class `2 * i` is bucket
+    /// of items between splitters `i - 1` and `i`, class `2 * i + 1` holds
+    /// items equal to splitter `i`.
+    fn classes(&self) -> usize {
+        2 * self.tree.len() - 1
+    }
+
+    fn classify(&self, value: T) -> usize {
+        let mut node = 1;
+
+        for _ in 0..self.log_buckets {
+            node = 2 * node + (self.tree[node] < value) as usize;
+        }
+
+        let bucket = node - self.tree.len();
+
+        if self.equal_buckets && bucket < self.splitters.len() && value == self.splitters[bucket] {
+            2 * bucket + 1
+        } else {
+            2 * bucket
+        }
+    }
+
+    fn classify_with_benchmark(&self, value: T, benchmark: &mut impl Benchmark) -> usize {
+        let mut node = 1;
+
+        for _ in 0..self.log_buckets {
+            benchmark.add_cmp();
+
+            node = 2 * node + (self.tree[node] < value) as usize;
+        }
+
+        let bucket = node - self.tree.len();
+
+        if self.equal_buckets && bucket < self.splitters.len() {
+            benchmark.add_cmp();
+
+            if value == self.splitters[bucket] {
+                return 2 * bucket + 1;
+            }
+        }
+
+        2 * bucket
+    }
+}
+
+/// Returns next number of xorshift64 generator.
+fn next_random(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+
+    *state
+}
+
+/// Returns index of class with most items among classes needing sorting.
+fn largest_bucket(bounds: &[usize]) -> usize {
+    (0..bounds.len() - 1)
+        .step_by(2)
+        .max_by_key(|&class| bounds[class + 1] - bounds[class])
+        .unwrap()
+}
+
+/// Moves random oversampled sample to the front of slice, sorts it and
+/// builds classifier with `2^k` buckets from its evenly spaced items.
+/// Slice has to be longer than base case length of `parameters`.
+fn build_classifier<T: Ord + Copy>(slice: &mut [T], parameters: Parameters) -> Classifier<T> {
+    let len = slice.len();
+    let log_buckets = (len / parameters.base_case_len)
+        .ilog2()
+        .clamp(1, parameters.max_log_buckets);
+    let buckets = 1 << log_buckets;
+    // at least two items per bucket, so that distinct sample always splits
+    // the slice into at least two buckets
+    let oversampling = (len.ilog2() as usize / 4).max(2);
+    let sample_len = oversampling * buckets - 1;
+
+    let mut random = len as u64;
+
+    for i in 0..sample_len {
+        let other = i + next_random(&mut random) as usize % (len - i);
+
+        slice.swap(i, other);
+    }
+
+    let sample = &mut slice[..sample_len];
+
+    PdqSort::sort(sample);
+
+    let splitters = (1..buckets)
+        .map(|i| sample[i * oversampling - 1])
+        .collect::<Vec<_>>();
+
+    let equal_buckets = sample.windows(2).any(|pair| pair[0] == pair[1]);
+
+    Classifier::new(splitters, log_buckets, equal_buckets)
+}
+
+/// Moves random oversampled sample to the front of slice, sorts it and
+/// builds classifier with `2^k` buckets from its evenly spaced items.
+/// Slice has to be longer than base case length of `parameters`.
+fn build_classifier_with_benchmark<T: Ord + Copy>(
+    slice: &mut [T],
+    parameters: Parameters,
+    benchmark: &mut impl Benchmark,
+) -> Classifier<T> {
+    let len = slice.len();
+    let log_buckets = (len / parameters.base_case_len)
+        .ilog2()
+        .clamp(1, parameters.max_log_buckets);
+    let buckets = 1 << log_buckets;
+    // at least two items per bucket, so that distinct sample always splits
+    // the slice into at least two buckets
+    let oversampling = (len.ilog2() as usize / 4).max(2);
+    let sample_len = oversampling * buckets - 1;
+
+    let mut random = len as u64;
+
+    for i in 0..sample_len {
+        let other = i + next_random(&mut random) as usize % (len - i);
+
+        benchmark.add_swap();
+        benchmark.add_writes(2);
+        slice.swap(i, other);
+    }
+
+    let sample = &mut slice[..sample_len];
+
+    PdqSort::sort_with_benchmark(sample, &mut NestedBenchmarker::new(benchmark));
+
+    let splitters = (1..buckets)
+        .map(|i| sample[i * oversampling - 1])
+        .collect::<Vec<_>>();
+
+    let equal_buckets = sample.windows(2).any(|pair| {
+        benchmark.add_cmp();
+        pair[0] == pair[1]
+    });
+
+    benchmark.add_allocation(mem::size_of_val(&splitters[..]));
+    benchmark.add_allocation(mem::size_of::<T>() << log_buckets);
+
+    Classifier::new(splitters, log_buckets, equal_buckets)
+}
+
+/// Buffers of one thread of `Ips4oSort`, reused for all slices it
+/// classifies. After classification of stripe, its first `written` items
+/// are full blocks and the rest of its items stays in partially filled
+/// buffers, one per class.
+struct LocalBuffers<T> {
+    buffers: Vec<Vec<T>>,
+    counts: Vec<usize>,
+    written: usize,
+}
+
+impl<T> LocalBuffers<T> {
+    fn new() -> Self {
+        LocalBuffers {
+            buffers: Vec::new(),
+            counts: Vec::new(),
+            written: 0,
+        }
+    }
+}
+
+/// Struct implementing `Sorter` + `BenchmarkingSorter` using super scalar
+/// samplesort. Buckets are chosen by splitters from oversampled random
+/// sample and items are classified by branch-free decision tree, then
+/// distributed into buckets through buffer and buckets are sorted
+/// recursively.
+pub struct SampleSort;
+
+impl SampleSort {
+    fn sample_sort<T: Ord + Copy>(slice: &mut [T]) {
+        let parameters = Parameters::current::<T>();
+
+        if slice.len() <= parameters.base_case_len {
+            InsertionSort::sort(slice);
+            return;
+        }
+
+        let mut buffer = slice.to_vec();
+        let mut oracle = vec![0; slice.len()];
+
+        SampleSort::sort_buckets(slice, &mut buffer, &mut oracle, parameters);
+    }
+
+    fn sort_buckets<T: Ord + Copy>(
+        mut slice: &mut [T],
+        mut buffer: &mut [T],
+        mut oracle: &mut [u8],
+        parameters: Parameters,
+    ) {
+        loop {
+            if slice.len() <= parameters.base_case_len {
+                InsertionSort::sort(slice);
+                return;
+            }
+
+            let classifier = build_classifier(slice, parameters);
+            let bounds = SampleSort::distribute(slice, buffer, oracle, &classifier);
+            let largest = largest_bucket(&bounds);
+
+            // recursing only into shorter buckets keeps stack depth logarithmic
+            for class in (0..classifier.classes())
+                .step_by(2)
+                .filter(|&class| class != largest)
+            {
+                let (start, end) = (bounds[class], bounds[class + 1]);
+
+                SampleSort::sort_buckets(
+                    &mut slice[start..end],
+                    &mut buffer[start..end],
+                    &mut oracle[start..end],
+                    parameters,
+                );
+            }
+
+            let (start, end) = (bounds[largest], bounds[largest + 1]);
+
+            slice = &mut mem::take(&mut slice)[start..end];
+            buffer = &mut mem::take(&mut buffer)[start..end];
+            oracle = &mut mem::take(&mut oracle)[start..end];
+        }
+    }
+
+    /// Classifies all items remembering their classes in `oracle`, then
+    /// moves them into their buckets through `buffer`. Returns bounds of
+    /// buckets.
+    fn distribute<T: Ord + Copy>(
+        slice: &mut [T],
+        buffer: &mut [T],
+        oracle: &mut [u8],
+        classifier: &Classifier<T>,
+    ) -> Vec<usize> {
+        let mut bounds = vec![0; classifier.classes() + 1];
+
+        for (&value, class) in slice.iter().zip(oracle.iter_mut()) {
+            *class = classifier.classify(value) as u8;
+            bounds[*class as usize + 1] += 1;
+        }
+
+        for i in 1..bounds.len() {
+            bounds[i] += bounds[i - 1];
+        }
+
+        let mut next = bounds.clone();
+
+        for (&value, &class) in slice.iter().zip(oracle.iter()) {
+            buffer[next[class as usize]] = value;
+            next[class as usize] += 1;
+        }
+
+        slice.copy_from_slice(buffer);
+
+        bounds
+    }
+
+    fn sample_sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        let parameters = Parameters::current::<T>();
+
+        if slice.len() <= parameters.base_case_len {
+            InsertionSort::insertion_sort_with_benchmark(slice, benchmark);
+            return;
+        }
+
+        let mut buffer = slice.to_vec();
+        let mut oracle = vec![0; slice.len()];
+
+        benchmark.add_allocation(mem::size_of_val(&buffer[..]));
+        benchmark.add_allocation(mem::size_of_val(&oracle[..]));
+
+        SampleSort::sort_buckets_with_benchmark(
+            slice,
+            &mut buffer,
+            &mut oracle,
+            parameters,
+            benchmark,
+        );
+    }
+
+    fn sort_buckets_with_benchmark<T: Ord + Copy>(
+        mut slice: &mut [T],
+        mut buffer: &mut [T],
+        mut oracle: &mut [u8],
+        parameters: Parameters,
+        benchmark: &mut impl Benchmark,
+    ) {
+        loop {
+            if slice.len() <= parameters.base_case_len {
+                InsertionSort::insertion_sort_with_benchmark(slice, benchmark);
+                return;
+            }
+
+            let classifier = build_classifier_with_benchmark(slice, parameters, benchmark);
+            let bounds = SampleSort::distribute_with_benchmark(
+                slice,
+                buffer,
+                oracle,
+                &classifier,
+                benchmark,
+            );
+            let largest = largest_bucket(&bounds);
+
+            // recursing only into shorter buckets keeps stack depth logarithmic
+            for class in (0..classifier.classes())
+                .step_by(2)
+                .filter(|&class| class != largest)
+            {
+                let (start, end) = (bounds[class], bounds[class + 1]);
+
+                SampleSort::sort_buckets_with_benchmark(
+                    &mut slice[start..end],
+                    &mut buffer[start..end],
+                    &mut oracle[start..end],
+                    parameters,
+                    benchmark,
+                );
+            }
+
+            let (start, end) = (bounds[largest], bounds[largest + 1]);
+
+            slice = &mut mem::take(&mut slice)[start..end];
+            buffer = &mut mem::take(&mut buffer)[start..end];
+            oracle = &mut mem::take(&mut oracle)[start..end];
+        }
+    }
+
+    /// Classifies all items remembering their classes in `oracle`, then
+    /// moves them into their buckets through `buffer`. Returns bounds of
+    /// buckets.
+    fn distribute_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        buffer: &mut [T],
+        oracle: &mut [u8],
+        classifier: &Classifier<T>,
+        benchmark: &mut impl Benchmark,
+    ) -> Vec<usize> {
+        let mut bounds = vec![0; classifier.classes() + 1];
+
+        benchmark.add_allocation(mem::size_of_val(&bounds[..]));
+        benchmark.add_pass();
+
+        for (&value, class) in slice.iter().zip(oracle.iter_mut()) {
+            benchmark.add_scan();
+
+            *class = classifier.classify_with_benchmark(value, benchmark) as u8;
+            bounds[*class as usize + 1] += 1;
+        }
+
+        for i in 1..bounds.len() {
+            bounds[i] += bounds[i - 1];
+        }
+
+        let mut next = bounds.clone();
+
+        benchmark.add_allocation(mem::size_of_val(&next[..]));
+        benchmark.add_pass();
+
+        for (&value, &class) in slice.iter().zip(oracle.iter()) {
+            benchmark.add_scan();
+            benchmark.add_move();
+            benchmark.add_writes(1);
+
+            buffer[next[class as usize]] = value;
+            next[class as usize] += 1;
+        }
+
+        for _ in 0..slice.len() {
+            benchmark.add_move();
+            benchmark.add_writes(1);
+        }
+
+        slice.copy_from_slice(buffer);
+
+        bounds
+    }
+}
+
+impl Sorter for SampleSort {
+    /// Super scalar samplesort implementation working on types implementing
+    /// `Ord` + `Copy` so it's mostly usefull for primitive types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    ///
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// SampleSort::sort(&mut slice);
+    ///
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// ```
+    fn sort<T: Ord + Copy>(slice: &mut [T]) {
+        SampleSort::sample_sort(slice);
+    }
+}
+
+impl BenchmarkingSorter for SampleSort {
+    /// Super scalar samplesort implementation with additional benchmarking
+    /// capabilities. Classification and distribution of every slice are
+    /// counted as passes.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::StandardBenchmarker;
+    ///
+    /// let mut benchmarker = StandardBenchmarker::default();
+    /// let mut slice = (0..100).rev().collect::<Vec<_>>();
+    ///
+    /// SampleSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!((0..100).collect::<Vec<_>>(), slice);
+    /// assert_eq!(16, stats.passes);
+    /// ```
+    fn sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        benchmark.start_timer();
+
+        SampleSort::sample_sort_with_benchmark(slice, benchmark);
+
+        benchmark.stop_timer();
+    }
+}
+
+/// Struct implementing `ParallelSorter` + `BenchmarkingParallelSorter` using
+/// in-place parallel super scalar samplesort (IPS4o) on `THREADS` threads.
+/// Every thread classifies its stripe of slice into small buffers, which
+/// are flushed as blocks back into the stripe. Blocks are then permuted
+/// into their buckets and buckets are sorted by threads taking them from
+/// shared queue. Apart from buffers, no memory proportional to length of
+/// slice is needed.
+pub struct Ips4oSort<const THREADS: usize>;
+
+impl<const THREADS: usize> Ips4oSort<THREADS> {
+    fn ips4o<T: Ord + Copy + Send + Sync>(slice: &mut [T]) {
+        let parameters = Parameters::current::<T>();
+        let block_len = parameters.block_len;
+        let mut block = Vec::with_capacity(block_len);
+
+        if THREADS == 1 || slice.len() <= parameters.base_case_len * THREADS {
+            Ips4oSort::<THREADS>::sequential(
+                slice,
+                &mut LocalBuffers::new(),
+                &mut block,
+                parameters,
+            );
+            return;
+        }
+
+        let classifier = build_classifier(slice, parameters);
+        let stripe_len = slice.len().div_ceil(THREADS).next_multiple_of(block_len);
+
+        let locals = thread::scope(|scope| {
+            let workers = slice
+                .chunks_mut(stripe_len)
+                .map(|stripe| {
+                    let classifier = &classifier;
+
+                    scope.spawn(move || {
+                        let mut local = LocalBuffers::new();
+
+                        Ips4oSort::<THREADS>::classify_stripe(
+                            stripe, &mut local, classifier, block_len,
+                        );
+
+                        local
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            workers
+                .into_iter()
+                .map(|worker| worker.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+
+        let bounds = Ips4oSort::<THREADS>::permute_blocks(
+            slice,
+            &classifier,
+            &locals,
+            stripe_len,
+            block_len,
+            &mut block,
+        );
+
+        Ips4oSort::<THREADS>::sort_buckets(slice, &bounds, parameters);
+    }
+
+    /// Sorts buckets given by `bounds` on all threads, which take buckets
+    /// from shared queue, the largest first.
+    fn sort_buckets<T: Ord + Copy + Send + Sync>(
+        slice: &mut [T],
+        bounds: &[usize],
+        parameters: Parameters,
+    ) {
+        let mut buckets = Vec::new();
+        let mut rest = slice;
+
+        for class in 0..bounds.len() - 1 {
+            let (bucket, tail) =
+                mem::take(&mut rest).split_at_mut(bounds[class + 1] - bounds[class]);
+            rest = tail;
+
+            // odd classes are equality buckets, which are already sorted
+            if class % 2 == 0 && bucket.len() > 1 {
+                buckets.push(bucket);
+            }
+        }
+
+        buckets.sort_by_key(|bucket| bucket.len());
+
+        let queue = Mutex::new(buckets);
+
+        thread::scope(|scope| {
+            for _ in 0..THREADS {
+                scope.spawn(|| {
+                    let mut local = LocalBuffers::new();
+                    let mut block = Vec::with_capacity(parameters.block_len);
+                    let next_bucket = || queue.lock().unwrap().pop();
+
+                    while let Some(bucket) = next_bucket() {
+                        Ips4oSort::<THREADS>::sequential(
+                            bucket, &mut local, &mut block, parameters,
+                        );
+                    }
+                });
+            }
+        });
+    }
+
+    /// Sorts slice on current thread, reusing its buffers for all buckets.
+    fn sequential<T: Ord + Copy>(
+        mut slice: &mut [T],
+        local: &mut LocalBuffers<T>,
+        block: &mut Vec<T>,
+        parameters: Parameters,
+    ) {
+        loop {
+            if slice.len() <= parameters.base_case_len {
+                InsertionSort::sort(slice);
+                return;
+            }
+
+            let classifier = build_classifier(slice, parameters);
+
+            Ips4oSort::<THREADS>::classify_stripe(slice, local, &classifier, parameters.block_len);
+
+            let bounds = Ips4oSort::<THREADS>::permute_blocks(
+                slice,
+                &classifier,
+                std::slice::from_ref(local),
+                slice.len(),
+                parameters.block_len,
+                block,
+            );
+            let largest = largest_bucket(&bounds);
+
+            // recursing only into shorter buckets keeps stack depth logarithmic
+            for class in (0..classifier.classes())
+                .step_by(2)
+                .filter(|&class| class != largest)
+            {
+                Ips4oSort::<THREADS>::sequential(
+                    &mut slice[bounds[class]..bounds[class + 1]],
+                    local,
+                    block,
+                    parameters,
+                );
+            }
+
+            slice = &mut mem::take(&mut slice)[bounds[largest]..bounds[largest + 1]];
+        }
+    }
+
+    /// Classifies items of stripe into buffers of `block_len` items, one
+    /// per class. Every full buffer is flushed as block to the front of
+    /// stripe, where items were already read.
+    fn classify_stripe<T: Ord + Copy>(
+        stripe: &mut [T],
+        local: &mut LocalBuffers<T>,
+        classifier: &Classifier<T>,
+        block_len: usize,
+    ) {
+        let classes = classifier.classes();
+        let mut read = 0;
+
+        while local.buffers.len() < classes {
+            local.buffers.push(Vec::with_capacity(block_len));
+        }
+
+        for buffer in &mut local.buffers {
+            buffer.clear();
+        }
+
+        local.counts.clear();
+        local.counts.resize(classes, 0);
+        local.written = 0;
+
+        while read < stripe.len() {
+            let value = stripe[read];
+            let class = classifier.classify(value);
+            let buffer = &mut local.buffers[class];
+
+            local.counts[class] += 1;
+            buffer.push(value);
+            read += 1;
+
+            if buffer.len() == block_len {
+                stripe[local.written..local.written + block_len].copy_from_slice(buffer);
+                buffer.clear();
+                local.written += block_len;
+            }
+        }
+    }
+
+    /// Moves full blocks of all stripes into their buckets, then fills
+    /// the rest of buckets with items of partial buffers and items of
+    /// blocks reaching into next bucket. Blocks have `block_len` items and
+    /// `block` is used for swapping them. Returns bounds of buckets.
+    fn permute_blocks<T: Ord + Copy>(
+        slice: &mut [T],
+        classifier: &Classifier<T>,
+        locals: &[LocalBuffers<T>],
+        stripe_len: usize,
+        block_len: usize,
+        block: &mut Vec<T>,
+    ) -> Vec<usize> {
+        let len = slice.len();
+        let mut bounds = vec![0; classifier.classes() + 1];
+
+        for local in locals {
+            for (class, count) in local.counts.iter().enumerate() {
+                bounds[class + 1] += count;
+            }
+        }
+
+        for i in 1..bounds.len() {
+            bounds[i] += bounds[i - 1];
+        }
+
+        // slots of `block_len` items holding full blocks, which weren't moved yet
+        let mut full = vec![false; len.div_ceil(block_len)];
+
+        for (i, local) in locals.iter().enumerate() {
+            let first = i * stripe_len / block_len;
+
+            full[first..first + local.written / block_len].fill(true);
+        }
+
+        // blocks of every bucket are written from first aligned slot in it
+        let mut next = bounds
+            .iter()
+            .map(|&bound| bound.next_multiple_of(block_len))
+            .collect::<Vec<_>>();
+        let mut overflow = Vec::new();
+        let mut overflow_class = usize::MAX;
+
+        for slot in 0..full.len() {
+            if !full[slot] {
+                continue;
+            }
+
+            full[slot] = false;
+
+            block.clear();
+            block.extend_from_slice(&slice[slot * block_len..(slot + 1) * block_len]);
+
+            loop {
+                let class = classifier.classify(block[0]);
+                let dest = next[class];
+
+                next[class] += block_len;
+
+                // only the last slot may reach past the end of slice
+                if dest + block_len > len {
+                    let fits = len - dest;
+
+                    slice[dest..].copy_from_slice(&block[..fits]);
+                    overflow.extend_from_slice(&block[fits..]);
+                    overflow_class = class;
+                    break;
+                }
+
+                if full[dest / block_len] {
+                    full[dest / block_len] = false;
+
+                    slice[dest..dest + block_len].swap_with_slice(block);
+                } else {
+                    slice[dest..dest + block_len].copy_from_slice(block);
+                    break;
+                }
+            }
+        }
+
+        for class in 0..bounds.len() - 1 {
+            let (start, end) = (bounds[class], bounds[class + 1]);
+            let blocks_start = start.next_multiple_of(block_len);
+            let blocks_end = next[class].min(len);
+
+            // items of the last block reaching into next bucket
+            block.clear();
+
+            if blocks_end > end.max(blocks_start) {
+                block.extend_from_slice(&slice[end..blocks_end]);
+            }
+
+            let overflow = if class == overflow_class {
+                &overflow[..]
+            } else {
+                &[]
+            };
+
+            let items = block
+                .iter()
+                .chain(locals.iter().flat_map(|local| &local.buffers[class]))
+                .chain(overflow);
+            let positions = (start..blocks_start.min(end)).chain(blocks_end..end);
+
+            for (position, &value) in positions.zip(items) {
+                slice[position] = value;
+            }
+        }
+
+        bounds
+    }
+
+    fn ips4o_with_benchmark<T: Ord + Copy + Send + Sync>(
+        slice: &mut [T],
+        benchmark: &AtomicBenchmarker,
+    ) {
+        let mut counters = benchmark;
+        let parameters = Parameters::current::<T>();
+        let block_len = parameters.block_len;
+        let mut block = Vec::with_capacity(block_len);
+
+        counters.add_allocation(block_len * mem::size_of::<T>());
+
+        if THREADS == 1 || slice.len() <= parameters.base_case_len * THREADS {
+            Ips4oSort::<THREADS>::sequential_with_benchmark(
+                slice,
+                &mut LocalBuffers::new(),
+                &mut block,
+                parameters,
+                &mut counters,
+            );
+            return;
+        }
+
+        let classifier = build_classifier_with_benchmark(slice, parameters, &mut counters);
+        let stripe_len = slice.len().div_ceil(THREADS).next_multiple_of(block_len);
+
+        let locals = thread::scope(|scope| {
+            let workers = slice
+                .chunks_mut(stripe_len)
+                .map(|stripe| {
+                    let classifier = &classifier;
+
+                    scope.spawn(move || {
+                        let mut benchmark = benchmark;
+                        let mut local = LocalBuffers::new();
+
+                        Ips4oSort::<THREADS>::classify_stripe_with_benchmark(
+                            stripe,
+                            &mut local,
+                            classifier,
+                            block_len,
+                            &mut benchmark,
+                        );
+
+                        local
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            workers
+                .into_iter()
+                .map(|worker| worker.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+
+        let bounds = Ips4oSort::<THREADS>::permute_blocks_with_benchmark(
+            slice,
+            &classifier,
+            &locals,
+            stripe_len,
+            block_len,
+            &mut block,
+            &mut counters,
+        );
+
+        Ips4oSort::<THREADS>::sort_buckets_with_benchmark(slice, &bounds, parameters, benchmark);
+    }
+
+    /// Sorts buckets given by `bounds` on all threads, which take buckets
+    /// from shared queue, the largest first.
+    fn sort_buckets_with_benchmark<T: Ord + Copy + Send + Sync>(
+        slice: &mut [T],
+        bounds: &[usize],
+        parameters: Parameters,
+        benchmark: &AtomicBenchmarker,
+    ) {
+        let mut buckets = Vec::new();
+        let mut rest = slice;
+
+        for class in 0..bounds.len() - 1 {
+            let (bucket, tail) =
+                mem::take(&mut rest).split_at_mut(bounds[class + 1] - bounds[class]);
+            rest = tail;
+
+            // odd classes are equality buckets, which are already sorted
+            if class % 2 == 0 && bucket.len() > 1 {
+                buckets.push(bucket);
+            }
+        }
+
+        buckets.sort_by_key(|bucket| bucket.len());
+
+        let queue = Mutex::new(buckets);
+
+        thread::scope(|scope| {
+            for _ in 0..THREADS {
+                scope.spawn(|| {
+                    let mut benchmark = benchmark;
+                    let mut local = LocalBuffers::new();
+                    let mut block = Vec::with_capacity(parameters.block_len);
+                    let next_bucket = || queue.lock().unwrap().pop();
+
+                    benchmark.add_allocation(parameters.block_len * mem::size_of::<T>());
+
+                    while let Some(bucket) = next_bucket() {
+                        Ips4oSort::<THREADS>::sequential_with_benchmark(
+                            bucket,
+                            &mut local,
+                            &mut block,
+                            parameters,
+                            &mut benchmark,
+                        );
+                    }
+                });
+            }
+        });
+    }
+
+    /// Sorts slice on current thread, reusing its buffers for all buckets.
+    fn sequential_with_benchmark<T: Ord + Copy>(
+        mut slice: &mut [T],
+        local: &mut LocalBuffers<T>,
+        block: &mut Vec<T>,
+        parameters: Parameters,
+        benchmark: &mut impl Benchmark,
+    ) {
+        loop {
+            if slice.len() <= parameters.base_case_len {
+                InsertionSort::insertion_sort_with_benchmark(slice, benchmark);
+                return;
+            }
+
+            let classifier = build_classifier_with_benchmark(slice, parameters, benchmark);
+
+            Ips4oSort::<THREADS>::classify_stripe_with_benchmark(
+                slice,
+                local,
+                &classifier,
+                parameters.block_len,
+                benchmark,
+            );
+
+            let bounds = Ips4oSort::<THREADS>::permute_blocks_with_benchmark(
+                slice,
+                &classifier,
+                std::slice::from_ref(local),
+                slice.len(),
+                parameters.block_len,
+                block,
+                benchmark,
+            );
+            let largest = largest_bucket(&bounds);
+
+            // recursing only into shorter buckets keeps stack depth logarithmic
+            for class in (0..classifier.classes())
+                .step_by(2)
+                .filter(|&class| class != largest)
+            {
+                Ips4oSort::<THREADS>::sequential_with_benchmark(
+                    &mut slice[bounds[class]..bounds[class + 1]],
+                    local,
+                    block,
+                    parameters,
+                    benchmark,
+                );
+            }
+
+            slice = &mut mem::take(&mut slice)[bounds[largest]..bounds[largest + 1]];
+        }
+    }
+
+    /// Classifies items of stripe into buffers of `block_len` items, one
+    /// per class. Every full buffer is flushed as block to the front of
+    /// stripe, where items were already read.
+    fn classify_stripe_with_benchmark<T: Ord + Copy>(
+        stripe: &mut [T],
+        local: &mut LocalBuffers<T>,
+        classifier: &Classifier<T>,
+        block_len: usize,
+        benchmark: &mut impl Benchmark,
+    ) {
+        let classes = classifier.classes();
+        let mut read = 0;
+
+        while local.buffers.len() < classes {
+            benchmark.add_allocation(block_len * mem::size_of::<T>());
+
+            local.buffers.push(Vec::with_capacity(block_len));
+        }
+
+        for buffer in &mut local.buffers {
+            buffer.clear();
+        }
+
+        local.counts.clear();
+        local.counts.resize(classes, 0);
+        local.written = 0;
+
+        benchmark.add_pass();
+
+        while read < stripe.len() {
+            let value = stripe[read];
+            let class = classifier.classify_with_benchmark(value, benchmark);
+            let buffer = &mut local.buffers[class];
+
+            benchmark.add_scan();
+            benchmark.add_move();
+            benchmark.add_writes(1);
+
+            local.counts[class] += 1;
+            buffer.push(value);
+            read += 1;
+
+            if buffer.len() == block_len {
+                for _ in 0..block_len {
+                    benchmark.add_move();
+                    benchmark.add_writes(1);
+                }
+
+                stripe[local.written..local.written + block_len].copy_from_slice(buffer);
+                buffer.clear();
+                local.written += block_len;
+            }
+        }
+    }
+
+    /// Moves full blocks of all stripes into their buckets, then fills
+    /// the rest of buckets with items of partial buffers and items of
+    /// blocks reaching into next bucket. Blocks have `block_len` items and
+    /// `block` is used for swapping them. Returns bounds of buckets.
+    fn permute_blocks_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        classifier: &Classifier<T>,
+        locals: &[LocalBuffers<T>],
+        stripe_len: usize,
+        block_len: usize,
+        block: &mut Vec<T>,
+        benchmark: &mut impl Benchmark,
+    ) -> Vec<usize> {
+        let len = slice.len();
+        let mut bounds = vec![0; classifier.classes() + 1];
+
+        for local in locals {
+            for (class, count) in local.counts.iter().enumerate() {
+                bounds[class + 1] += count;
+            }
+        }
+
+        for i in 1..bounds.len() {
+            bounds[i] += bounds[i - 1];
+        }
+
+        // slots of `block_len` items holding full blocks, which weren't moved yet
+        let mut full = vec![false; len.div_ceil(block_len)];
+
+        for (i, local) in locals.iter().enumerate() {
+            let first = i * stripe_len / block_len;
+
+            full[first..first + local.written / block_len].fill(true);
+        }
+
+        // blocks of every bucket are written from first aligned slot in it
+        let mut next = bounds
+            .iter()
+            .map(|&bound| bound.next_multiple_of(block_len))
+            .collect::<Vec<_>>();
+        let mut overflow = Vec::new();
+        let mut overflow_class = usize::MAX;
+
+        benchmark.add_allocation(mem::size_of_val(&bounds[..]));
+        benchmark.add_allocation(mem::size_of_val(&full[..]));
+        benchmark.add_allocation(mem::size_of_val(&next[..]));
+        benchmark.add_pass();
+
+        for slot in 0..full.len() {
+            if !full[slot] {
+                continue;
+            }
+
+            full[slot] = false;
+
+            for _ in 0..block_len {
+                benchmark.add_move();
+                benchmark.add_writes(1);
+            }
+
+            block.clear();
+            block.extend_from_slice(&slice[slot * block_len..(slot + 1) * block_len]);
+
+            loop {
+                let class = classifier.classify_with_benchmark(block[0], benchmark);
+                let dest = next[class];
+
+                next[class] += block_len;
+
+                // only the last slot may reach past the end of slice
+                if dest + block_len > len {
+                    let fits = len - dest;
+
+                    for _ in 0..block_len {
+                        benchmark.add_move();
+                        benchmark.add_writes(1);
+                    }
+
+                    slice[dest..].copy_from_slice(&block[..fits]);
+                    overflow.extend_from_slice(&block[fits..]);
+                    overflow_class = class;
+                    break;
+                }
+
+                if full[dest / block_len] {
+                    full[dest / block_len] = false;
+
+                    for _ in 0..block_len {
+                        benchmark.add_swap();
+                        benchmark.add_writes(2);
+                    }
+
+                    slice[dest..dest + block_len].swap_with_slice(block);
+                } else {
+                    for _ in 0..block_len {
+                        benchmark.add_move();
+                        benchmark.add_writes(1);
+                    }
+
+                    slice[dest..dest + block_len].copy_from_slice(block);
+                    break;
+                }
+            }
+        }
+
+        for class in 0..bounds.len() - 1 {
+            let (start, end) = (bounds[class], bounds[class + 1]);
+            let blocks_start = start.next_multiple_of(block_len);
+            let blocks_end = next[class].min(len);
+
+            // items of the last block reaching into next bucket
+            block.clear();
+
+            if blocks_end > end.max(blocks_start) {
+                for _ in end..blocks_end {
+                    benchmark.add_move();
+                    benchmark.add_writes(1);
+                }
+
+                block.extend_from_slice(&slice[end..blocks_end]);
+            }
+
+            let overflow = if class == overflow_class {
+                &overflow[..]
+            } else {
+                &[]
+            };
+
+            let items = block
+                .iter()
+                .chain(locals.iter().flat_map(|local| &local.buffers[class]))
+                .chain(overflow);
+            let positions = (start..blocks_start.min(end)).chain(blocks_end..end);
+
+            for (position, &value) in positions.zip(items) {
+                benchmark.add_move();
+                benchmark.add_writes(1);
+
+                slice[position] = value;
+            }
+        }
+
+        bounds
+    }
+}
+
+impl<const THREADS: usize> ParallelSorter for Ips4oSort<THREADS> {
+    /// In-place parallel samplesort implementation working on types
+    /// implementing `Ord` + `Copy` + `Send` + `Sync` so it's mostly usefull
+    /// for primitive types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::sample_sort::Ips4oSort;
+    ///
+    /// let mut slice = (0..10000).rev().collect::<Vec<_>>();
+    ///
+    /// Ips4oSort::<4>::sort(&mut slice);
+    ///
+    /// assert_eq!((0..10000).collect::<Vec<_>>(), slice);
+    /// ```
+    fn sort<T: Ord + Copy + Send + Sync>(slice: &mut [T]) {
+        Ips4oSort::<THREADS>::ips4o(slice);
+    }
+}
+
+impl<const THREADS: usize> BenchmarkingParallelSorter for Ips4oSort<THREADS> {
+    /// In-place parallel samplesort implementation with additional
+    /// benchmarking capabilities. Counters of all threads are summed up,
+    /// classification and block permutation of every slice are counted
+    /// as passes.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::AtomicBenchmarker;
+    /// use algorithms::sample_sort::Ips4oSort;
+    ///
+    /// let benchmarker = AtomicBenchmarker::default();
+    /// let mut slice = (0..10000).rev().collect::<Vec<_>>();
+    ///
+    /// Ips4oSort::<4>::sort_with_benchmark(&mut slice, &benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!((0..10000).collect::<Vec<_>>(), slice);
+    /// assert!(stats.comparisons > 0);
+    /// ```
+    fn sort_with_benchmark<T: Ord + Copy + Send + Sync>(
+        slice: &mut [T],
+        benchmark: &AtomicBenchmarker,
+    ) {
+        let mut timer = benchmark;
+
+        timer.start_timer();
+
+        Ips4oSort::<THREADS>::ips4o_with_benchmark(slice, benchmark);
+
+        timer.stop_timer();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarking::StandardBenchmarker;
+    use crate::conformance;
+    use crate::generator::*;
+
+    crate::sorter_conformance_tests!(SampleSort);
+
+    mod ips4o_one_thread {
+        use super::*;
+
+        crate::parallel_sorter_conformance_tests!(Ips4oSort<1>);
+    }
+
+    mod ips4o_four_threads {
+        use super::*;
+
+        crate::parallel_sorter_conformance_tests!(Ips4oSort<4>);
+    }
+
+    #[test]
+    fn classifier_counts_smaller_splitters() {
+        let splitters = vec![10, 20, 20, 30, 40, 50, 60];
+        let classifier = Classifier::new(splitters.clone(), 3, true);
+
+        assert_eq!(15, classifier.classes());
+
+        for value in 0..70 {
+            let bucket = splitters
+                .iter()
+                .filter(|&&splitter| splitter < value)
+                .count();
+            let expected = if splitters.contains(&value) {
+                2 * bucket + 1
+            } else {
+                2 * bucket
+            };
+
+            assert_eq!(expected, classifier.classify(value));
+            assert_eq!(
+                expected,
+                classifier.classify_with_benchmark(value, &mut StandardBenchmarker::default())
+            );
+        }
+    }
+
+    #[test]
+    fn few_distinct_values_use_equality_buckets() {
+        for keys in [1, 2, 5, 100] {
+            let input = conformance::keyed_input(100_000, keys, 7)
+                .into_iter()
+                .map(|item| item.key)
+                .collect::<Vec<_>>();
+            let mut expected = input.clone();
+
+            expected.sort();
+
+            let mut sample_sorted = input.clone();
+            let mut ips4o_sorted = input.clone();
+
+            SampleSort::sort(&mut sample_sorted);
+            Ips4oSort::<4>::sort(&mut ips4o_sorted);
+
+            assert_eq!(expected, sample_sorted);
+            assert_eq!(expected, ips4o_sorted);
+        }
+    }
+
+    #[test]
+    fn ips4o_sorts_large_inputs_of_wide_items() {
+        let input = RandomGenerator::generate_seeded(200_000, 3)
+            .into_iter()
+            .map(|value| (value % 1000, [value; 3]))
+            .collect::<Vec<_>>();
+        let mut expected = input.clone();
+        let mut sorted = input.clone();
+
+        expected.sort();
+        Ips4oSort::<3>::sort(&mut sorted);
+
+        assert_eq!(expected, sorted);
+    }
+
+    #[test]
+    fn ips4o_does_not_allocate_buffer_for_whole_slice() {
+        // with few distinct values all items end up in equality buckets
+        // after single level of classification
+        let input = RandomGenerator::generate_seeded(1_000_000, 1)
+            .into_iter()
+            .map(|value| value % 4)
+            .collect::<Vec<_>>();
+
+        let mut sample_benchmarker = StandardBenchmarker::default();
+        let ips4o_benchmarker = AtomicBenchmarker::default();
+
+        SampleSort::sort_with_benchmark(&mut input.clone(), &mut sample_benchmarker);
+        Ips4oSort::<4>::sort_with_benchmark(&mut input.clone(), &ips4o_benchmarker);
+
+        let input_bytes = mem::size_of_val(&input[..]);
+
+        assert!(sample_benchmarker.get_stats().allocated_bytes > input_bytes);
+        assert!(ips4o_benchmarker.get_stats().allocated_bytes < input_bytes / 4);
+    }
+
+    #[test]
+    fn sample_sort_compares_less_than_quick_sort() {
+        let input = RandomGenerator::generate_seeded(100_000, 0);
+
+        let mut sample_benchmarker = StandardBenchmarker::default();
+        let mut quick_benchmarker = StandardBenchmarker::default();
+
+        SampleSort::sort_with_benchmark(&mut input.clone(), &mut sample_benchmarker);
+        QuickSort::sort_with_benchmark(&mut input.clone(), &mut quick_benchmarker);
+
+        assert!(
+            sample_benchmarker.get_stats().comparisons < quick_benchmarker.get_stats().comparisons
+        );
+    }
+
+    #[test]
+    fn sorted_input_keeps_stack_shallow() {
+        conformance::shallow_stack_sorting_test::<SampleSort>(1_000_000);
+    }
+}
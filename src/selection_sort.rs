@@ -0,0 +1,123 @@
+//! Selection sort algorithm implementation.
+
+use crate::prelude::*;
+
+/// Struct implementing `Sorter` + `BenchmarkingSorter` using selection sort
+/// to sort items. Minimum of unsorted part is swapped to its front, so it
+/// makes `O(n^2)` comparisons but at most `n - 1` swaps, i.e. `2(n - 1)`
+/// writes. Items already in place aren't swapped.
+pub struct SelectionSort;
+
+impl SelectionSort {
+    fn selection_sort<T: Ord + Copy>(slice: &mut [T]) {
+        for i in 0..slice.len() {
+            let mut min = i;
+
+            for j in i + 1..slice.len() {
+                if slice[j] < slice[min] {
+                    min = j;
+                }
+            }
+
+            if min != i {
+                slice.swap(i, min);
+            }
+        }
+    }
+
+    fn selection_sort_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        benchmark: &mut impl Benchmark,
+    ) {
+        for i in 0..slice.len() {
+            let mut min = i;
+
+            for j in i + 1..slice.len() {
+                benchmark.add_cmp();
+
+                if slice[j] < slice[min] {
+                    min = j;
+                }
+            }
+
+            if min != i {
+                benchmark.add_swap();
+                benchmark.add_writes(2);
+                slice.swap(i, min);
+            }
+        }
+    }
+}
+
+impl Sorter for SelectionSort {
+    /// Selection sort implementation working on types implementing
+    /// `Ord` + `Copy` so it's mostly usefull for primitive types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    ///
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// SelectionSort::sort(&mut slice);
+    ///
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// ```
+    fn sort<T: Ord + Copy>(slice: &mut [T]) {
+        SelectionSort::selection_sort(slice);
+    }
+}
+
+impl BenchmarkingSorter for SelectionSort {
+    /// Selection sort implementation with additional benchmarking
+    /// capabilities.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::StandardBenchmarker;
+    ///
+    /// let mut benchmarker = StandardBenchmarker::default();
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// SelectionSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!(15, stats.comparisons);
+    /// assert_eq!(4, stats.swaps);
+    /// assert_eq!(8, stats.writes);
+    /// ```
+    fn sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        benchmark.start_timer();
+
+        SelectionSort::selection_sort_with_benchmark(slice, benchmark);
+
+        benchmark.stop_timer();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarking::StandardBenchmarker;
+    use crate::generator::{RandomGenerator, SeededGenerator};
+
+    crate::sorter_conformance_tests!(SelectionSort);
+
+    #[test]
+    fn selection_sort_swaps_at_most_once_per_item() {
+        for n in [1, 10, 1000] {
+            let mut slice = RandomGenerator::generate_seeded(n, 1);
+            let mut benchmarker = StandardBenchmarker::default();
+
+            SelectionSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+
+            let stats = benchmarker.get_stats();
+
+            assert!(stats.swaps < n);
+            assert_eq!(2 * stats.swaps, stats.writes);
+            assert_eq!(n * (n - 1) / 2, stats.comparisons);
+        }
+    }
+}
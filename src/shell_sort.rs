@@ -0,0 +1,257 @@
+//! Shellsort algorithm implementation with selectable gap sequences.
+
+use std::iter;
+use std::marker::PhantomData;
+
+use crate::prelude::*;
+
+/// Sequence of gaps used by `ShellSort`.
+pub trait GapSequence {
+    /// Returns gaps used to sort slice of length `len` in decreasing order.
+    /// Last gap is always 1, for `len` smaller than 2 there are no gaps.
+    fn gaps(len: usize) -> Vec<usize>;
+}
+
+/// Takes increasing gaps smaller than `limit` and reverses them.
+fn gaps_below(sequence: impl Iterator<Item = usize>, limit: usize) -> Vec<usize> {
+    let mut gaps = sequence.take_while(|&gap| gap < limit).collect::<Vec<_>>();
+
+    gaps.reverse();
+
+    gaps
+}
+
+/// Shell's original sequence: `n / 2`, `n / 4`, ..., 1.
+pub struct ShellGaps;
+
+impl GapSequence for ShellGaps {
+    fn gaps(len: usize) -> Vec<usize> {
+        iter::successors(Some(len / 2), |&gap| Some(gap / 2))
+            .take_while(|&gap| gap > 0)
+            .collect()
+    }
+}
+
+/// Knuth's sequence `(3^k - 1) / 2`: 1, 4, 13, 40, ... not bigger than `n / 3`.
+pub struct KnuthGaps;
+
+impl GapSequence for KnuthGaps {
+    fn gaps(len: usize) -> Vec<usize> {
+        let sequence = iter::successors(Some(1usize), |&gap| gap.checked_mul(3)?.checked_add(1));
+        let limit = if len < 2 { len } else { len.div_ceil(3) + 1 };
+
+        gaps_below(sequence, limit)
+    }
+}
+
+/// Hibbard's sequence `2^k - 1`: 1, 3, 7, 15, ...
+pub struct HibbardGaps;
+
+impl GapSequence for HibbardGaps {
+    fn gaps(len: usize) -> Vec<usize> {
+        let sequence = iter::successors(Some(1usize), |&gap| gap.checked_mul(2)?.checked_add(1));
+
+        gaps_below(sequence, len)
+    }
+}
+
+/// Sedgewick's sequence `4^k + 3 * 2^(k - 1) + 1` prefixed with 1:
+/// 1, 8, 23, 77, 281, ...
+pub struct SedgewickGaps;
+
+impl GapSequence for SedgewickGaps {
+    fn gaps(len: usize) -> Vec<usize> {
+        let sequence = iter::once(1).chain((1..usize::BITS / 2).map(|k| {
+            let power = 1usize << k;
+
+            power * power + 3 * (power / 2) + 1
+        }));
+
+        gaps_below(sequence, len)
+    }
+}
+
+/// Tokuda's sequence `ceil(h_k)` where `h_1 = 1` and `h_k = 2.25 * h_(k - 1) + 1`:
+/// 1, 4, 9, 20, 46, 103, ...
+pub struct TokudaGaps;
+
+impl GapSequence for TokudaGaps {
+    fn gaps(len: usize) -> Vec<usize> {
+        let sequence = iter::successors(Some(1.0f64), |&gap| Some(2.25 * gap + 1.0))
+            .map(|gap| gap.ceil())
+            .take_while(|&gap| gap < usize::MAX as f64)
+            .map(|gap| gap as usize);
+
+        gaps_below(sequence, len)
+    }
+}
+
+/// Ciura's experimentally found sequence 1, 4, 10, 23, 57, 132, 301, 701, 1750,
+/// extended by multiplying last gap by 2.25.
+pub struct CiuraGaps;
+
+impl CiuraGaps {
+    const GAPS: [usize; 9] = [1, 4, 10, 23, 57, 132, 301, 701, 1750];
+}
+
+impl GapSequence for CiuraGaps {
+    fn gaps(len: usize) -> Vec<usize> {
+        let last = CiuraGaps::GAPS[CiuraGaps::GAPS.len() - 1];
+        let extension =
+            iter::successors(Some(last), |&gap| gap.checked_mul(9).map(|gap| gap / 4)).skip(1);
+
+        gaps_below(CiuraGaps::GAPS.into_iter().chain(extension), len)
+    }
+}
+
+/// Struct implementing `Sorter` + `BenchmarkingSorter` using shellsort
+/// to sort items. Slice is insertion sorted with every gap taken from gap
+/// sequence `G`, so items can travel far in few moves.
+pub struct ShellSort<G> {
+    gaps: PhantomData<G>,
+}
+
+impl<G: GapSequence> Sorter for ShellSort<G> {
+    /// Shellsort implementation working on types implementing
+    /// `Ord` + `Copy` so it's mostly usefull for primitive types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::shell_sort::CiuraGaps;
+    ///
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// ShellSort::<CiuraGaps>::sort(&mut slice);
+    ///
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// ```
+    fn sort<T: Ord + Copy>(slice: &mut [T]) {
+        for gap in G::gaps(slice.len()) {
+            InsertionSort::gapped_insertion_sort(slice, gap);
+        }
+    }
+}
+
+impl<G: GapSequence> BenchmarkingSorter for ShellSort<G> {
+    /// Shellsort implementation with additional benchmarking capabilities.
+    /// Every gap is counted as a pass.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::StandardBenchmarker;
+    /// use algorithms::shell_sort::CiuraGaps;
+    ///
+    /// let mut benchmarker = StandardBenchmarker::default();
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// ShellSort::<CiuraGaps>::sort_with_benchmark(&mut slice, &mut benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!(2, stats.passes);
+    /// assert_eq!(4, stats.swaps);
+    /// ```
+    fn sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        benchmark.start_timer();
+
+        for gap in G::gaps(slice.len()) {
+            benchmark.add_pass();
+
+            InsertionSort::gapped_insertion_sort_with_benchmark(slice, gap, benchmark);
+        }
+
+        benchmark.stop_timer();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarking::StandardBenchmarker;
+    use crate::generator::*;
+
+    mod shell_gaps {
+        use super::*;
+
+        crate::sorter_conformance_tests!(ShellSort<ShellGaps>);
+    }
+
+    mod knuth_gaps {
+        use super::*;
+
+        crate::sorter_conformance_tests!(ShellSort<KnuthGaps>);
+    }
+
+    mod hibbard_gaps {
+        use super::*;
+
+        crate::sorter_conformance_tests!(ShellSort<HibbardGaps>);
+    }
+
+    mod sedgewick_gaps {
+        use super::*;
+
+        crate::sorter_conformance_tests!(ShellSort<SedgewickGaps>);
+    }
+
+    mod tokuda_gaps {
+        use super::*;
+
+        crate::sorter_conformance_tests!(ShellSort<TokudaGaps>);
+    }
+
+    mod ciura_gaps {
+        use super::*;
+
+        crate::sorter_conformance_tests!(ShellSort<CiuraGaps>);
+    }
+
+    #[test]
+    fn gap_sequences_test() {
+        assert_eq!(vec![50, 25, 12, 6, 3, 1], ShellGaps::gaps(100));
+        assert_eq!(vec![13, 4, 1], KnuthGaps::gaps(100));
+        assert_eq!(vec![63, 31, 15, 7, 3, 1], HibbardGaps::gaps(100));
+        assert_eq!(vec![77, 23, 8, 1], SedgewickGaps::gaps(100));
+        assert_eq!(vec![46, 20, 9, 4, 1], TokudaGaps::gaps(100));
+        assert_eq!(vec![57, 23, 10, 4, 1], CiuraGaps::gaps(100));
+        assert_eq!(vec![3937, 1750, 701], CiuraGaps::gaps(5000)[..3]);
+    }
+
+    #[test]
+    fn gap_sequences_edge_cases_test() {
+        fn check<G: GapSequence>() {
+            assert!(G::gaps(0).is_empty());
+            assert!(G::gaps(1).is_empty());
+
+            for len in [2, 3, 10, 1000, usize::MAX] {
+                let gaps = G::gaps(len);
+
+                assert_eq!(Some(&1), gaps.last());
+                assert!(gaps.windows(2).all(|pair| pair[0] > pair[1]));
+                assert!(gaps.iter().all(|&gap| gap < len));
+            }
+        }
+
+        check::<ShellGaps>();
+        check::<KnuthGaps>();
+        check::<HibbardGaps>();
+        check::<SedgewickGaps>();
+        check::<TokudaGaps>();
+        check::<CiuraGaps>();
+    }
+
+    #[test]
+    fn shell_sort_beats_insertion_sort() {
+        let input = RandomGenerator::generate_seeded(10000, 0);
+
+        let mut insertion_benchmarker = StandardBenchmarker::default();
+        let mut shell_benchmarker = StandardBenchmarker::default();
+
+        InsertionSort::sort_with_benchmark(&mut input.clone(), &mut insertion_benchmarker);
+        ShellSort::<CiuraGaps>::sort_with_benchmark(&mut input.clone(), &mut shell_benchmarker);
+
+        assert!(10 * shell_benchmarker.get_stats().swaps < insertion_benchmarker.get_stats().swaps);
+    }
+}
@@ -0,0 +1,342 @@
+//! Smoothsort algorithm implementation.
+
+use crate::prelude::*;
+
+/// Number of Leonardo numbers which fit into `usize`.
+const ORDERS: usize = 92;
+
+/// Leonardo numbers `L(0) = L(1) = 1`, `L(k) = L(k - 1) + L(k - 2) + 1`,
+/// which are sizes of Leonardo trees of order `k`.
+const LEONARDO: [usize; ORDERS] = {
+    let mut leonardo = [1; ORDERS];
+    let mut k = 2;
+
+    while k < ORDERS {
+        leonardo[k] = leonardo[k - 1] + leonardo[k - 2] + 1;
+        k += 1;
+    }
+
+    leonardo
+};
+
+/// Struct implementing `Sorter` + `BenchmarkingSorter` using Dijkstra's
+/// smoothsort to sort items. Slice is turned into a forest of max-heap
+/// ordered Leonardo trees with roots ascending from left to right, so the
+/// last root is always the largest item. Tree of order `k` rooted at `root`
+/// has its right subtree of order `k - 2` rooted at `root - 1` and left
+/// subtree of order `k - 1` rooted before it. Orders of trees are distinct,
+/// so the whole forest is stored as a bitmask of orders and sorting is in
+/// place. Presorted input keeps trees ordered, so sorting it takes linear
+/// time, while the worst case is `O(n log n)`.
+pub struct SmoothSort;
+
+impl SmoothSort {
+    fn smooth_sort<T: Ord + Copy>(slice: &mut [T]) {
+        let mut orders = 0u128;
+
+        for root in 0..slice.len() {
+            let order = SmoothSort::push(&mut orders);
+
+            SmoothSort::trinkle(slice, root, order, orders);
+        }
+
+        for root in (0..slice.len()).rev() {
+            let order = orders.trailing_zeros() as usize;
+
+            orders &= !(1 << order);
+
+            // removing root of bigger tree exposes its subtrees as new trees
+            if order >= 2 {
+                let right = root - 1;
+                let left = right - LEONARDO[order - 2];
+
+                orders |= 0b11 << (order - 2);
+
+                SmoothSort::trinkle(slice, left, order - 1, orders);
+                SmoothSort::trinkle(slice, right, order - 2, orders);
+            }
+        }
+    }
+
+    /// Adds tree for the next item to forest and returns its order. Item
+    /// becomes root of two last trees if they have consecutive orders.
+    fn push(orders: &mut u128) -> usize {
+        let last = orders.trailing_zeros() as usize;
+
+        let order = if *orders != 0 && *orders >> last & 0b10 != 0 {
+            *orders &= !(0b11 << last);
+            last + 2
+        } else if *orders != 0 && last == 1 {
+            0
+        } else {
+            1
+        };
+
+        *orders |= 1 << order;
+
+        order
+    }
+
+    /// Moves root of tree of order `order` to the left over smaller roots of
+    /// preceding trees, then sifts it down into the tree where it stops.
+    fn trinkle<T: Ord + Copy>(slice: &mut [T], mut root: usize, mut order: usize, orders: u128) {
+        loop {
+            let preceding = orders >> order >> 1;
+
+            if preceding == 0 {
+                break;
+            }
+
+            let previous = root - LEONARDO[order];
+
+            if slice[previous] <= slice[root] {
+                break;
+            }
+
+            if order >= 2 {
+                let right = root - 1;
+                let left = right - LEONARDO[order - 2];
+
+                if slice[previous] <= slice[left].max(slice[right]) {
+                    break;
+                }
+            }
+
+            slice.swap(previous, root);
+
+            root = previous;
+            order += preceding.trailing_zeros() as usize + 1;
+        }
+
+        SmoothSort::sift_down(slice, root, order);
+    }
+
+    fn sift_down<T: Ord + Copy>(slice: &mut [T], mut root: usize, mut order: usize) {
+        while order >= 2 {
+            let right = root - 1;
+            let left = right - LEONARDO[order - 2];
+
+            let (child, child_order) = if slice[left] > slice[right] {
+                (left, order - 1)
+            } else {
+                (right, order - 2)
+            };
+
+            if slice[root] >= slice[child] {
+                break;
+            }
+
+            slice.swap(root, child);
+
+            root = child;
+            order = child_order;
+        }
+    }
+
+    fn smooth_sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        let mut orders = 0u128;
+
+        for root in 0..slice.len() {
+            let order = SmoothSort::push(&mut orders);
+
+            SmoothSort::trinkle_with_benchmark(slice, root, order, orders, benchmark);
+        }
+
+        benchmark.add_pass();
+
+        for root in (0..slice.len()).rev() {
+            let order = orders.trailing_zeros() as usize;
+
+            orders &= !(1 << order);
+
+            // removing root of bigger tree exposes its subtrees as new trees
+            if order >= 2 {
+                let right = root - 1;
+                let left = right - LEONARDO[order - 2];
+
+                orders |= 0b11 << (order - 2);
+
+                SmoothSort::trinkle_with_benchmark(slice, left, order - 1, orders, benchmark);
+                SmoothSort::trinkle_with_benchmark(slice, right, order - 2, orders, benchmark);
+            }
+        }
+
+        benchmark.add_pass();
+    }
+
+    fn trinkle_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        mut root: usize,
+        mut order: usize,
+        orders: u128,
+        benchmark: &mut impl Benchmark,
+    ) {
+        loop {
+            let preceding = orders >> order >> 1;
+
+            if preceding == 0 {
+                break;
+            }
+
+            let previous = root - LEONARDO[order];
+
+            benchmark.add_cmp();
+
+            if slice[previous] <= slice[root] {
+                break;
+            }
+
+            if order >= 2 {
+                let right = root - 1;
+                let left = right - LEONARDO[order - 2];
+
+                benchmark.add_cmp();
+                benchmark.add_cmp();
+
+                if slice[previous] <= slice[left].max(slice[right]) {
+                    break;
+                }
+            }
+
+            benchmark.add_swap();
+            benchmark.add_writes(2);
+            slice.swap(previous, root);
+
+            root = previous;
+            order += preceding.trailing_zeros() as usize + 1;
+        }
+
+        SmoothSort::sift_down_with_benchmark(slice, root, order, benchmark);
+    }
+
+    fn sift_down_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        mut root: usize,
+        mut order: usize,
+        benchmark: &mut impl Benchmark,
+    ) {
+        while order >= 2 {
+            let right = root - 1;
+            let left = right - LEONARDO[order - 2];
+
+            benchmark.add_cmp();
+
+            let (child, child_order) = if slice[left] > slice[right] {
+                (left, order - 1)
+            } else {
+                (right, order - 2)
+            };
+
+            benchmark.add_cmp();
+
+            if slice[root] >= slice[child] {
+                break;
+            }
+
+            benchmark.add_swap();
+            benchmark.add_writes(2);
+            slice.swap(root, child);
+
+            root = child;
+            order = child_order;
+        }
+    }
+}
+
+impl Sorter for SmoothSort {
+    /// Smoothsort implementation working on types implementing
+    /// `Ord` + `Copy` so it's mostly usefull for primitive types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    ///
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// SmoothSort::sort(&mut slice);
+    ///
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// ```
+    fn sort<T: Ord + Copy>(slice: &mut [T]) {
+        SmoothSort::smooth_sort(slice);
+    }
+}
+
+impl BenchmarkingSorter for SmoothSort {
+    /// Smoothsort implementation with additional benchmarking capabilities.
+    /// Building the forest and dismantling it are counted as passes.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::StandardBenchmarker;
+    ///
+    /// let mut benchmarker = StandardBenchmarker::default();
+    /// let mut slice = (0..1000).collect::<Vec<_>>();
+    ///
+    /// SmoothSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!((0..1000).collect::<Vec<_>>(), slice);
+    /// assert_eq!(0, stats.swaps);
+    /// assert!(stats.comparisons < 5 * 1000);
+    /// ```
+    fn sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        benchmark.start_timer();
+
+        SmoothSort::smooth_sort_with_benchmark(slice, benchmark);
+
+        benchmark.stop_timer();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarking::StandardBenchmarker;
+    use crate::generator::{
+        AscendingGenerator, DescendingGenerator, RandomGenerator, SeededGenerator,
+    };
+
+    crate::sorter_conformance_tests!(SmoothSort);
+
+    fn comparisons(input: &[u32]) -> usize {
+        let mut benchmarker = StandardBenchmarker::default();
+
+        SmoothSort::sort_with_benchmark(&mut input.to_vec(), &mut benchmarker);
+
+        benchmarker.get_stats().comparisons
+    }
+
+    #[test]
+    fn leonardo_numbers_fit_usize() {
+        assert_eq!([1, 1, 3, 5, 9, 15, 25, 41], LEONARDO[..8]);
+        assert!(LEONARDO[ORDERS - 1]
+            .checked_add(LEONARDO[ORDERS - 2])
+            .is_none());
+    }
+
+    #[test]
+    fn ascending_input_takes_linear_comparisons() {
+        for n in [10usize, 1000, 100000] {
+            let input = AscendingGenerator::generate_seeded(n, 1);
+
+            assert!(comparisons(&input) <= 5 * n, "{} items", n);
+        }
+    }
+
+    #[test]
+    fn worst_case_takes_n_log_n_comparisons() {
+        for n in [10usize, 1000, 100000] {
+            let bound = 4 * n * n.ilog2() as usize;
+
+            for input in [
+                RandomGenerator::generate_seeded(n, 1),
+                DescendingGenerator::generate_seeded(n, 1),
+            ] {
+                assert!(comparisons(&input) <= bound, "{} items", n);
+            }
+        }
+    }
+}
@@ -0,0 +1,615 @@
+//! Sorting networks: best known networks for up to 16 items, Batcher's
+//! generators for any number of items and verifier based on 0-1 principle.
+
+use crate::prelude::*;
+
+/// Networks with the fewest comparators known for `n` items, indexed by `n`,
+/// one layer of parallel comparators per line. Their sizes are proven
+/// optimal only up to 12 items: up to 8 by Floyd and Knuth (Knuth, The Art
+/// of Computer Programming, vol. 3, section 5.3.4), 9 and 10 by Codish et
+/// al. ("Twenty-Five Comparators is Optimal when Sorting Nine Inputs (and
+/// Twenty-Nine for Ten)", 2014) and 11 and 12 by Harder ("An Answer to the
+/// Bose-Nelson Sorting Problem for 11 and 12 Channels", 2020). Networks for
+/// 13 to 16 items are only best known.
+#[rustfmt::skip]
+const BEST_KNOWN: [&[(usize, usize)]; 17] = [
+    &[],
+    &[],
+    &[(0, 1)],
+    &[
+        (0, 2),
+        (0, 1),
+        (1, 2),
+    ],
+    &[
+        (0, 2), (1, 3),
+        (0, 1), (2, 3),
+        (1, 2),
+    ],
+    &[
+        (0, 3), (1, 4),
+        (0, 2), (1, 3),
+        (0, 1), (2, 4),
+        (1, 2), (3, 4),
+        (2, 3),
+    ],
+    &[
+        (0, 5), (1, 3), (2, 4),
+        (1, 2), (3, 4),
+        (0, 3), (2, 5),
+        (0, 1), (2, 3), (4, 5),
+        (1, 2), (3, 4),
+    ],
+    &[
+        (0, 6), (2, 3), (4, 5),
+        (0, 2), (1, 4), (3, 6),
+        (0, 1), (2, 5), (3, 4),
+        (1, 2), (4, 6),
+        (2, 3), (4, 5),
+        (1, 2), (3, 4), (5, 6),
+    ],
+    &[
+        (0, 2), (1, 3), (4, 6), (5, 7),
+        (0, 4), (1, 5), (2, 6), (3, 7),
+        (0, 1), (2, 3), (4, 5), (6, 7),
+        (2, 4), (3, 5),
+        (1, 4), (3, 6),
+        (1, 2), (3, 4), (5, 6),
+    ],
+    &[
+        (0, 3), (1, 7), (2, 5), (4, 8),
+        (0, 7), (2, 4), (3, 8), (5, 6),
+        (0, 2), (1, 3), (4, 5), (7, 8),
+        (1, 4), (3, 6), (5, 7),
+        (0, 1), (2, 4), (3, 5), (6, 8),
+        (2, 3), (4, 5), (6, 7),
+        (1, 2), (3, 4), (5, 6),
+    ],
+    &[
+        (0, 8), (1, 9), (2, 7), (3, 5), (4, 6),
+        (0, 2), (1, 4), (5, 8), (7, 9),
+        (0, 3), (2, 4), (5, 7), (6, 9),
+        (0, 1), (3, 6), (8, 9),
+        (1, 5), (2, 3), (4, 8), (6, 7),
+        (1, 2), (3, 5), (4, 6), (7, 8),
+        (2, 3), (4, 5), (6, 7),
+        (3, 4), (5, 6),
+    ],
+    &[
+        (0, 9), (1, 6), (2, 4), (3, 7), (5, 8),
+        (0, 1), (3, 5), (4, 10), (6, 9), (7, 8),
+        (1, 3), (2, 5), (4, 7), (8, 10),
+        (0, 4), (1, 2), (3, 7), (5, 9), (6, 8),
+        (0, 1), (2, 6), (4, 5), (7, 8), (9, 10),
+        (2, 4), (3, 6), (5, 7), (8, 9),
+        (1, 2), (3, 4), (5, 6), (7, 8),
+        (2, 3), (4, 5), (6, 7),
+    ],
+    &[
+        (0, 8), (1, 7), (2, 6), (3, 11), (4, 10), (5, 9),
+        (0, 1), (2, 5), (3, 4), (6, 9), (7, 8), (10, 11),
+        (0, 2), (1, 6), (5, 10), (9, 11),
+        (0, 3), (1, 2), (4, 6), (5, 7), (8, 11), (9, 10),
+        (1, 4), (3, 5), (6, 8), (7, 10),
+        (1, 3), (2, 5), (6, 9), (8, 10),
+        (2, 3), (4, 5), (6, 7), (8, 9),
+        (4, 6), (5, 7),
+        (3, 4), (5, 6), (7, 8),
+    ],
+    &[
+        (0, 12), (1, 10), (2, 9), (3, 7), (5, 11), (6, 8),
+        (1, 6), (2, 3), (4, 11), (7, 9), (8, 10),
+        (0, 4), (1, 2), (3, 6), (7, 8), (9, 10), (11, 12),
+        (4, 6), (5, 9), (8, 11), (10, 12),
+        (0, 5), (3, 8), (4, 7), (6, 11), (9, 10),
+        (0, 1), (2, 5), (6, 9), (7, 8), (10, 11),
+        (1, 3), (2, 4), (5, 6), (9, 10),
+        (1, 2), (3, 4), (5, 7), (6, 8),
+        (2, 3), (4, 5), (6, 7), (8, 9),
+        (3, 4), (5, 6),
+    ],
+    &[
+        (0, 1), (2, 3), (4, 5), (6, 7), (8, 9), (10, 11), (12, 13),
+        (0, 2), (1, 3), (4, 8), (5, 9), (10, 12), (11, 13),
+        (0, 4), (1, 2), (3, 7), (5, 8), (6, 10), (9, 13), (11, 12),
+        (0, 6), (1, 5), (3, 9), (4, 10), (7, 13), (8, 12),
+        (2, 10), (3, 11), (4, 6), (7, 9),
+        (1, 3), (2, 8), (5, 11), (6, 7), (10, 12),
+        (1, 4), (2, 6), (3, 5), (7, 11), (8, 10), (9, 12),
+        (2, 4), (3, 6), (5, 8), (7, 10), (9, 11),
+        (3, 4), (5, 6), (7, 8), (9, 10),
+        (6, 7),
+    ],
+    &[
+        (0, 13), (1, 12), (3, 14), (4, 8), (5, 6), (7, 11), (9, 10),
+        (0, 5), (1, 7), (2, 9), (3, 4), (6, 13), (8, 14), (11, 12),
+        (0, 1), (2, 3), (4, 5), (6, 8), (7, 9), (10, 11), (12, 13),
+        (0, 2), (1, 3), (4, 10), (5, 11), (6, 7), (8, 9), (12, 14),
+        (1, 2), (3, 12), (4, 6), (5, 7), (8, 10), (9, 11), (13, 14),
+        (1, 4), (2, 6), (5, 8), (7, 10), (9, 13), (11, 14),
+        (2, 4), (3, 6), (9, 12), (11, 13),
+        (3, 5), (6, 8), (7, 9), (10, 12),
+        (3, 4), (5, 6), (7, 8), (9, 10), (11, 12),
+        (6, 7), (8, 9),
+    ],
+    &[
+        (0, 13), (1, 12), (2, 15), (3, 14), (4, 8), (5, 6), (7, 11), (9, 10),
+        (0, 5), (1, 7), (2, 9), (3, 4), (6, 13), (8, 14), (10, 15), (11, 12),
+        (0, 1), (2, 3), (4, 5), (6, 8), (7, 9), (10, 11), (12, 13), (14, 15),
+        (0, 2), (1, 3), (4, 10), (5, 11), (6, 7), (8, 9), (12, 14), (13, 15),
+        (1, 2), (3, 12), (4, 6), (5, 7), (8, 10), (9, 11), (13, 14),
+        (1, 4), (2, 6), (5, 8), (7, 10), (9, 13), (11, 14),
+        (2, 4), (3, 6), (9, 12), (11, 13),
+        (3, 5), (6, 8), (7, 9), (10, 12),
+        (3, 4), (5, 6), (7, 8), (9, 10), (11, 12),
+        (6, 7), (8, 9),
+    ],
+];
+
+/// Compares items at `i` < `j` and exchanges them if they are out of order.
+/// Both items are always written back, so there is no branch on result of
+/// comparison.
+fn compare_exchange<T: Ord + Copy>(slice: &mut [T], i: usize, j: usize) {
+    let (a, b) = (slice[i], slice[j]);
+    let swap = b < a;
+
+    slice[i] = if swap { b } else { a };
+    slice[j] = if swap { a } else { b };
+}
+
+fn compare_exchange_with_benchmark<T: Ord + Copy>(
+    slice: &mut [T],
+    i: usize,
+    j: usize,
+    benchmark: &mut impl Benchmark,
+) {
+    let (a, b) = (slice[i], slice[j]);
+    let swap = b < a;
+
+    benchmark.add_cmp();
+    // both items are stored whether they are swapped or not
+    benchmark.add_writes(2);
+
+    if swap {
+        benchmark.add_swap();
+    }
+
+    slice[i] = if swap { b } else { a };
+    slice[j] = if swap { a } else { b };
+}
+
+/// Comparator network for fixed number of items. Every comparator `(i, j)`
+/// with `i < j` compare-exchanges items at these indices, so smaller one
+/// ends up at `i`. Comparators are applied in order, they don't depend on
+/// results of previous comparisons.
+///
+/// Examples:
+/// ```
+/// use algorithms::sorting_network::SortingNetwork;
+///
+/// let network = SortingNetwork::new(3, vec![(0, 2), (0, 1), (1, 2)]);
+/// let mut slice = [3, 1, 2];
+///
+/// network.apply(&mut slice);
+///
+/// assert_eq!([1, 2, 3], slice);
+/// assert!(network.is_sorting_network());
+/// assert_eq!(3, network.depth());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SortingNetwork {
+    width: usize,
+    comparators: Vec<(usize, usize)>,
+}
+
+impl SortingNetwork {
+    /// Creates network for `width` items. Panics if some comparator doesn't
+    /// connect two different indices smaller than `width` in increasing
+    /// order.
+    pub fn new(width: usize, comparators: Vec<(usize, usize)>) -> Self {
+        assert!(
+            comparators.iter().all(|&(i, j)| i < j && j < width),
+            "comparator has to connect two indices in increasing order within network"
+        );
+
+        SortingNetwork { width, comparators }
+    }
+
+    /// Returns best known network for `width` items, which has the fewest
+    /// comparators found so far. There are such networks for up to 16 items.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::sorting_network::SortingNetwork;
+    ///
+    /// let network = SortingNetwork::best_known(16).unwrap();
+    ///
+    /// assert_eq!(60, network.size());
+    /// assert_eq!(10, network.depth());
+    /// assert!(SortingNetwork::best_known(17).is_none());
+    /// ```
+    pub fn best_known(width: usize) -> Option<Self> {
+        let comparators = BEST_KNOWN.get(width)?;
+
+        Some(SortingNetwork::new(width, comparators.to_vec()))
+    }
+
+    /// Generates Batcher's odd-even merge sorting network for `width` items.
+    /// Sorted halves are merged by merging their even and odd subsequences
+    /// recursively. Network is built for the next power of two and
+    /// comparators reaching past `width` are left out, which is correct
+    /// because missing items can be taken as bigger than all others.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::sorting_network::SortingNetwork;
+    ///
+    /// let network = SortingNetwork::odd_even_merge(16);
+    ///
+    /// assert_eq!(63, network.size());
+    /// assert_eq!(10, network.depth());
+    /// ```
+    pub fn odd_even_merge(width: usize) -> Self {
+        let padded = width.next_power_of_two();
+        let mut comparators = Vec::new();
+
+        let mut p = 1;
+
+        while p < padded {
+            let mut k = p;
+
+            while k >= 1 {
+                for j in (k % p..padded - k).step_by(2 * k) {
+                    for i in 0..k {
+                        // both items have to be in the same block of `2 * p` items
+                        if (i + j) / (2 * p) == (i + j + k) / (2 * p) {
+                            comparators.push((i + j, i + j + k));
+                        }
+                    }
+                }
+
+                k /= 2;
+            }
+
+            p *= 2;
+        }
+
+        comparators.retain(|&(_, j)| j < width);
+
+        SortingNetwork::new(width, comparators)
+    }
+
+    /// Generates bitonic sorting network for `width` items. Every merge
+    /// first compares items symmetric around the middle of block, which
+    /// turns two sorted halves into two bitonic halves, then bitonic halves
+    /// are halved recursively. Comparators reaching past `width` are left
+    /// out as in `odd_even_merge`.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::sorting_network::SortingNetwork;
+    ///
+    /// let network = SortingNetwork::bitonic(16);
+    ///
+    /// assert_eq!(80, network.size());
+    /// assert_eq!(10, network.depth());
+    /// ```
+    pub fn bitonic(width: usize) -> Self {
+        let padded = width.next_power_of_two();
+        let mut comparators = Vec::new();
+
+        let mut block = 2;
+
+        while block <= padded {
+            let mut distance = block - 1;
+
+            loop {
+                for i in 0..padded {
+                    let partner = i ^ distance;
+
+                    if partner > i && partner < width {
+                        comparators.push((i, partner));
+                    }
+                }
+
+                if distance == block - 1 {
+                    distance = block / 4;
+                } else {
+                    distance /= 2;
+                }
+
+                if distance == 0 {
+                    break;
+                }
+            }
+
+            block *= 2;
+        }
+
+        SortingNetwork::new(width, comparators)
+    }
+
+    /// Number of items the network sorts.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of comparators of the network.
+    pub fn size(&self) -> usize {
+        self.comparators.len()
+    }
+
+    /// Number of layers of the network, where all comparators of one layer
+    /// could be applied in parallel.
+    pub fn depth(&self) -> usize {
+        let mut layers = vec![0; self.width];
+
+        for &(i, j) in &self.comparators {
+            let layer = layers[i].max(layers[j]) + 1;
+
+            layers[i] = layer;
+            layers[j] = layer;
+        }
+
+        layers.into_iter().max().unwrap_or(0)
+    }
+
+    pub fn comparators(&self) -> &[(usize, usize)] {
+        &self.comparators
+    }
+
+    /// Applies the network to slice. Panics if length of slice isn't equal
+    /// to width of the network.
+    pub fn apply<T: Ord + Copy>(&self, slice: &mut [T]) {
+        assert_eq!(
+            self.width,
+            slice.len(),
+            "slice has to be as long as network is wide"
+        );
+
+        for &(i, j) in &self.comparators {
+            compare_exchange(slice, i, j);
+        }
+    }
+
+    /// Applies the network with additional benchmarking capabilities. Every
+    /// comparator is counted as a comparison and two writes, every exchange
+    /// as a swap.
+    pub fn apply_with_benchmark<T: Ord + Copy>(
+        &self,
+        slice: &mut [T],
+        benchmark: &mut impl Benchmark,
+    ) {
+        assert_eq!(
+            self.width,
+            slice.len(),
+            "slice has to be as long as network is wide"
+        );
+
+        for &(i, j) in &self.comparators {
+            compare_exchange_with_benchmark(slice, i, j, benchmark);
+        }
+    }
+
+    /// Checks whether the network sorts all inputs. By 0-1 principle it's
+    /// enough to check all `2^width` inputs made of zeros and ones. They are
+    /// checked 64 at once, every item is represented by 64-bit mask with one
+    /// bit per input, so compare-exchange becomes bitwise and + or. Time is
+    /// exponential in width of the network.
+    pub fn is_sorting_network(&self) -> bool {
+        // bits of input `k` in lane `k` for the first 6 items
+        const LANES: [u64; 6] = [
+            0xAAAA_AAAA_AAAA_AAAA,
+            0xCCCC_CCCC_CCCC_CCCC,
+            0xF0F0_F0F0_F0F0_F0F0,
+            0xFF00_FF00_FF00_FF00,
+            0xFFFF_0000_FFFF_0000,
+            0xFFFF_FFFF_0000_0000,
+        ];
+
+        let batches = 1u64 << self.width.saturating_sub(LANES.len());
+        let mut items = vec![0; self.width];
+
+        (0..batches).all(|batch| {
+            for (i, item) in items.iter_mut().enumerate() {
+                *item = match i.checked_sub(LANES.len()) {
+                    None => LANES[i],
+                    Some(bit) if batch >> bit & 1 == 1 => u64::MAX,
+                    Some(_) => 0,
+                };
+            }
+
+            for &(i, j) in &self.comparators {
+                let (a, b) = (items[i], items[j]);
+
+                items[i] = a & b;
+                items[j] = a | b;
+            }
+
+            // no input may have one followed by zero
+            items.windows(2).all(|pair| pair[0] & !pair[1] == 0)
+        })
+    }
+}
+
+/// Struct implementing `Sorter` + `BenchmarkingSorter` using sorting
+/// networks. Slices up to 16 items are sorted by best known networks,
+/// longer slices by odd-even merge network generated for their length. It's
+/// meant as small-case sorter of hybrid algorithms, e.g.
+/// `TimSortWith<NetworkSort>`.
+pub struct NetworkSort;
+
+impl Sorter for NetworkSort {
+    /// Sorting network implementation working on types implementing
+    /// `Ord` + `Copy` so it's mostly usefull for primitive types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    ///
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// NetworkSort::sort(&mut slice);
+    ///
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// ```
+    fn sort<T: Ord + Copy>(slice: &mut [T]) {
+        match BEST_KNOWN.get(slice.len()) {
+            Some(comparators) => {
+                for &(i, j) in comparators.iter() {
+                    compare_exchange(slice, i, j);
+                }
+            }
+            None => SortingNetwork::odd_even_merge(slice.len()).apply(slice),
+        }
+    }
+}
+
+impl BenchmarkingSorter for NetworkSort {
+    /// Sorting network implementation with additional benchmarking
+    /// capabilities. Number of comparisons depends only on length of slice.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::StandardBenchmarker;
+    ///
+    /// let mut benchmarker = StandardBenchmarker::default();
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// NetworkSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!(12, stats.comparisons);
+    /// ```
+    fn sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        benchmark.start_timer();
+
+        match BEST_KNOWN.get(slice.len()) {
+            Some(comparators) => {
+                for &(i, j) in comparators.iter() {
+                    compare_exchange_with_benchmark(slice, i, j, benchmark);
+                }
+            }
+            None => {
+                SortingNetwork::odd_even_merge(slice.len()).apply_with_benchmark(slice, benchmark)
+            }
+        }
+
+        benchmark.stop_timer();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::sorter_conformance_tests!(NetworkSort);
+
+    #[test]
+    fn best_known_networks_sort() {
+        let sizes = [0, 0, 1, 3, 5, 9, 12, 16, 19, 25, 29, 35, 39, 45, 51, 56, 60];
+        let depths = [0, 0, 1, 3, 3, 5, 5, 6, 6, 7, 8, 8, 9, 10, 10, 10, 10];
+
+        for width in 0..=16 {
+            let network = SortingNetwork::best_known(width).unwrap();
+
+            assert!(network.is_sorting_network(), "network for {} items", width);
+            assert_eq!(sizes[width], network.size());
+            assert_eq!(depths[width], network.depth());
+        }
+    }
+
+    #[test]
+    fn generated_networks_sort() {
+        for width in 0..=20 {
+            let odd_even = SortingNetwork::odd_even_merge(width);
+            let bitonic = SortingNetwork::bitonic(width);
+
+            assert!(
+                odd_even.is_sorting_network(),
+                "odd-even network for {} items",
+                width
+            );
+            assert!(
+                bitonic.is_sorting_network(),
+                "bitonic network for {} items",
+                width
+            );
+            assert!(odd_even.size() <= bitonic.size());
+
+            if let Some(best_known) = SortingNetwork::best_known(width) {
+                assert!(best_known.size() <= odd_even.size());
+            }
+        }
+
+        for log in 1..10 {
+            let width = 1 << log;
+
+            assert_eq!(
+                log * (log + 1) / 2,
+                SortingNetwork::odd_even_merge(width).depth()
+            );
+            assert_eq!(log * (log + 1) / 2, SortingNetwork::bitonic(width).depth());
+            assert_eq!(
+                width * log * (log + 1) / 4,
+                SortingNetwork::bitonic(width).size()
+            );
+        }
+    }
+
+    #[test]
+    fn verifier_rejects_broken_networks() {
+        for width in 2..=16 {
+            let network = SortingNetwork::best_known(width).unwrap();
+
+            for missing in 0..network.size() {
+                let mut comparators = network.comparators().to_vec();
+
+                comparators.remove(missing);
+
+                assert!(!SortingNetwork::new(width, comparators).is_sorting_network());
+            }
+        }
+    }
+
+    #[test]
+    fn networks_sort_all_permutations() {
+        fn permutations(items: &mut Vec<u32>, k: usize, check: &mut impl FnMut(&[u32])) {
+            if k == items.len() {
+                check(items);
+                return;
+            }
+
+            for i in k..items.len() {
+                items.swap(k, i);
+                permutations(items, k + 1, check);
+                items.swap(k, i);
+            }
+        }
+
+        for width in 0..=7 {
+            let best_known = SortingNetwork::best_known(width).unwrap();
+            let bitonic = SortingNetwork::bitonic(width);
+            let sorted = (0..width as u32).collect::<Vec<_>>();
+
+            permutations(&mut sorted.clone(), 0, &mut |permutation| {
+                let mut slice = permutation.to_vec();
+                let mut bitonic_slice = permutation.to_vec();
+
+                best_known.apply(&mut slice);
+                bitonic.apply(&mut bitonic_slice);
+
+                assert_eq!(sorted, slice);
+                assert_eq!(sorted, bitonic_slice);
+            });
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "comparator has to connect two indices")]
+    fn comparators_out_of_network_are_rejected() {
+        SortingNetwork::new(3, vec![(0, 3)]);
+    }
+}
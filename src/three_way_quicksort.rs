@@ -0,0 +1,278 @@
+//! Three-way quicksort algorithm implementation.
+
+use crate::prelude::*;
+use crate::tuning::Parameter;
+
+/// Struct implementing `Sorter` + `BenchmarkingSorter` using quicksort with
+/// Dijkstra's three-way partitioning. Slice is split into items smaller
+/// than, equal to and bigger than pivot, and equal items are never touched
+/// again, so input with `k` distinct values takes `O(n log k)` comparisons.
+/// Pivot is median of the first, middle and last item and recursion goes
+/// into the smaller part only, so stack depth stays `O(log n)`. Slices up
+/// to `Parameter::ThreeWayInsertionLen` items are sorted by insertion sort.
+pub struct ThreeWayQuicksort;
+
+impl ThreeWayQuicksort {
+    fn three_way_quicksort<T: Ord + Copy>(mut slice: &mut [T], insertion_len: usize) {
+        loop {
+            if slice.len() <= insertion_len {
+                InsertionSort::sort(slice);
+                return;
+            }
+
+            let pivot = ThreeWayQuicksort::median_of_three(slice);
+            let (lt, gt) = ThreeWayQuicksort::partition(slice, pivot);
+
+            let (smaller, rest) = slice.split_at_mut(lt);
+            let bigger = &mut rest[gt - lt..];
+
+            if smaller.len() < bigger.len() {
+                ThreeWayQuicksort::three_way_quicksort(smaller, insertion_len);
+                slice = bigger;
+            } else {
+                ThreeWayQuicksort::three_way_quicksort(bigger, insertion_len);
+                slice = smaller;
+            }
+        }
+    }
+
+    fn median_of_three<T: Ord + Copy>(slice: &[T]) -> T {
+        let a = slice[0];
+        let b = slice[slice.len() / 2];
+        let c = slice[slice.len() - 1];
+
+        if a < b {
+            if b < c {
+                b
+            } else if a < c {
+                c
+            } else {
+                a
+            }
+        } else if a < c {
+            a
+        } else if b < c {
+            c
+        } else {
+            b
+        }
+    }
+
+    /// Partitions slice into items smaller than `pivot`, equal to it and
+    /// bigger than it. Returns bounds `(lt, gt)` of equal items.
+    fn partition<T: Ord + Copy>(slice: &mut [T], pivot: T) -> (usize, usize) {
+        let mut lt = 0;
+        let mut i = 0;
+        let mut gt = slice.len();
+
+        while i < gt {
+            if slice[i] < pivot {
+                if lt != i {
+                    slice.swap(lt, i);
+                }
+
+                lt += 1;
+                i += 1;
+            } else if slice[i] > pivot {
+                gt -= 1;
+                slice.swap(i, gt);
+            } else {
+                i += 1;
+            }
+        }
+
+        (lt, gt)
+    }
+
+    fn three_way_quicksort_with_benchmark<T: Ord + Copy>(
+        mut slice: &mut [T],
+        insertion_len: usize,
+        benchmark: &mut impl Benchmark,
+    ) {
+        loop {
+            if slice.len() <= insertion_len {
+                InsertionSort::insertion_sort_with_benchmark(slice, benchmark);
+                return;
+            }
+
+            let pivot = ThreeWayQuicksort::median_of_three_with_benchmark(slice, benchmark);
+            let (lt, gt) = ThreeWayQuicksort::partition_with_benchmark(slice, pivot, benchmark);
+
+            let (smaller, rest) = slice.split_at_mut(lt);
+            let bigger = &mut rest[gt - lt..];
+
+            if smaller.len() < bigger.len() {
+                ThreeWayQuicksort::three_way_quicksort_with_benchmark(
+                    smaller,
+                    insertion_len,
+                    benchmark,
+                );
+                slice = bigger;
+            } else {
+                ThreeWayQuicksort::three_way_quicksort_with_benchmark(
+                    bigger,
+                    insertion_len,
+                    benchmark,
+                );
+                slice = smaller;
+            }
+        }
+    }
+
+    fn median_of_three_with_benchmark<T: Ord + Copy>(
+        slice: &[T],
+        benchmark: &mut impl Benchmark,
+    ) -> T {
+        let a = slice[0];
+        let b = slice[slice.len() / 2];
+        let c = slice[slice.len() - 1];
+
+        benchmark.add_cmp();
+
+        if a < b {
+            benchmark.add_cmp();
+
+            if b < c {
+                b
+            } else {
+                benchmark.add_cmp();
+
+                if a < c {
+                    c
+                } else {
+                    a
+                }
+            }
+        } else {
+            benchmark.add_cmp();
+
+            if a < c {
+                a
+            } else {
+                benchmark.add_cmp();
+
+                if b < c {
+                    c
+                } else {
+                    b
+                }
+            }
+        }
+    }
+
+    fn partition_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        pivot: T,
+        benchmark: &mut impl Benchmark,
+    ) -> (usize, usize) {
+        let mut lt = 0;
+        let mut i = 0;
+        let mut gt = slice.len();
+
+        benchmark.add_pass();
+
+        while i < gt {
+            benchmark.add_cmp();
+
+            if slice[i] < pivot {
+                if lt != i {
+                    benchmark.add_swap();
+                    benchmark.add_writes(2);
+                    slice.swap(lt, i);
+                }
+
+                lt += 1;
+                i += 1;
+            } else {
+                benchmark.add_cmp();
+
+                if slice[i] > pivot {
+                    gt -= 1;
+
+                    benchmark.add_swap();
+                    benchmark.add_writes(2);
+                    slice.swap(i, gt);
+                } else {
+                    i += 1;
+                }
+            }
+        }
+
+        (lt, gt)
+    }
+}
+
+impl Sorter for ThreeWayQuicksort {
+    /// Three-way quicksort implementation working on types implementing
+    /// `Ord` + `Copy` so it's mostly usefull for primitive types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    ///
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// ThreeWayQuicksort::sort(&mut slice);
+    ///
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// ```
+    fn sort<T: Ord + Copy>(slice: &mut [T]) {
+        ThreeWayQuicksort::three_way_quicksort(slice, Parameter::ThreeWayInsertionLen.get());
+    }
+}
+
+impl BenchmarkingSorter for ThreeWayQuicksort {
+    /// Three-way quicksort implementation with additional benchmarking
+    /// capabilities. Every partitioning is counted as a pass.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::StandardBenchmarker;
+    ///
+    /// let mut benchmarker = StandardBenchmarker::default();
+    /// let mut slice = [2, 1].repeat(10);
+    ///
+    /// ThreeWayQuicksort::sort_with_benchmark(&mut slice, &mut benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!([[1; 10], [2; 10]].concat(), slice);
+    /// assert_eq!(1, stats.passes);
+    /// ```
+    fn sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        benchmark.start_timer();
+
+        ThreeWayQuicksort::three_way_quicksort_with_benchmark(
+            slice,
+            Parameter::ThreeWayInsertionLen.get(),
+            benchmark,
+        );
+
+        benchmark.stop_timer();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conformance::count_comparisons;
+    use crate::generator::{RandomGenerator, SeededGenerator};
+
+    crate::sorter_conformance_tests!(ThreeWayQuicksort);
+
+    #[test]
+    fn few_distinct_values_take_linear_comparisons() {
+        for distinct in [1, 2, 4] {
+            let input = RandomGenerator::generate_seeded(10000, distinct as u64)
+                .into_iter()
+                .map(|item| item % distinct)
+                .collect::<Vec<_>>();
+
+            let three_way = count_comparisons::<ThreeWayQuicksort>(&input);
+
+            assert!(three_way <= 2 * 10000 * (distinct as usize + 1));
+            assert!(three_way < count_comparisons::<QuickSort>(&input));
+        }
+    }
+}
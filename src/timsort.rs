@@ -1,23 +1,31 @@
 //! Timsort algorithm implementation.
 
 use crate::prelude::*;
+use crate::tuning::Parameter;
+use std::marker::PhantomData;
 
 /// Struct implementing `Sorter` + `BenchmarkingSorter` using timsort
-/// to sort items. It uses mergesort with insertion sort for smaller problems.
-pub struct TimSort;
+/// to sort items. It uses mergesort with sorter `S` for problems up to
+/// `Parameter::TimSortCutoff` items.
+pub struct TimSortWith<S> {
+    small: PhantomData<S>,
+}
+
+/// Timsort using binary insertion sort for smaller problems.
+pub type TimSort = TimSortWith<BinaryInsertionSort>;
 
-impl TimSort {
-    fn tim_sort<T: Ord + Copy>(slice: &mut [T]) {
-        if slice.len() <= 10 {
-            InsertionSort::sort(slice);
+impl<S: Sorter + BenchmarkingSorter> TimSortWith<S> {
+    fn tim_sort<T: Ord + Copy>(slice: &mut [T], cutoff: usize) {
+        if slice.len() <= cutoff {
+            S::sort(slice);
             return;
         }
 
         let slice_mid = slice.len() / 2;
 
-        TimSort::tim_sort(&mut slice[..slice_mid]);
-        TimSort::tim_sort(&mut slice[slice_mid..]);
-        TimSort::merge(slice, slice_mid);
+        TimSortWith::<S>::tim_sort(&mut slice[..slice_mid], cutoff);
+        TimSortWith::<S>::tim_sort(&mut slice[slice_mid..], cutoff);
+        TimSortWith::<S>::merge(slice, slice_mid);
     }
 
     fn merge<T>(slice: &mut [T], slice_mid: usize)
@@ -30,38 +38,42 @@ impl TimSort {
         let mut first_slice_index = 0;
         let mut second_slice_index = 0;
 
-        for i in 0..slice.len() {
+        for item in slice.iter_mut() {
             if first_slice_index < first_slice.len() {
                 if second_slice_index < second_slice.len() {
                     if first_slice[first_slice_index] <= second_slice[second_slice_index] {
-                        slice[i] = first_slice[first_slice_index];
+                        *item = first_slice[first_slice_index];
                         first_slice_index += 1;
                     } else {
-                        slice[i] = second_slice[second_slice_index];
+                        *item = second_slice[second_slice_index];
                         second_slice_index += 1;
                     }
                 } else {
-                    slice[i] = first_slice[first_slice_index];
+                    *item = first_slice[first_slice_index];
                     first_slice_index += 1;
                 }
             } else {
-                slice[i] = second_slice[second_slice_index];
+                *item = second_slice[second_slice_index];
                 second_slice_index += 1;
             }
         }
     }
 
-    fn tim_sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
-        if slice.len() <= 10 {
-            InsertionSort::sort_with_benchmark(slice, benchmark);
+    fn tim_sort_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        cutoff: usize,
+        benchmark: &mut impl Benchmark,
+    ) {
+        if slice.len() <= cutoff {
+            S::sort_with_benchmark(slice, benchmark);
             return;
         }
 
         let slice_mid = slice.len() / 2;
 
-        TimSort::tim_sort_with_benchmark(&mut slice[..slice_mid], benchmark);
-        TimSort::tim_sort_with_benchmark(&mut slice[slice_mid..], benchmark);
-        TimSort::merge_with_benchmark(slice, slice_mid, benchmark);
+        TimSortWith::<S>::tim_sort_with_benchmark(&mut slice[..slice_mid], cutoff, benchmark);
+        TimSortWith::<S>::tim_sort_with_benchmark(&mut slice[slice_mid..], cutoff, benchmark);
+        TimSortWith::<S>::merge_with_benchmark(slice, slice_mid, benchmark);
     }
 
     fn merge_with_benchmark<T>(slice: &mut [T], slice_mid: usize, benchmark: &mut impl Benchmark)
@@ -71,56 +83,62 @@ impl TimSort {
         let first_slice = slice[..slice_mid].to_vec();
         let second_slice = slice[slice_mid..].to_vec();
 
+        benchmark.add_writes(slice.len());
+
         let mut first_slice_index = 0;
         let mut second_slice_index = 0;
 
-        for i in 0..slice.len() {
+        for item in slice.iter_mut() {
             if first_slice_index < first_slice.len() {
                 if second_slice_index < second_slice.len() {
                     benchmark.add_cmp();
 
                     if first_slice[first_slice_index] <= second_slice[second_slice_index] {
-                        slice[i] = first_slice[first_slice_index];
+                        *item = first_slice[first_slice_index];
                         first_slice_index += 1;
                     } else {
-                        slice[i] = second_slice[second_slice_index];
+                        *item = second_slice[second_slice_index];
                         second_slice_index += 1;
                     }
                 } else {
-                    slice[i] = first_slice[first_slice_index];
+                    *item = first_slice[first_slice_index];
                     first_slice_index += 1;
                 }
             } else {
-                slice[i] = second_slice[second_slice_index];
+                *item = second_slice[second_slice_index];
                 second_slice_index += 1;
             }
 
             benchmark.add_swap();
+            benchmark.add_writes(1);
         }
     }
 }
 
-impl Sorter for TimSort {
+impl<S: Sorter + BenchmarkingSorter> Sorter for TimSortWith<S> {
     /// Timsort implementation working on types implementing
     /// `Ord` + `Copy` so it's mostly usefull for primitive types.
-    /// It uses mergesort with insertion sort for smaller problems.
+    /// It uses mergesort with sorter `S` for smaller problems.
     ///
     /// Examples:
     /// ```
     /// use algorithms::prelude::*;
     ///
     /// let mut slice = [1, 8, 2, 3, 9, 5];
+    /// let mut network_slice = slice;
     ///
     /// TimSort::sort(&mut slice);
+    /// TimSortWith::<NetworkSort>::sort(&mut network_slice);
     ///
     /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// assert_eq!([1, 2, 3, 5, 8, 9], network_slice);
     /// ```
     fn sort<T: Ord + Copy>(slice: &mut [T]) {
-        TimSort::tim_sort(slice);
+        TimSortWith::<S>::tim_sort(slice, Parameter::TimSortCutoff.get());
     }
 }
 
-impl BenchmarkingSorter for TimSort {
+impl<S: Sorter + BenchmarkingSorter> BenchmarkingSorter for TimSortWith<S> {
     /// Timsort implementation with additional benchmarking capabilities.
     ///
     /// Examples:
@@ -135,41 +153,32 @@ impl BenchmarkingSorter for TimSort {
     ///
     /// let stats = benchmarker.get_stats();
     ///
-    /// assert_eq!(4, stats.comparisons);
+    /// assert_eq!(10, stats.comparisons);
     /// assert_eq!(4, stats.swaps);
     /// ```
     fn sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
         // benchmark.start_timer();
         // timer won't work for now
 
-        TimSort::tim_sort_with_benchmark(slice, benchmark);
+        TimSortWith::<S>::tim_sort_with_benchmark(slice, Parameter::TimSortCutoff.get(), benchmark);
 
         // benchmark.stop_timer();
     }
 }
 
+/// Timsort is stable only if its small-case sorter is, sorting networks
+/// aren't.
+impl<S: StableSorter + BenchmarkingSorter> StableSorter for TimSortWith<S> {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tests::*;
 
-    #[test]
-    fn quick_sort_basic_sorting_test() {
-        basic_sorting_test::<TimSort>();
-    }
-
-    #[test]
-    fn quick_sort_empty_sorting_test() {
-        empty_sorting_test::<TimSort>();
-    }
+    crate::sorter_conformance_tests!(TimSort, stable);
 
-    #[test]
-    fn quick_sort_sorted_sorting_test() {
-        sorted_sorting_test::<TimSort>();
-    }
+    mod network {
+        use super::*;
 
-    #[test]
-    fn quick_sort_sorted_backwards_sorting_test() {
-        sorted_backwards_sorting_test::<TimSort>();
+        crate::sorter_conformance_tests!(TimSortWith<NetworkSort>);
     }
 }
@@ -0,0 +1,517 @@
+//! Tunable parameters of hybrid sorters, such as insertion sort cutoffs,
+//! pivot sample sizes and block sizes.
+//!
+//! Every sorter reads current values of parameters once when it starts.
+//! They begin as `Tuning::COMPILED` and can be replaced at runtime by
+//! `Tuning::apply`.
+//! Configuration is text with one `name = value` per line, `#` starts a
+//! comment. It's picked up at compile time from `ALGORITHMS_TUNING`
+//! environment variable holding configuration text, e.g.
+//! `ALGORITHMS_TUNING="$(cat tuning.cfg)" cargo build`, or loaded at runtime
+//! by `Tuning::load`.
+//!
+//! Parameters cover cutoffs, sample sizes and block sizes of `TimSortWith`,
+//! `PdqSort`, `ThreeWayQuicksort`, `BlockPartitioning`, `MsdRadixSort`,
+//! `AdaptiveSort`, `SampleSort`, `Ips4oSort`, `MultiPivotQuicksort`,
+//! `BlockSort` and `BucketSort`. Pattern detection limits of `PdqSort`,
+//! `CUTOFF` const generics of parallel sorters and chunk sizes of oblivious
+//! sorters stay fixed.
+
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Tunable parameter of hybrid sorter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Parameter {
+    /// Slices up to this length are sorted by small sorter of `TimSortWith`.
+    TimSortCutoff,
+    /// Slices up to this length are sorted by insertion sort in `PdqSort`.
+    PdqInsertionLen,
+    /// Slices from this length take pivot from sample of 9 items instead of
+    /// 3 in `PdqSort`.
+    PdqNintherLen,
+    /// Slices up to this length are sorted by insertion sort in
+    /// `ThreeWayQuicksort`.
+    ThreeWayInsertionLen,
+    /// Number of items in one block of `BlockPartitioning`.
+    BlockPartitioningBlock,
+    /// Buckets up to this length are sorted by insertion sort in
+    /// `MsdRadixSort`.
+    MsdRadixInsertionLen,
+    /// Inputs up to this length are dispatched to insertion sort by
+    /// `AdaptiveSort`.
+    AdaptiveInsertionLen,
+    /// Slices up to this length are sorted by insertion sort in
+    /// `SampleSort` and `Ips4oSort`.
+    SampleSortBaseCaseLen,
+    /// Binary logarithm of maximal number of buckets of `SampleSort` and
+    /// `Ips4oSort`.
+    SampleSortMaxLogBuckets,
+    /// Approximate size in bytes of blocks moved by `Ips4oSort`.
+    Ips4oBlockBytes,
+    /// Distance between pivots in sorted sample of `MultiPivotQuicksort`.
+    MultiPivotSampleSpacing,
+    /// Slices up to this length are sorted by insertion sort in
+    /// `MultiPivotQuicksort`, unless twice its sample is longer.
+    MultiPivotInsertionLen,
+    /// Length of runs sorted by insertion sort before merging in
+    /// `BlockSort`.
+    BlockSortRunLen,
+    /// Average number of items in one bucket of `BucketSort`.
+    BucketSortBucketLen,
+}
+
+impl Parameter {
+    pub const ALL: [Parameter; 14] = [
+        Parameter::TimSortCutoff,
+        Parameter::PdqInsertionLen,
+        Parameter::PdqNintherLen,
+        Parameter::ThreeWayInsertionLen,
+        Parameter::BlockPartitioningBlock,
+        Parameter::MsdRadixInsertionLen,
+        Parameter::AdaptiveInsertionLen,
+        Parameter::SampleSortBaseCaseLen,
+        Parameter::SampleSortMaxLogBuckets,
+        Parameter::Ips4oBlockBytes,
+        Parameter::MultiPivotSampleSpacing,
+        Parameter::MultiPivotInsertionLen,
+        Parameter::BlockSortRunLen,
+        Parameter::BucketSortBucketLen,
+    ];
+
+    pub const COUNT: usize = Parameter::ALL.len();
+
+    /// Returns name of parameter used in configuration.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Parameter::TimSortCutoff => "tim_sort_cutoff",
+            Parameter::PdqInsertionLen => "pdq_insertion_len",
+            Parameter::PdqNintherLen => "pdq_ninther_len",
+            Parameter::ThreeWayInsertionLen => "three_way_insertion_len",
+            Parameter::BlockPartitioningBlock => "block_partitioning_block",
+            Parameter::MsdRadixInsertionLen => "msd_radix_insertion_len",
+            Parameter::AdaptiveInsertionLen => "adaptive_insertion_len",
+            Parameter::SampleSortBaseCaseLen => "sample_sort_base_case_len",
+            Parameter::SampleSortMaxLogBuckets => "sample_sort_max_log_buckets",
+            Parameter::Ips4oBlockBytes => "ips4o_block_bytes",
+            Parameter::MultiPivotSampleSpacing => "multi_pivot_sample_spacing",
+            Parameter::MultiPivotInsertionLen => "multi_pivot_insertion_len",
+            Parameter::BlockSortRunLen => "block_sort_run_len",
+            Parameter::BucketSortBucketLen => "bucket_sort_bucket_len",
+        }
+    }
+
+    /// Returns value of parameter hard-coded in sorters before tuning.
+    pub const fn default_value(self) -> usize {
+        match self {
+            Parameter::TimSortCutoff => 10,
+            Parameter::PdqInsertionLen => 20,
+            Parameter::PdqNintherLen => 50,
+            Parameter::ThreeWayInsertionLen => 16,
+            Parameter::BlockPartitioningBlock => 128,
+            Parameter::MsdRadixInsertionLen => 32,
+            Parameter::AdaptiveInsertionLen => 32,
+            Parameter::SampleSortBaseCaseLen => 16,
+            Parameter::SampleSortMaxLogBuckets => 6,
+            Parameter::Ips4oBlockBytes => 1024,
+            Parameter::MultiPivotSampleSpacing => 2,
+            Parameter::MultiPivotInsertionLen => 16,
+            Parameter::BlockSortRunLen => 16,
+            Parameter::BucketSortBucketLen => 1,
+        }
+    }
+
+    /// Returns range of values for which sorters work correctly.
+    pub const fn range(self) -> RangeInclusive<usize> {
+        match self {
+            Parameter::TimSortCutoff => 1..=64,
+            Parameter::PdqInsertionLen => 4..=128,
+            Parameter::PdqNintherLen => 8..=1024,
+            Parameter::ThreeWayInsertionLen => 1..=128,
+            // offsets in block have to fit into `u8`
+            Parameter::BlockPartitioningBlock => 8..=256,
+            Parameter::MsdRadixInsertionLen => 1..=256,
+            Parameter::AdaptiveInsertionLen => 1..=256,
+            // sample has to fit into slices longer than base case
+            Parameter::SampleSortBaseCaseLen => 4..=256,
+            // classes of `SampleSort` are stored in `u8`
+            Parameter::SampleSortMaxLogBuckets => 1..=7,
+            Parameter::Ips4oBlockBytes => 64..=16384,
+            // the last pivot has to be below the largest item of sample
+            Parameter::MultiPivotSampleSpacing => 2..=8,
+            Parameter::MultiPivotInsertionLen => 1..=256,
+            Parameter::BlockSortRunLen => 1..=64,
+            Parameter::BucketSortBucketLen => 1..=64,
+        }
+    }
+
+    /// Returns current value of parameter used by sorters.
+    pub fn get(self) -> usize {
+        VALUES[self as usize].load(Ordering::Relaxed)
+    }
+
+    const fn from_name(name: &[u8]) -> Option<Parameter> {
+        let mut i = 0;
+
+        while i < Parameter::COUNT {
+            let candidate = Parameter::ALL[i].name().as_bytes();
+
+            if candidate.len() == name.len() {
+                let mut j = 0;
+
+                while j < name.len() && candidate[j] == name[j] {
+                    j += 1;
+                }
+
+                if j == name.len() {
+                    return Some(Parameter::ALL[i]);
+                }
+            }
+
+            i += 1;
+        }
+
+        None
+    }
+}
+
+/// Error of parsing or validating tuning configuration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TuningError {
+    /// Line with given number isn't `name = value`.
+    Syntax { line: usize },
+    /// Line with given number names unknown parameter.
+    UnknownParameter { line: usize },
+    /// Value lies outside of range of parameter.
+    OutOfRange { parameter: Parameter, value: usize },
+}
+
+impl fmt::Display for TuningError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TuningError::Syntax { line } => {
+                write!(f, "line {} isn't in `name = value` form", line)
+            }
+            TuningError::UnknownParameter { line } => {
+                write!(f, "line {} names unknown parameter", line)
+            }
+            TuningError::OutOfRange { parameter, value } => write!(
+                f,
+                "value {} of {} is outside of {:?}",
+                value,
+                parameter.name(),
+                parameter.range()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TuningError {}
+
+/// Values of all tunable parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Tuning {
+    values: [usize; Parameter::COUNT],
+}
+
+impl Tuning {
+    /// Values hard-coded in sorters before tuning.
+    pub const DEFAULT: Tuning = {
+        let mut values = [0; Parameter::COUNT];
+        let mut i = 0;
+
+        while i < Parameter::COUNT {
+            values[i] = Parameter::ALL[i].default_value();
+            i += 1;
+        }
+
+        Tuning { values }
+    };
+
+    /// Values picked up at compile time from `ALGORITHMS_TUNING`
+    /// environment variable, parameters missing there keep their default.
+    pub const COMPILED: Tuning = match option_env!("ALGORITHMS_TUNING") {
+        Some(config) => match Tuning::parse(config) {
+            Ok(tuning) => tuning,
+            Err(_) => panic!("ALGORITHMS_TUNING isn't valid tuning configuration"),
+        },
+        None => Tuning::DEFAULT,
+    };
+
+    /// Returns values currently used by sorters.
+    pub fn current() -> Tuning {
+        let mut tuning = Tuning::DEFAULT;
+
+        for parameter in Parameter::ALL {
+            tuning.values[parameter as usize] = parameter.get();
+        }
+
+        tuning
+    }
+
+    /// Makes sorters in all threads use these values from now on. Sorters
+    /// running meanwhile keep values they read when they started.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::tuning::{Parameter, Tuning};
+    ///
+    /// let mut tuning = Tuning::current();
+    ///
+    /// tuning.set(Parameter::TimSortCutoff, 2).unwrap();
+    /// tuning.apply();
+    ///
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// TimSort::sort(&mut slice);
+    ///
+    /// assert_eq!(2, Parameter::TimSortCutoff.get());
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// ```
+    pub fn apply(&self) {
+        for parameter in Parameter::ALL {
+            VALUES[parameter as usize].store(self.get(parameter), Ordering::Relaxed);
+        }
+    }
+
+    pub fn get(&self, parameter: Parameter) -> usize {
+        self.values[parameter as usize]
+    }
+
+    /// Sets value of `parameter`. Fails if value is outside of its range.
+    pub fn set(&mut self, parameter: Parameter, value: usize) -> Result<(), TuningError> {
+        let range = parameter.range();
+
+        if value < *range.start() || value > *range.end() {
+            return Err(TuningError::OutOfRange { parameter, value });
+        }
+
+        self.values[parameter as usize] = value;
+
+        Ok(())
+    }
+
+    /// Parses configuration with one `name = value` per line, starting from
+    /// default values. Blank lines and comments starting with `#` are
+    /// skipped. It's `const` so that configuration can be parsed at compile
+    /// time.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::tuning::{Parameter, Tuning, TuningError};
+    ///
+    /// let tuning = Tuning::parse("# tuned\ntim_sort_cutoff = 16\n").unwrap();
+    ///
+    /// assert_eq!(16, tuning.get(Parameter::TimSortCutoff));
+    /// assert_eq!(20, tuning.get(Parameter::PdqInsertionLen));
+    /// assert_eq!(
+    ///     Err(TuningError::UnknownParameter { line: 1 }),
+    ///     Tuning::parse("cutoff = 16")
+    /// );
+    /// ```
+    pub const fn parse(config: &str) -> Result<Tuning, TuningError> {
+        let bytes = config.as_bytes();
+        let mut tuning = Tuning::DEFAULT;
+        let mut line = 1;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            i = Tuning::skip_spaces(bytes, i);
+
+            if i == bytes.len() || bytes[i] == b'\n' || bytes[i] == b'#' {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+
+                i += 1;
+                line += 1;
+                continue;
+            }
+
+            let name_start = i;
+
+            while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                i += 1;
+            }
+
+            let (_, rest) = bytes.split_at(name_start);
+            let (name, _) = rest.split_at(i - name_start);
+
+            i = Tuning::skip_spaces(bytes, i);
+
+            if name.is_empty() || i == bytes.len() || bytes[i] != b'=' {
+                return Err(TuningError::Syntax { line });
+            }
+
+            i = Tuning::skip_spaces(bytes, i + 1);
+
+            let value_start = i;
+            let mut value: usize = 0;
+
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                value = match value.checked_mul(10) {
+                    Some(value) => value.saturating_add((bytes[i] - b'0') as usize),
+                    None => usize::MAX,
+                };
+                i += 1;
+            }
+
+            i = Tuning::skip_spaces(bytes, i);
+
+            if i == value_start || (i < bytes.len() && bytes[i] != b'\n' && bytes[i] != b'#') {
+                return Err(TuningError::Syntax { line });
+            }
+
+            let parameter = match Parameter::from_name(name) {
+                Some(parameter) => parameter,
+                None => return Err(TuningError::UnknownParameter { line }),
+            };
+
+            let range = parameter.range();
+
+            if value < *range.start() || value > *range.end() {
+                return Err(TuningError::OutOfRange { parameter, value });
+            }
+
+            tuning.values[parameter as usize] = value;
+        }
+
+        Ok(tuning)
+    }
+
+    const fn skip_spaces(bytes: &[u8], mut i: usize) -> usize {
+        while i < bytes.len() && (bytes[i] == b' ' || bytes[i] == b'\t' || bytes[i] == b'\r') {
+            i += 1;
+        }
+
+        i
+    }
+
+    /// Loads configuration written by `save` from file at `path`.
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Tuning> {
+        let config = std::fs::read_to_string(path)?;
+
+        Tuning::parse(&config)
+            .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))
+    }
+
+    /// Saves configuration with values of all parameters to file at `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.to_string())
+    }
+}
+
+impl fmt::Display for Tuning {
+    /// Formats configuration with values of all parameters, which
+    /// `Tuning::parse` reads back.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for parameter in Parameter::ALL {
+            writeln!(f, "{} = {}", parameter.name(), self.get(parameter))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Current values of parameters, indexed by `Parameter`.
+static VALUES: [AtomicUsize; Parameter::COUNT] = {
+    let mut values = [const { AtomicUsize::new(0) }; Parameter::COUNT];
+    let mut i = 0;
+
+    while i < Parameter::COUNT {
+        values[i] = AtomicUsize::new(Tuning::COMPILED.values[i]);
+        i += 1;
+    }
+
+    values
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_are_in_range() {
+        for parameter in Parameter::ALL {
+            assert!(parameter.range().contains(&parameter.default_value()));
+            assert_eq!(
+                Some(parameter),
+                Parameter::from_name(parameter.name().as_bytes())
+            );
+        }
+    }
+
+    #[test]
+    fn formatted_configuration_parses_back() {
+        let mut tuning = Tuning::DEFAULT;
+
+        for (i, parameter) in Parameter::ALL.into_iter().enumerate() {
+            let range = parameter.range();
+
+            tuning
+                .set(parameter, (*range.start() + i).min(*range.end()))
+                .unwrap();
+        }
+
+        assert_eq!(Ok(tuning), Tuning::parse(&tuning.to_string()));
+        assert_eq!(Ok(Tuning::DEFAULT), Tuning::parse(""));
+    }
+
+    #[test]
+    fn invalid_configuration_is_rejected() {
+        for (config, error) in [
+            ("tim_sort_cutoff 10", TuningError::Syntax { line: 1 }),
+            ("\n\ntim_sort_cutoff = ", TuningError::Syntax { line: 3 }),
+            ("tim_sort_cutoff = 1 0", TuningError::Syntax { line: 1 }),
+            ("= 10", TuningError::Syntax { line: 1 }),
+            (
+                "# comment\nunknown = 10",
+                TuningError::UnknownParameter { line: 2 },
+            ),
+            (
+                "tim_sort_cutoff = 0",
+                TuningError::OutOfRange {
+                    parameter: Parameter::TimSortCutoff,
+                    value: 0,
+                },
+            ),
+            (
+                "block_partitioning_block = 99999999999999999999999",
+                TuningError::OutOfRange {
+                    parameter: Parameter::BlockPartitioningBlock,
+                    value: usize::MAX,
+                },
+            ),
+        ] {
+            assert_eq!(Err(error), Tuning::parse(config), "{:?}", config);
+        }
+    }
+
+    #[test]
+    fn comments_and_spaces_are_skipped() {
+        let tuning = Tuning::parse(
+            "# tuned configuration\r\n\n  pdq_insertion_len\t=  24  # insertion\r\npdq_ninther_len=64",
+        )
+        .unwrap();
+
+        assert_eq!(24, tuning.get(Parameter::PdqInsertionLen));
+        assert_eq!(64, tuning.get(Parameter::PdqNintherLen));
+    }
+
+    #[test]
+    fn set_rejects_values_out_of_range() {
+        let mut tuning = Tuning::DEFAULT;
+
+        assert_eq!(
+            Err(TuningError::OutOfRange {
+                parameter: Parameter::BlockPartitioningBlock,
+                value: 257,
+            }),
+            tuning.set(Parameter::BlockPartitioningBlock, 257)
+        );
+        assert_eq!(Tuning::DEFAULT, tuning);
+    }
+}
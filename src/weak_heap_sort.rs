@@ -0,0 +1,708 @@
+//! Weak-heap sort and adaptive weak-heap sort algorithms implementation.
+
+use std::mem;
+
+use crate::prelude::*;
+
+/// Reverse bits of weak heap nodes, one bit per item.
+struct ReverseBits {
+    words: Vec<u64>,
+}
+
+impl ReverseBits {
+    fn new(len: usize) -> Self {
+        ReverseBits {
+            words: vec![0; len.div_ceil(64)],
+        }
+    }
+
+    fn get(&self, node: usize) -> usize {
+        (self.words[node / 64] >> (node % 64) & 1) as usize
+    }
+
+    fn flip(&mut self, node: usize) {
+        self.words[node / 64] ^= 1 << (node % 64);
+    }
+
+    fn clear(&mut self, node: usize) {
+        self.words[node / 64] &= !(1 << (node % 64));
+    }
+}
+
+/// Struct implementing `Sorter` + `BenchmarkingSorter` using weak-heap sort
+/// to sort items. Weak heap is a binary tree where every node is bigger than
+/// all nodes in its right subtree, root has no left subtree and children of
+/// a node are swapped by flipping its reverse bit. Node `i` has children
+/// `2i + r[i]` and `2i + 1 - r[i]`. Building weak heap takes exactly `n - 1`
+/// comparisons and every extraction `ceil(log n)` at most, so sorting takes
+/// at most `n log n - 0.9n` comparisons, the fewest of heap sorts. It isn't
+/// adaptive, presorted input takes the same number of comparisons, see
+/// `AdaptiveWeakHeapSort` for that.
+pub struct WeakHeapSort;
+
+impl WeakHeapSort {
+    fn weak_heap_sort<T: Ord + Copy>(slice: &mut [T]) {
+        if slice.len() < 2 {
+            return;
+        }
+
+        let mut bits = ReverseBits::new(slice.len());
+
+        for node in (1..slice.len()).rev() {
+            let ancestor = WeakHeapSort::distinguished_ancestor(&bits, node);
+
+            WeakHeapSort::join(slice, &mut bits, ancestor, node);
+        }
+
+        for end in (2..slice.len()).rev() {
+            slice.swap(0, end);
+
+            // the new root is joined with the left spine of its right subtree
+            let mut node = 1;
+
+            while 2 * node + bits.get(node) < end {
+                node = 2 * node + bits.get(node);
+            }
+
+            while node > 0 {
+                WeakHeapSort::join(&mut slice[..end], &mut bits, 0, node);
+                node /= 2;
+            }
+        }
+
+        slice.swap(0, 1);
+    }
+
+    /// Returns parent of the first ancestor of `node`, including itself,
+    /// which is a right child.
+    fn distinguished_ancestor(bits: &ReverseBits, mut node: usize) -> usize {
+        while node & 1 == bits.get(node / 2) {
+            node /= 2;
+        }
+
+        node / 2
+    }
+
+    /// Joins weak heaps rooted at `ancestor` and `node`, where `node` is the
+    /// root of right subtree of `ancestor`'s weak heap.
+    fn join<T: Ord + Copy>(slice: &mut [T], bits: &mut ReverseBits, ancestor: usize, node: usize) {
+        if slice[ancestor] < slice[node] {
+            slice.swap(ancestor, node);
+            bits.flip(node);
+        }
+    }
+
+    fn weak_heap_sort_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        benchmark: &mut impl Benchmark,
+    ) {
+        if slice.len() < 2 {
+            return;
+        }
+
+        let mut bits = ReverseBits::new(slice.len());
+
+        benchmark.add_allocation(bits.words.len() * mem::size_of::<u64>());
+
+        for node in (1..slice.len()).rev() {
+            let ancestor = WeakHeapSort::distinguished_ancestor(&bits, node);
+
+            WeakHeapSort::join_with_benchmark(slice, &mut bits, ancestor, node, benchmark);
+        }
+
+        benchmark.add_pass();
+
+        for end in (2..slice.len()).rev() {
+            benchmark.add_swap();
+            benchmark.add_writes(2);
+            slice.swap(0, end);
+
+            // the new root is joined with the left spine of its right subtree
+            let mut node = 1;
+
+            while 2 * node + bits.get(node) < end {
+                node = 2 * node + bits.get(node);
+            }
+
+            while node > 0 {
+                WeakHeapSort::join_with_benchmark(&mut slice[..end], &mut bits, 0, node, benchmark);
+                node /= 2;
+            }
+        }
+
+        benchmark.add_swap();
+        benchmark.add_writes(2);
+        slice.swap(0, 1);
+
+        benchmark.add_pass();
+    }
+
+    fn join_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        bits: &mut ReverseBits,
+        ancestor: usize,
+        node: usize,
+        benchmark: &mut impl Benchmark,
+    ) {
+        benchmark.add_cmp();
+
+        if slice[ancestor] < slice[node] {
+            benchmark.add_swap();
+            benchmark.add_writes(2);
+            slice.swap(ancestor, node);
+            bits.flip(node);
+        }
+    }
+}
+
+impl Sorter for WeakHeapSort {
+    /// Weak-heap sort implementation working on types implementing
+    /// `Ord` + `Copy` so it's mostly usefull for primitive types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    ///
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// WeakHeapSort::sort(&mut slice);
+    ///
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// ```
+    fn sort<T: Ord + Copy>(slice: &mut [T]) {
+        WeakHeapSort::weak_heap_sort(slice);
+    }
+}
+
+impl BenchmarkingSorter for WeakHeapSort {
+    /// Weak-heap sort implementation with additional benchmarking
+    /// capabilities. Reverse bits are counted as allocation, building the
+    /// weak heap and extracting items are counted as passes.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::StandardBenchmarker;
+    ///
+    /// let mut benchmarker = StandardBenchmarker::default();
+    /// let mut slice = (0..1024).rev().collect::<Vec<_>>();
+    ///
+    /// WeakHeapSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!((0..1024).collect::<Vec<_>>(), slice);
+    /// assert!(stats.comparisons <= 1024 * 10);
+    /// assert_eq!(128, stats.allocated_bytes);
+    /// ```
+    fn sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        benchmark.start_timer();
+
+        WeakHeapSort::weak_heap_sort_with_benchmark(slice, benchmark);
+
+        benchmark.stop_timer();
+    }
+}
+
+/// Missing child in Cartesian tree.
+const NONE: usize = usize::MAX;
+
+/// Weak heap of indices into slice ordered by items they point to, used as
+/// priority queue by `AdaptiveWeakHeapSort`.
+struct WeakHeapQueue {
+    nodes: Vec<usize>,
+    bits: ReverseBits,
+}
+
+impl WeakHeapQueue {
+    fn new(capacity: usize) -> Self {
+        WeakHeapQueue {
+            nodes: Vec::with_capacity(capacity),
+            bits: ReverseBits::new(capacity),
+        }
+    }
+
+    /// Adds `index` as new leaf, which is joined with its distinguished
+    /// ancestors for as long as it's bigger than them.
+    fn push<T: Ord + Copy>(&mut self, slice: &[T], index: usize) {
+        let mut node = self.nodes.len();
+
+        self.nodes.push(index);
+        self.bits.clear(node);
+
+        while node > 0 {
+            let ancestor = WeakHeapSort::distinguished_ancestor(&self.bits, node);
+
+            if !self.join(slice, ancestor, node) {
+                break;
+            }
+
+            node = ancestor;
+        }
+    }
+
+    /// Removes index of the biggest item. The last leaf takes place of the
+    /// root and is joined with the left spine of its right subtree.
+    fn pop<T: Ord + Copy>(&mut self, slice: &[T]) -> Option<usize> {
+        let last = self.nodes.pop()?;
+
+        if self.nodes.is_empty() {
+            return Some(last);
+        }
+
+        let top = mem::replace(&mut self.nodes[0], last);
+        let len = self.nodes.len();
+
+        if len > 1 {
+            let mut node = 1;
+
+            while 2 * node + self.bits.get(node) < len {
+                node = 2 * node + self.bits.get(node);
+            }
+
+            while node > 0 {
+                self.join(slice, 0, node);
+                node /= 2;
+            }
+        }
+
+        Some(top)
+    }
+
+    /// Joins weak heaps rooted at `ancestor` and `node`. Returns whether
+    /// their roots were swapped.
+    fn join<T: Ord + Copy>(&mut self, slice: &[T], ancestor: usize, node: usize) -> bool {
+        if slice[self.nodes[ancestor]] < slice[self.nodes[node]] {
+            self.nodes.swap(ancestor, node);
+            self.bits.flip(node);
+
+            true
+        } else {
+            false
+        }
+    }
+
+    fn push_with_benchmark<T: Ord + Copy>(
+        &mut self,
+        slice: &[T],
+        index: usize,
+        benchmark: &mut impl Benchmark,
+    ) {
+        let mut node = self.nodes.len();
+
+        self.nodes.push(index);
+        self.bits.clear(node);
+
+        while node > 0 {
+            let ancestor = WeakHeapSort::distinguished_ancestor(&self.bits, node);
+
+            if !self.join_with_benchmark(slice, ancestor, node, benchmark) {
+                break;
+            }
+
+            node = ancestor;
+        }
+    }
+
+    fn pop_with_benchmark<T: Ord + Copy>(
+        &mut self,
+        slice: &[T],
+        benchmark: &mut impl Benchmark,
+    ) -> Option<usize> {
+        let last = self.nodes.pop()?;
+
+        if self.nodes.is_empty() {
+            return Some(last);
+        }
+
+        let top = mem::replace(&mut self.nodes[0], last);
+        let len = self.nodes.len();
+
+        if len > 1 {
+            let mut node = 1;
+
+            while 2 * node + self.bits.get(node) < len {
+                node = 2 * node + self.bits.get(node);
+            }
+
+            while node > 0 {
+                self.join_with_benchmark(slice, 0, node, benchmark);
+                node /= 2;
+            }
+        }
+
+        Some(top)
+    }
+
+    fn join_with_benchmark<T: Ord + Copy>(
+        &mut self,
+        slice: &[T],
+        ancestor: usize,
+        node: usize,
+        benchmark: &mut impl Benchmark,
+    ) -> bool {
+        benchmark.add_cmp();
+
+        if slice[self.nodes[ancestor]] < slice[self.nodes[node]] {
+            benchmark.add_swap();
+            self.nodes.swap(ancestor, node);
+            self.bits.flip(node);
+
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Struct implementing `Sorter` + `BenchmarkingSorter` using adaptive
+/// heapsort of Levcopoulos and Petersson with weak heap as priority queue.
+/// Items are first linked into Cartesian tree, where every item is at least
+/// as big as items in its subtrees. Starting from its root, the biggest
+/// item in the queue is repeatedly moved to the end of output and replaced
+/// by its children, so the queue only holds items whose bigger neighbours
+/// are already sorted. Presorted input keeps the queue small and takes
+/// linear number of comparisons, any input takes `O(n log n)`. Unlike
+/// `WeakHeapSort` it isn't in-place, it takes `O(n)` extra space for buffer
+/// of items and four indices per item, two children in Cartesian tree, one
+/// on its spine while building it and one in the queue.
+pub struct AdaptiveWeakHeapSort;
+
+impl AdaptiveWeakHeapSort {
+    fn adaptive_weak_heap_sort<T: Ord + Copy>(slice: &mut [T]) {
+        if slice.len() < 2 {
+            return;
+        }
+
+        let (root, left, right) = AdaptiveWeakHeapSort::cartesian_tree(slice);
+        let mut queue = WeakHeapQueue::new(slice.len());
+        let mut buffer = slice.to_vec();
+        let mut end = slice.len();
+
+        queue.push(slice, root);
+
+        while let Some(index) = queue.pop(slice) {
+            end -= 1;
+            buffer[end] = slice[index];
+
+            for child in [left[index], right[index]] {
+                if child != NONE {
+                    queue.push(slice, child);
+                }
+            }
+        }
+
+        slice.copy_from_slice(&buffer);
+    }
+
+    /// Builds Cartesian tree of slice by keeping its right spine on stack.
+    /// Returns its root and left and right child of every item.
+    fn cartesian_tree<T: Ord + Copy>(slice: &[T]) -> (usize, Vec<usize>, Vec<usize>) {
+        let mut left = vec![NONE; slice.len()];
+        let mut right = vec![NONE; slice.len()];
+        let mut spine = Vec::with_capacity(slice.len());
+
+        for index in 0..slice.len() {
+            let mut last = NONE;
+
+            while let Some(&top) = spine.last() {
+                if slice[top] >= slice[index] {
+                    break;
+                }
+
+                last = top;
+                spine.pop();
+            }
+
+            left[index] = last;
+
+            if let Some(&top) = spine.last() {
+                right[top] = index;
+            }
+
+            spine.push(index);
+        }
+
+        (spine[0], left, right)
+    }
+
+    fn adaptive_weak_heap_sort_with_benchmark<T: Ord + Copy>(
+        slice: &mut [T],
+        benchmark: &mut impl Benchmark,
+    ) {
+        if slice.len() < 2 {
+            return;
+        }
+
+        let (root, left, right) =
+            AdaptiveWeakHeapSort::cartesian_tree_with_benchmark(slice, benchmark);
+        let mut queue = WeakHeapQueue::new(slice.len());
+        let mut buffer = slice.to_vec();
+        let mut end = slice.len();
+
+        benchmark.add_allocation(slice.len() * mem::size_of::<usize>());
+        benchmark.add_allocation(queue.bits.words.len() * mem::size_of::<u64>());
+        benchmark.add_allocation(mem::size_of_val(slice));
+        benchmark.add_pass();
+
+        queue.push_with_benchmark(slice, root, benchmark);
+
+        while let Some(index) = queue.pop_with_benchmark(slice, benchmark) {
+            end -= 1;
+            buffer[end] = slice[index];
+
+            benchmark.add_move();
+            benchmark.add_writes(1);
+
+            for child in [left[index], right[index]] {
+                if child != NONE {
+                    queue.push_with_benchmark(slice, child, benchmark);
+                }
+            }
+        }
+
+        benchmark.add_pass();
+
+        slice.copy_from_slice(&buffer);
+
+        for _ in 0..slice.len() {
+            benchmark.add_swap();
+            benchmark.add_move();
+            benchmark.add_writes(1);
+        }
+    }
+
+    fn cartesian_tree_with_benchmark<T: Ord + Copy>(
+        slice: &[T],
+        benchmark: &mut impl Benchmark,
+    ) -> (usize, Vec<usize>, Vec<usize>) {
+        let mut left = vec![NONE; slice.len()];
+        let mut right = vec![NONE; slice.len()];
+        let mut spine = Vec::with_capacity(slice.len());
+
+        for _ in 0..3 {
+            benchmark.add_allocation(slice.len() * mem::size_of::<usize>());
+        }
+
+        benchmark.add_pass();
+
+        for index in 0..slice.len() {
+            let mut last = NONE;
+
+            while let Some(&top) = spine.last() {
+                benchmark.add_cmp();
+
+                if slice[top] >= slice[index] {
+                    break;
+                }
+
+                last = top;
+                spine.pop();
+            }
+
+            left[index] = last;
+
+            if let Some(&top) = spine.last() {
+                right[top] = index;
+            }
+
+            spine.push(index);
+        }
+
+        (spine[0], left, right)
+    }
+}
+
+impl Sorter for AdaptiveWeakHeapSort {
+    /// Adaptive weak-heap sort implementation working on types implementing
+    /// `Ord` + `Copy` so it's mostly usefull for primitive types.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    ///
+    /// let mut slice = [1, 8, 2, 3, 9, 5];
+    ///
+    /// AdaptiveWeakHeapSort::sort(&mut slice);
+    ///
+    /// assert_eq!([1, 2, 3, 5, 8, 9], slice);
+    /// ```
+    fn sort<T: Ord + Copy>(slice: &mut [T]) {
+        AdaptiveWeakHeapSort::adaptive_weak_heap_sort(slice);
+    }
+}
+
+impl BenchmarkingSorter for AdaptiveWeakHeapSort {
+    /// Adaptive weak-heap sort implementation with additional benchmarking
+    /// capabilities. Building Cartesian tree, extracting items into buffer
+    /// and copying them back are counted as passes, swaps of indices in the
+    /// queue and writes back into slice as swaps. Cartesian tree, queue and
+    /// buffer are counted as allocation.
+    ///
+    /// Examples:
+    /// ```
+    /// use algorithms::prelude::*;
+    /// use algorithms::benchmarking::StandardBenchmarker;
+    ///
+    /// let mut benchmarker = StandardBenchmarker::default();
+    /// let mut slice = (0..1024).collect::<Vec<_>>();
+    ///
+    /// AdaptiveWeakHeapSort::sort_with_benchmark(&mut slice, &mut benchmarker);
+    ///
+    /// let stats = benchmarker.get_stats();
+    ///
+    /// assert_eq!((0..1024).collect::<Vec<_>>(), slice);
+    /// assert_eq!(1023, stats.comparisons);
+    /// assert_eq!(3, stats.passes);
+    /// assert_eq!(4 * 1024 * 8 + 128 + 1024 * 4, stats.allocated_bytes);
+    /// ```
+    fn sort_with_benchmark<T: Ord + Copy>(slice: &mut [T], benchmark: &mut impl Benchmark) {
+        benchmark.start_timer();
+
+        AdaptiveWeakHeapSort::adaptive_weak_heap_sort_with_benchmark(slice, benchmark);
+
+        benchmark.stop_timer();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarking::StandardBenchmarker;
+    use crate::conformance::count_comparisons;
+    use crate::generator::{
+        AscendingGenerator, DescendingGenerator, RandomGenerator, SeededGenerator,
+    };
+
+    crate::sorter_conformance_tests!(WeakHeapSort);
+
+    fn comparisons(input: &[u32]) -> usize {
+        let mut benchmarker = StandardBenchmarker::default();
+
+        WeakHeapSort::sort_with_benchmark(&mut input.to_vec(), &mut benchmarker);
+
+        benchmarker.get_stats().comparisons
+    }
+
+    #[test]
+    fn comparisons_stay_below_n_log_n() {
+        for n in [2usize, 3, 10, 1000, 100000] {
+            let levels = n.next_power_of_two().ilog2() as usize;
+            let bound = n * levels - (1 << levels) + n - 1;
+
+            for input in [
+                RandomGenerator::generate_seeded(n, 1),
+                AscendingGenerator::generate_seeded(n, 1),
+                DescendingGenerator::generate_seeded(n, 1),
+            ] {
+                assert!(comparisons(&input) <= bound, "{} items", n);
+            }
+        }
+    }
+
+    #[test]
+    fn building_weak_heap_compares_every_node_once() {
+        let mut slice = RandomGenerator::generate_seeded(1000, 2);
+        let mut bits = ReverseBits::new(slice.len());
+        let mut benchmarker = StandardBenchmarker::default();
+
+        for node in (1..slice.len()).rev() {
+            let ancestor = WeakHeapSort::distinguished_ancestor(&bits, node);
+
+            WeakHeapSort::join_with_benchmark(
+                &mut slice,
+                &mut bits,
+                ancestor,
+                node,
+                &mut benchmarker,
+            );
+        }
+
+        assert_eq!(999, benchmarker.get_stats().comparisons);
+        assert_eq!(slice.iter().max(), Some(&slice[0]));
+    }
+
+    mod adaptive {
+        use super::*;
+
+        crate::sorter_conformance_tests!(AdaptiveWeakHeapSort);
+    }
+
+    #[test]
+    fn queue_pops_biggest_items_first() {
+        let slice = RandomGenerator::generate_seeded(1000, 3);
+        let mut queue = WeakHeapQueue::new(slice.len());
+        let mut popped = Vec::new();
+
+        for index in 0..slice.len() {
+            queue.push(&slice, index);
+
+            if index % 3 == 0 {
+                popped.push(slice[queue.pop(&slice).unwrap()]);
+            }
+        }
+
+        let mut rest = Vec::new();
+
+        while let Some(index) = queue.pop(&slice) {
+            rest.push(slice[index]);
+        }
+
+        assert!(rest.windows(2).all(|pair| pair[0] >= pair[1]));
+
+        popped.extend(rest);
+        popped.sort();
+
+        let mut expected = slice.clone();
+        expected.sort();
+
+        assert_eq!(expected, popped);
+    }
+
+    #[test]
+    fn adaptive_presorted_input_takes_linear_comparisons() {
+        for n in [10usize, 1000, 100000] {
+            for input in [
+                AscendingGenerator::generate_seeded(n, 1),
+                DescendingGenerator::generate_seeded(n, 1),
+            ] {
+                assert!(
+                    count_comparisons::<AdaptiveWeakHeapSort>(&input) <= 3 * n,
+                    "{} items",
+                    n
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn adaptive_takes_linear_extra_space() {
+        for n in [10usize, 1000, 100000] {
+            let mut benchmarker = StandardBenchmarker::default();
+
+            AdaptiveWeakHeapSort::sort_with_benchmark(
+                &mut RandomGenerator::generate_seeded(n, 1),
+                &mut benchmarker,
+            );
+
+            let per_item = 4 * mem::size_of::<usize>() + mem::size_of::<u32>();
+            let bits = n.div_ceil(64) * mem::size_of::<u64>();
+
+            assert_eq!(n * per_item + bits, benchmarker.get_stats().allocated_bytes);
+        }
+    }
+
+    #[test]
+    fn adaptive_worst_case_takes_n_log_n_comparisons() {
+        for n in [10usize, 1000, 100000] {
+            let bound = 4 * n * n.ilog2() as usize;
+            let input = RandomGenerator::generate_seeded(n, 1);
+
+            assert!(
+                count_comparisons::<AdaptiveWeakHeapSort>(&input) <= bound,
+                "{} items",
+                n
+            );
+        }
+    }
+}
@@ -0,0 +1,29 @@
+//! Applying tuning changes process-wide parameters, so it's checked in its
+//! own test binary, where no other test sorts meanwhile.
+
+use algorithms::conformance::count_comparisons;
+use algorithms::generator::{RandomGenerator, SeededGenerator};
+use algorithms::prelude::*;
+use algorithms::tuning::{Parameter, Tuning};
+
+#[test]
+fn applied_tuning_changes_comparisons() {
+    let input = RandomGenerator::generate_seeded(1000, 0);
+    let mut tuning = Tuning::DEFAULT;
+
+    tuning.set(Parameter::TimSortCutoff, 2).unwrap();
+    tuning.set(Parameter::PdqInsertionLen, 64).unwrap();
+
+    // `count_comparisons` checks that output is sorted
+    Tuning::DEFAULT.apply();
+    let timsort = count_comparisons::<TimSort>(&input);
+    let pdqsort = count_comparisons::<PdqSort>(&input);
+
+    tuning.apply();
+    let tuned_timsort = count_comparisons::<TimSort>(&input);
+    let tuned_pdqsort = count_comparisons::<PdqSort>(&input);
+
+    assert_eq!(tuning, Tuning::current());
+    assert_ne!(timsort, tuned_timsort);
+    assert_ne!(pdqsort, tuned_pdqsort);
+}